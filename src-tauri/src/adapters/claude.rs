@@ -1,8 +1,15 @@
-use crate::core::{ConfigAdapter, ConfigScope, McpServer, Skill};
+use crate::core::{
+    delete_md_command, managed_settings_path, read_marketplaces, read_md_commands, read_plugins,
+    render_agent_md, render_skill_md, scan_agents_dir, scan_skills_dir, write_md_command, Agent,
+    AgentScan, ApprovalPolicy, ClaudePlugins, Command, CommandCapabilities, ConfigAdapter,
+    ConfigScope, HookConfig, McpCapabilities, McpServer, ModelCapabilities, ModelSettings,
+    PermissionCapabilities, Permissions, RulesCapabilities, SandboxMode, SettingsLayer,
+    SettingsLayerFile, Skill, SkillCapabilities, SkillScan, ToolCapabilities, Transport,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn validate_name(name: &str) -> Result<(), String> {
     use std::path::Component;
@@ -34,26 +41,32 @@ struct ClaudeMcpServerEntry {
     env: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct SkillFrontmatter {
-    name: String,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
+/// The `permissions` object of `settings.json`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct ClaudePermissions {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ask: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deny: Vec<String>,
+    #[serde(default, rename = "defaultMode", skip_serializing_if = "Option::is_none")]
+    default_mode: Option<String>,
+    /// `additionalDirectories`, `disableBypassPermissionsMode`, ...
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
-fn parse_skill_frontmatter(content: &str) -> Option<(SkillFrontmatter, String)> {
-    let content = content.trim_start();
-    if !content.starts_with("---") {
-        return None;
-    }
-    let rest = &content[3..];
-    let end = rest.find("\n---")?;
-    let yaml = &rest[..end];
-    let body = rest[end + 4..].trim_start().to_string();
-    let fm: SkillFrontmatter = serde_yaml::from_str(yaml).ok()?;
-    Some((fm, body))
-}
+/// `defaultMode` values and what they mean in the normalized model. Plan
+/// mode never edits, so it maps to a read-only sandbox.
+const PERMISSION_MODES: &[(&str, Option<ApprovalPolicy>, Option<SandboxMode>)] = &[
+    ("default", Some(ApprovalPolicy::Untrusted), None),
+    ("acceptEdits", Some(ApprovalPolicy::OnRequest), None),
+    ("bypassPermissions", Some(ApprovalPolicy::Never), None),
+    ("plan", None, Some(SandboxMode::ReadOnly)),
+];
 
+/// Config paths, `settings.json` layers, plugins and subagents.
 impl ClaudeAdapter {
     fn mcp_path(&self, scope: &ConfigScope) -> PathBuf {
         match scope {
@@ -69,6 +82,13 @@ impl ClaudeAdapter {
         }
     }
 
+    fn prompts_dir(&self, scope: &ConfigScope) -> PathBuf {
+        match scope {
+            ConfigScope::Global => self.global_config_path().join("commands"),
+            ConfigScope::Project(p) => self.project_config_path(p).join("commands"),
+        }
+    }
+
     fn rules_path(&self, scope: &ConfigScope) -> PathBuf {
         match scope {
             ConfigScope::Global => dirs::home_dir()
@@ -78,6 +98,414 @@ impl ClaudeAdapter {
             ConfigScope::Project(p) => p.join("CLAUDE.md"),
         }
     }
+
+    pub fn settings_path(&self, scope: &ConfigScope) -> PathBuf {
+        match scope {
+            ConfigScope::Global => self.global_config_path().join("settings.json"),
+            ConfigScope::Project(p) => self.project_config_path(p).join("settings.json"),
+        }
+    }
+
+    /// The whole `settings.json` object; empty when the file is missing.
+    pub fn read_settings(
+        &self,
+        scope: &ConfigScope,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+        read_settings_file(&self.settings_path(scope))
+    }
+
+    /// Sets (or with `None`, removes) one top-level key of `settings.json`,
+    /// leaving the others as they are.
+    pub fn write_setting(
+        &self,
+        key: &str,
+        value: Option<serde_json::Value>,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        write_setting_file(&self.settings_path(scope), key, value)
+    }
+
+    /// The file behind a settings layer. Project and local layers need a
+    /// project.
+    pub fn settings_layer_path(
+        &self,
+        layer: SettingsLayer,
+        project: Option<&Path>,
+    ) -> Result<PathBuf, String> {
+        let project_dir = || {
+            project
+                .map(|p| self.project_config_path(&p.to_path_buf()))
+                .ok_or_else(|| format!("The {:?} settings layer needs a project", layer))
+        };
+        match layer {
+            SettingsLayer::User => Ok(self.settings_path(&ConfigScope::Global)),
+            SettingsLayer::Project => Ok(project_dir()?.join("settings.json")),
+            SettingsLayer::Local => Ok(project_dir()?.join("settings.local.json")),
+            SettingsLayer::Managed => Ok(managed_settings_path()),
+        }
+    }
+
+    /// Every layer that applies, lowest precedence first. A file that fails
+    /// to parse is reported in its entry rather than failing the whole read.
+    pub fn read_settings_layers(&self, project: Option<&Path>) -> Vec<SettingsLayerFile> {
+        let mut layers = vec![SettingsLayer::User];
+        if project.is_some() {
+            layers.extend([SettingsLayer::Project, SettingsLayer::Local]);
+        }
+        layers.push(SettingsLayer::Managed);
+        layers
+            .into_iter()
+            .filter_map(|layer| {
+                let path = self.settings_layer_path(layer, project).ok()?;
+                let (settings, error) = match read_settings_file(&path) {
+                    Ok(s) => (s, None),
+                    Err(e) => (Default::default(), Some(e)),
+                };
+                Some(SettingsLayerFile { layer, exists: path.is_file(), path, settings, error })
+            })
+            .collect()
+    }
+
+    pub fn write_layer_setting(
+        &self,
+        layer: SettingsLayer,
+        project: Option<&Path>,
+        key: &str,
+        value: Option<serde_json::Value>,
+    ) -> Result<(), String> {
+        if layer == SettingsLayer::Managed {
+            return Err("Managed settings are read-only".into());
+        }
+        write_setting_file(&self.settings_layer_path(layer, project)?, key, value)
+    }
+
+    pub fn plugins_dir(&self) -> PathBuf {
+        self.global_config_path().join("plugins")
+    }
+
+    /// `enabledPlugins` merged across the settings layers; a higher layer
+    /// decides for each plugin it lists.
+    pub fn enabled_plugins(
+        &self,
+        project: Option<&Path>,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut enabled = serde_json::Map::new();
+        for layer in self.read_settings_layers(project) {
+            if let Some(serde_json::Value::Object(plugins)) = layer.settings.get("enabledPlugins") {
+                enabled.extend(plugins.clone());
+            }
+        }
+        enabled
+    }
+
+    /// Plugins installed for the user or for `project`. Unreadable index
+    /// files end up in `error`.
+    pub fn read_plugins(&self, project: Option<&Path>) -> ClaudePlugins {
+        let dir = self.plugins_dir();
+        let mut result = ClaudePlugins::default();
+        match read_plugins(&dir, &self.enabled_plugins(project), project) {
+            Ok(plugins) => result.plugins = plugins,
+            Err(e) => result.error = Some(e),
+        }
+        match read_marketplaces(&dir) {
+            Ok(marketplaces) => result.marketplaces = marketplaces,
+            Err(e) => {
+                result.error.get_or_insert(e);
+            }
+        }
+        result
+    }
+
+    /// Turns a plugin on or off in the highest settings layer that lists
+    /// it, so the change takes effect. A plugin no layer lists yet goes into
+    /// the user file, or the project's shared file given a project. Fails
+    /// when managed settings decide it.
+    pub fn set_plugin_enabled(
+        &self,
+        id: &str,
+        enabled: bool,
+        project: Option<&Path>,
+    ) -> Result<(), String> {
+        if id.trim().is_empty() {
+            return Err("Plugin id cannot be empty".into());
+        }
+        let decider = self
+            .read_settings_layers(project)
+            .into_iter()
+            .rev()
+            .find(|l| l.settings.get("enabledPlugins").is_some_and(|p| p.get(id).is_some()));
+        let (layer, mut settings) = match decider {
+            Some(l) if l.layer == SettingsLayer::Managed => {
+                return Err(format!("Plugin {} is set by managed settings", id))
+            }
+            Some(l) => (l.layer, l.settings),
+            None => {
+                let layer =
+                    if project.is_some() { SettingsLayer::Project } else { SettingsLayer::User };
+                (layer, read_settings_file(&self.settings_layer_path(layer, project)?)?)
+            }
+        };
+        let mut plugins = match settings.shift_remove("enabledPlugins") {
+            None => serde_json::Map::new(),
+            Some(serde_json::Value::Object(plugins)) => plugins,
+            Some(_) => {
+                return Err(format!(
+                    "enabledPlugins in {} is not an object",
+                    self.settings_layer_path(layer, project)?.display()
+                ))
+            }
+        };
+        plugins.insert(id.to_string(), enabled.into());
+        self.write_layer_setting(layer, project, "enabledPlugins", Some(plugins.into()))
+    }
+
+    /// Whether `.mcp.json` server `name` was approved (`Some(true)`) or
+    /// rejected (`Some(false)`). `None` means Claude Code hasn't asked yet;
+    /// it prompts on startup and loads nothing until then. A rejection in
+    /// any settings layer wins.
+    pub fn project_server_approval(&self, project: &Path, name: &str) -> Option<bool> {
+        let layers = self.read_settings_layers(Some(project));
+        let listed = |key: &str| layers.iter().any(|l| lists_server(&l.settings, key, name));
+        if listed(DISABLED_MCPJSON) {
+            return Some(false);
+        }
+        let enable_all = layers
+            .iter()
+            .any(|l| l.settings.get(ENABLE_ALL_MCPJSON).and_then(|v| v.as_bool()) == Some(true));
+        (enable_all || listed(ENABLED_MCPJSON)).then_some(true)
+    }
+
+    /// Records an approval (`Some(true)`), a rejection (`Some(false)`) or,
+    /// with `None`, forgets `name` in the project's settings. Entries are
+    /// removed from whichever project file lists them; new ones go to
+    /// `settings.local.json`, as Claude Code does.
+    fn set_project_server_approval(
+        &self,
+        project: &Path,
+        name: &str,
+        enabled: Option<bool>,
+    ) -> Result<(), String> {
+        let layers = self.read_settings_layers(Some(project));
+        let listed = |key: &str| layers.iter().any(|l| lists_server(&l.settings, key, name));
+        let enable_all = layers
+            .iter()
+            .any(|l| l.settings.get(ENABLE_ALL_MCPJSON).and_then(|v| v.as_bool()) == Some(true));
+
+        let (unlist, add): (&[&str], _) = match enabled {
+            Some(true) => (&[DISABLED_MCPJSON], !enable_all && !listed(ENABLED_MCPJSON)),
+            Some(false) => (&[ENABLED_MCPJSON], !listed(DISABLED_MCPJSON)),
+            None => (&[ENABLED_MCPJSON, DISABLED_MCPJSON], false),
+        };
+
+        for layer in [SettingsLayer::Project, SettingsLayer::Local] {
+            let path = self.settings_layer_path(layer, Some(project))?;
+            let adds_here = add && layer == SettingsLayer::Local;
+            if !(adds_here || path.is_file()) {
+                continue;
+            }
+            let mut settings = read_settings_file(&path)?;
+            let mut changed = false;
+            for key in unlist {
+                changed |= unlist_server(&mut settings, key, name);
+            }
+            if adds_here {
+                let key = if enabled == Some(true) { ENABLED_MCPJSON } else { DISABLED_MCPJSON };
+                let names = settings
+                    .entry(key)
+                    .or_insert_with(|| serde_json::Value::Array(vec![]));
+                match names.as_array_mut() {
+                    Some(names) => names.push(name.into()),
+                    None => return Err(format!("{} in {} is not a list", key, path.display())),
+                }
+                changed = true;
+            }
+            if changed {
+                write_settings_file(&path, &settings)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_permission_settings(&self, scope: &ConfigScope) -> Result<ClaudePermissions, String> {
+        match self.read_settings(scope)?.remove("permissions") {
+            None => Ok(ClaudePermissions::default()),
+            Some(v) => serde_json::from_value(v).map_err(|e| {
+                format!("Invalid permissions in {}: {}", self.settings_path(scope).display(), e)
+            }),
+        }
+    }
+
+    pub fn read_hooks(&self, scope: &ConfigScope) -> Result<HookConfig, String> {
+        match self.read_settings(scope)?.remove("hooks") {
+            None => Ok(HookConfig::new()),
+            Some(v) => serde_json::from_value(v).map_err(|e| {
+                format!("Invalid hooks in {}: {}", self.settings_path(scope).display(), e)
+            }),
+        }
+    }
+
+    pub fn write_hooks(&self, hooks: &HookConfig, scope: &ConfigScope) -> Result<(), String> {
+        let value = if hooks.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_value(hooks).map_err(|e| format!("Failed to serialize: {}", e))?)
+        };
+        self.write_setting("hooks", value, scope)
+    }
+
+    pub fn agents_dir(&self, scope: &ConfigScope) -> PathBuf {
+        match scope {
+            ConfigScope::Global => self.global_config_path().join("agents"),
+            ConfigScope::Project(p) => self.project_config_path(p).join("agents"),
+        }
+    }
+
+    pub fn read_agents(&self, scope: &ConfigScope) -> Result<AgentScan, String> {
+        scan_agents_dir(&self.agents_dir(scope))
+    }
+
+    /// File holding the agent `name`; agents are found by their frontmatter
+    /// name, which need not match the file name.
+    pub fn agent_path(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        let found = self
+            .read_agents(scope)?
+            .agents
+            .into_iter()
+            .find(|a| a.name == name)
+            .map(|a| a.path);
+        Ok(found.unwrap_or_else(|| self.agents_dir(scope).join(format!("{}.md", name))))
+    }
+
+    pub fn write_agent(&self, agent: &Agent, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.agent_path(&agent.name, scope)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        let content = render_agent_md(agent)?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn delete_agent(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.agent_path(name, scope)?;
+        if !path.exists() {
+            return Ok(());
+        }
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+    }
+
+    pub fn capabilities() -> ToolCapabilities {
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports: vec![Transport::Stdio],
+                // Through `disabledMcpjsonServers`.
+                enabled: true,
+                enabled_project_only: true,
+                global: true,
+                project: true,
+                ..Default::default()
+            },
+            skills: SkillCapabilities { global: true, project: true, resources: true },
+            rules: RulesCapabilities { global: true, project: true, imports: true },
+            commands: CommandCapabilities {
+                global: true,
+                project: true,
+                arguments: "$ARGUMENTS",
+                positional: true,
+                argument_hint: true,
+                model: true,
+            },
+            permissions: PermissionCapabilities {
+                global: true,
+                project: true,
+                rules: true,
+                ask: true,
+                rule_tools: &[],
+                approvals: &[
+                    ApprovalPolicy::Untrusted,
+                    ApprovalPolicy::OnRequest,
+                    ApprovalPolicy::Never,
+                ],
+                sandbox_modes: &[SandboxMode::ReadOnly],
+            },
+            models: ModelCapabilities { global: true, project: true, ..Default::default() },
+        }
+    }
+}
+
+fn read_settings_file(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    match serde_json::from_str(&content) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} must contain a JSON object", path.display())),
+        Err(e) => Err(format!("Invalid JSON in {}: {}", path.display(), e)),
+    }
+}
+
+fn write_setting_file(
+    path: &Path,
+    key: &str,
+    value: Option<serde_json::Value>,
+) -> Result<(), String> {
+    let mut settings = read_settings_file(path)?;
+    match value {
+        Some(v) => settings.insert(key.to_string(), v),
+        None => settings.shift_remove(key),
+    };
+    write_settings_file(path, &settings)
+}
+
+fn write_settings_file(
+    path: &Path,
+    settings: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Settings keys Claude Code uses to approve servers from a project's
+/// `.mcp.json`.
+const ENABLED_MCPJSON: &str = "enabledMcpjsonServers";
+const DISABLED_MCPJSON: &str = "disabledMcpjsonServers";
+const ENABLE_ALL_MCPJSON: &str = "enableAllProjectMcpServers";
+
+fn lists_server(
+    settings: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    name: &str,
+) -> bool {
+    settings
+        .get(key)
+        .and_then(|v| v.as_array())
+        .is_some_and(|names| names.iter().any(|n| n.as_str() == Some(name)))
+}
+
+/// Drops `name` from a settings list, removing the key once it is empty.
+fn unlist_server(
+    settings: &mut serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    name: &str,
+) -> bool {
+    let Some(names) = settings.get_mut(key).and_then(|v| v.as_array_mut()) else {
+        return false;
+    };
+    let before = names.len();
+    names.retain(|n| n.as_str() != Some(name));
+    let changed = names.len() != before;
+    if names.is_empty() {
+        settings.shift_remove(key);
+    }
+    changed
 }
 
 impl ConfigAdapter for ClaudeAdapter {
@@ -108,12 +536,17 @@ impl ConfigAdapter for ClaudeAdapter {
             .mcp_servers
             .into_iter()
             .map(|(name, entry)| McpServer {
+                enabled: match scope {
+                    ConfigScope::Global => true,
+                    ConfigScope::Project(p) => {
+                        self.project_server_approval(p, &name) == Some(true)
+                    }
+                },
                 name,
                 command: entry.command,
                 args: entry.args,
                 env: entry.env,
                 url: None,
-                enabled: true,
             })
             .collect();
 
@@ -122,6 +555,9 @@ impl ConfigAdapter for ClaudeAdapter {
     }
 
     fn write_mcp_server(&self, server: &McpServer, scope: &ConfigScope) -> Result<(), String> {
+        if !server.enabled && *scope == ConfigScope::Global {
+            return Err("Claude Code can only disable project MCP servers".into());
+        }
         let path = self.mcp_path(scope);
 
         let mut config = match fs::read_to_string(&path) {
@@ -149,7 +585,21 @@ impl ConfigAdapter for ClaudeAdapter {
 
         let json = serde_json::to_string_pretty(&config)
             .map_err(|e| format!("Failed to serialize: {}", e))?;
-        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        fs::write(&path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        match scope {
+            ConfigScope::Global => Ok(()),
+            // Saving a server that is still awaiting approval leaves it pending.
+            ConfigScope::Project(p)
+                if !server.enabled && self.project_server_approval(p, &server.name).is_none() =>
+            {
+                Ok(())
+            }
+            ConfigScope::Project(p) => {
+                self.set_project_server_approval(p, &server.name, Some(server.enabled))
+            }
+        }
     }
 
     fn delete_mcp_server(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
@@ -168,44 +618,50 @@ impl ConfigAdapter for ClaudeAdapter {
 
         let json = serde_json::to_string_pretty(&config)
             .map_err(|e| format!("Failed to serialize: {}", e))?;
-        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
-    }
+        fs::write(&path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
 
-    fn read_skills(&self, scope: &ConfigScope) -> Result<Vec<Skill>, String> {
-        let dir = self.skills_dir(scope);
-        if !dir.exists() {
-            return Ok(vec![]);
+        match scope {
+            ConfigScope::Global => Ok(()),
+            ConfigScope::Project(p) => self.set_project_server_approval(p, name, None),
         }
+    }
 
-        let entries = fs::read_dir(&dir)
-            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        // Edit the raw document so `type`, `url`, `headers` and any other keys
+        // survive, and the server keeps its place in the file.
+        let path = self.mcp_path(scope);
+        let mut config = read_settings_file(&path)?;
+        let servers = config
+            .get_mut("mcpServers")
+            .and_then(|v| v.as_object_mut())
+            .filter(|servers| servers.contains_key(old))
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+        if servers.contains_key(new) {
+            return Err(format!("Server already exists: {}", new));
+        }
+        *servers = std::mem::take(servers)
+            .into_iter()
+            .map(|(name, entry)| if name == old { (new.to_string(), entry) } else { (name, entry) })
+            .collect();
+        write_settings_file(&path, &config)?;
 
-        let mut skills = Vec::new();
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let skill_file = path.join("SKILL.md");
-            if !skill_file.exists() {
-                continue;
-            }
-            let content = match fs::read_to_string(&skill_file) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-            if let Some((fm, body)) = parse_skill_frontmatter(&content) {
-                skills.push(Skill {
-                    name: fm.name,
-                    description: fm.description,
-                    content: body,
-                    path: path.clone(),
-                });
+        match scope {
+            ConfigScope::Global => Ok(()),
+            ConfigScope::Project(p) => {
+                let approval = self.project_server_approval(p, old);
+                self.set_project_server_approval(p, old, None)?;
+                self.set_project_server_approval(p, new, approval)
             }
         }
+    }
 
-        skills.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(skills)
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.mcp_path(scope))
+    }
+
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String> {
+        scan_skills_dir(&self.skills_dir(scope))
     }
 
     fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String> {
@@ -214,15 +670,7 @@ impl ConfigAdapter for ClaudeAdapter {
         fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
 
-        let fm = SkillFrontmatter {
-            name: skill.name.clone(),
-            description: skill.description.clone(),
-        };
-        let yaml = serde_yaml::to_string(&fm)
-            .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
-        let yaml_clean = yaml.trim_start_matches("---\n");
-
-        let content = format!("---\n{}---\n\n{}", yaml_clean, skill.content);
+        let content = render_skill_md(skill)?;
         let path = dir.join("SKILL.md");
         fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
@@ -237,6 +685,116 @@ impl ConfigAdapter for ClaudeAdapter {
             .map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
     }
 
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        Ok(self.skills_dir(scope).join(name))
+    }
+
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        read_md_commands(&self.prompts_dir(scope))
+    }
+
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&command.name)?;
+        write_md_command(&self.prompts_dir(scope), command)
+    }
+
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(name)?;
+        delete_md_command(&self.prompts_dir(scope), name)
+    }
+
+    fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.prompts_dir(scope))
+    }
+
+    fn read_permissions(&self, scope: &ConfigScope) -> Result<Permissions, String> {
+        let settings = self.read_permission_settings(scope)?;
+        let mode = settings
+            .default_mode
+            .as_deref()
+            .and_then(|m| PERMISSION_MODES.iter().find(|(name, _, _)| *name == m));
+        Ok(Permissions {
+            allow: settings.allow,
+            ask: settings.ask,
+            deny: settings.deny,
+            approval: mode.and_then(|(_, approval, _)| *approval),
+            sandbox: mode.and_then(|(_, _, sandbox)| *sandbox),
+        })
+    }
+
+    fn write_permissions(
+        &self,
+        permissions: &Permissions,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let mut settings = self.read_permission_settings(scope)?;
+        let mode = match (permissions.sandbox, permissions.approval) {
+            (Some(SandboxMode::ReadOnly), _) => Some("plan"),
+            (Some(other), _) => {
+                return Err(format!("Claude Code has no permission mode for sandbox {:?}", other))
+            }
+            (None, Some(approval)) => Some(
+                PERMISSION_MODES
+                    .iter()
+                    .find(|(_, a, _)| *a == Some(approval))
+                    .map(|(name, _, _)| *name)
+                    .ok_or_else(|| {
+                        format!("Claude Code has no permission mode for approval {:?}", approval)
+                    })?,
+            ),
+            (None, None) => None,
+        };
+        match mode {
+            Some(m) => settings.default_mode = Some(m.to_string()),
+            // A mode the hub does not know about is left alone.
+            None => {
+                if settings
+                    .default_mode
+                    .as_deref()
+                    .is_some_and(|m| PERMISSION_MODES.iter().any(|(name, _, _)| *name == m))
+                {
+                    settings.default_mode = None;
+                }
+            }
+        }
+        settings.allow = permissions.allow.clone();
+        settings.ask = permissions.ask.clone();
+        settings.deny = permissions.deny.clone();
+
+        let empty = settings.allow.is_empty()
+            && settings.ask.is_empty()
+            && settings.deny.is_empty()
+            && settings.default_mode.is_none()
+            && settings.extra.is_empty();
+        let value = if empty {
+            None
+        } else {
+            let v = serde_json::to_value(&settings)
+                .map_err(|e| format!("Failed to serialize: {}", e))?;
+            Some(v)
+        };
+        self.write_setting("permissions", value, scope)
+    }
+
+    fn read_model_settings(&self, scope: &ConfigScope) -> Result<ModelSettings, String> {
+        let settings = self.read_settings(scope)?;
+        let model = settings.get("model").and_then(|v| v.as_str()).map(String::from);
+        Ok(ModelSettings { model, ..Default::default() })
+    }
+
+    /// Only `model`; providers are configured through environment variables.
+    fn write_model_settings(
+        &self,
+        settings: &ModelSettings,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        if settings.provider.is_some() || !settings.providers.is_empty() {
+            return Err("Claude Code does not support model providers".into());
+        }
+        self.write_setting("model", settings.model.clone().map(Into::into), scope)
+    }
+
     fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
         let path = self.rules_path(scope);
         match fs::read_to_string(&path) {
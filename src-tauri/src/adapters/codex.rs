@@ -1,8 +1,15 @@
-use crate::core::{ConfigAdapter, ConfigScope, McpServer, Skill};
+use crate::core::{
+    delete_md_command, read_md_commands, read_trust_levels, render_skill_md, scan_skills_dir,
+    write_md_command, write_trust_levels, ApprovalPolicy, CodexProfile, CodexProfiles, Command,
+    CommandCapabilities, ConfigAdapter, ConfigScope, McpCapabilities, McpServer, ModelCapabilities,
+    ModelProvider, ModelSettings, PermissionCapabilities, Permissions, RulesCapabilities,
+    SandboxMode, Skill, SkillCapabilities, SkillScan, ToolCapabilities, Transport, TrustLevel,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table, TableLike};
 
 fn home_dir() -> Result<PathBuf, String> {
     dirs::home_dir().ok_or_else(|| "HOME directory not found".to_string())
@@ -50,26 +57,6 @@ fn default_enabled() -> bool {
     true
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct SkillFrontmatter {
-    name: String,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
-}
-
-fn parse_skill_frontmatter(content: &str) -> Option<(SkillFrontmatter, String)> {
-    let content = content.trim_start();
-    if !content.starts_with("---") {
-        return None;
-    }
-    let rest = &content[3..];
-    let end = rest.find("\n---")?;
-    let yaml = &rest[..end];
-    let body = rest[end + 4..].trim_start().to_string();
-    let fm: SkillFrontmatter = serde_yaml::from_str(yaml).ok()?;
-    Some((fm, body))
-}
-
 impl CodexAdapter {
     fn config_path(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
         match scope {
@@ -85,12 +72,395 @@ impl CodexAdapter {
         }
     }
 
+    /// Custom prompts are only read from `~/.codex/prompts`.
+    fn prompts_dir(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        match scope {
+            ConfigScope::Global => Ok(home_dir()?.join(".codex/prompts")),
+            ConfigScope::Project(_) => Err("Codex only supports global prompts".into()),
+        }
+    }
+
     fn rules_path(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
         match scope {
             ConfigScope::Global => Ok(home_dir()?.join(".codex/AGENTS.md")),
             ConfigScope::Project(p) => Ok(p.join("AGENTS.md")),
         }
     }
+
+    fn load_config(&self, scope: &ConfigScope) -> Result<CodexTomlConfig, String> {
+        let path = self.config_path(scope)?;
+        match fs::read_to_string(&path) {
+            Ok(c) => toml::from_str(&c)
+                .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CodexTomlConfig::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Writes the top-level `keys` of `config` through `toml_edit`, touching
+    /// only what differs from the file so comments and layout survive.
+    fn save_keys(
+        &self,
+        config: &CodexTomlConfig,
+        keys: &[&str],
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let path = self.config_path(scope)?;
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let on_disk: CodexTomlConfig = toml::from_str(&content)
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+        for key in keys {
+            sync_key(doc.as_table_mut(), key, on_disk.other.get(*key), config.other.get(*key))?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, doc.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn profiles_table(config: &CodexTomlConfig) -> Option<&toml::Table> {
+        config.other.get("profiles").and_then(|v| v.as_table())
+    }
+
+    pub fn read_profiles(&self, scope: &ConfigScope) -> Result<CodexProfiles, String> {
+        let config = self.load_config(scope)?;
+        let profiles = match Self::profiles_table(&config) {
+            Some(table) => table
+                .iter()
+                .filter_map(|(name, v)| Some(profile_from_toml(name, v.as_table()?)))
+                .collect(),
+            None => vec![],
+        };
+        let default = config.other.get("profile").and_then(|v| v.as_str()).map(String::from);
+        Ok(CodexProfiles { default, profiles })
+    }
+
+    /// Adds or replaces a profile.
+    pub fn write_profile(&self, profile: &CodexProfile, scope: &ConfigScope) -> Result<(), String> {
+        validate_profile_name(&profile.name)?;
+        let entry = profile_to_toml(profile)?;
+        let mut config = self.load_config(scope)?;
+        let profiles = config
+            .other
+            .entry("profiles")
+            .or_insert_with(|| toml::Value::Table(Default::default()));
+        let Some(profiles) = profiles.as_table_mut() else {
+            return Err("`profiles` in config.toml is not a table".into());
+        };
+        profiles.insert(profile.name.clone(), toml::Value::Table(entry));
+        self.save_keys(&config, &["profiles"], scope)
+    }
+
+    /// Removes a profile, clearing `profile` if it was the default.
+    pub fn delete_profile(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        let mut config = self.load_config(scope)?;
+        let removed = config
+            .other
+            .get_mut("profiles")
+            .and_then(|v| v.as_table_mut())
+            .and_then(|t| t.remove(name))
+            .is_some();
+        if !removed {
+            return Ok(());
+        }
+        if config.other.get("profile").and_then(|v| v.as_str()) == Some(name) {
+            config.other.remove("profile");
+        }
+        if Self::profiles_table(&config).is_some_and(|t| t.is_empty()) {
+            config.other.remove("profiles");
+        }
+        self.save_keys(&config, &["profiles", "profile"], scope)
+    }
+
+    pub fn set_default_profile(
+        &self,
+        name: Option<&str>,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let mut config = self.load_config(scope)?;
+        match name {
+            Some(n) => {
+                if !Self::profiles_table(&config).is_some_and(|t| t.contains_key(n)) {
+                    return Err(format!("Profile not found: {}", n));
+                }
+                config.other.insert("profile".into(), toml::Value::String(n.to_string()));
+            }
+            None => {
+                config.other.remove("profile");
+            }
+        }
+        self.save_keys(&config, &["profile"], scope)
+    }
+
+    /// Top-level settings with the profile laid over them; with no profile
+    /// given, the default one applies. MCP servers are left out.
+    pub fn effective_config(
+        &self,
+        profile: Option<&str>,
+        scope: &ConfigScope,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+        let config = self.load_config(scope)?;
+        let mut base = config.other.clone();
+        base.remove("profiles");
+        let default = base.remove("profile").and_then(|v| v.as_str().map(String::from));
+        let name = profile.map(String::from).or(default);
+        if let Some(name) = name {
+            let entry = Self::profiles_table(&config)
+                .and_then(|t| t.get(&name))
+                .and_then(|v| v.as_table())
+                .ok_or_else(|| format!("Profile not found: {}", name))?;
+            merge_tables(&mut base, entry);
+        }
+        Ok(toml_to_json(&base))
+    }
+
+    /// Project trust lives in the global config as `[projects."<path>"]`.
+    pub fn trust_levels(&self) -> Result<BTreeMap<String, TrustLevel>, String> {
+        read_trust_levels(&self.config_path(&ConfigScope::Global)?)
+    }
+
+    pub fn set_trust_levels(&self, changes: &[(String, Option<TrustLevel>)]) -> Result<(), String> {
+        write_trust_levels(&self.config_path(&ConfigScope::Global)?, changes)
+    }
+
+    pub fn capabilities() -> ToolCapabilities {
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports: vec![Transport::Stdio, Transport::Http],
+                enabled: true,
+                enabled_project_only: false,
+                headers: true,
+                timeouts: true,
+                global: true,
+                project: true,
+            },
+            skills: SkillCapabilities { global: true, project: true, resources: true },
+            rules: RulesCapabilities { global: true, project: true, imports: false },
+            commands: CommandCapabilities {
+                global: true,
+                project: false,
+                arguments: "$ARGUMENTS",
+                positional: true,
+                argument_hint: true,
+                model: false,
+            },
+            permissions: PermissionCapabilities {
+                global: true,
+                project: true,
+                approvals: &[
+                    ApprovalPolicy::Untrusted,
+                    ApprovalPolicy::OnFailure,
+                    ApprovalPolicy::OnRequest,
+                    ApprovalPolicy::Never,
+                ],
+                sandbox_modes: &[
+                    SandboxMode::ReadOnly,
+                    SandboxMode::WorkspaceWrite,
+                    SandboxMode::DangerFullAccess,
+                ],
+                ..Default::default()
+            },
+            models: ModelCapabilities {
+                global: true,
+                project: true,
+                provider: true,
+                providers: true,
+            },
+        }
+    }
+}
+
+fn toml_to_json(table: &toml::Table) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(table) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => Default::default(),
+    }
+}
+
+fn json_to_toml(map: &serde_json::Map<String, serde_json::Value>) -> Result<toml::Table, String> {
+    let mut table = toml::Table::new();
+    for (key, value) in map {
+        let v = toml::Value::try_from(value)
+            .map_err(|e| format!("Invalid value for `{}`: {}", key, e))?;
+        table.insert(key.clone(), v);
+    }
+    Ok(table)
+}
+
+/// A `toml` value as an inline `toml_edit` one.
+fn edit_value(value: &toml::Value) -> Result<toml_edit::Value, String> {
+    value.to_string().parse().map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+/// Brings `parent[key]` from `old` to `new`. Unchanged values are left alone
+/// and tables are edited key by key, so their comments and layout survive.
+fn sync_key(
+    parent: &mut dyn TableLike,
+    key: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+) -> Result<(), String> {
+    if old == new {
+        return Ok(());
+    }
+    let Some(new) = new else {
+        parent.remove(key);
+        return Ok(());
+    };
+    if let toml::Value::Table(new) = new {
+        if parent.get(key).is_none() {
+            // A table holding only tables gets no header of its own.
+            let mut table = Table::new();
+            table.set_implicit(!new.is_empty());
+            parent.insert(key, Item::Table(table));
+        }
+        if let Some(table) = parent.get_mut(key).and_then(|item| item.as_table_like_mut()) {
+            let empty = toml::Table::new();
+            let old = match old {
+                Some(toml::Value::Table(t)) => t,
+                _ => &empty,
+            };
+            for gone in old.keys().filter(|k| !new.contains_key(*k)) {
+                table.remove(gone);
+            }
+            for (k, v) in new {
+                sync_key(table, k, old.get(k), Some(v))?;
+            }
+            return Ok(());
+        }
+    }
+    parent.insert(key, Item::Value(edit_value(new)?));
+    Ok(())
+}
+
+/// Removes `key` from `table` if it holds a string.
+fn take_string(table: &mut toml::Table, key: &str) -> Option<String> {
+    match table.remove(key) {
+        Some(toml::Value::String(s)) => Some(s),
+        Some(other) => {
+            table.insert(key.to_string(), other);
+            None
+        }
+        None => None,
+    }
+}
+
+fn provider_from_toml(id: &str, entry: &toml::Table) -> ModelProvider {
+    let mut extra = entry.clone();
+    ModelProvider {
+        id: id.to_string(),
+        name: take_string(&mut extra, "name"),
+        base_url: take_string(&mut extra, "base_url"),
+        env_key: take_string(&mut extra, "env_key"),
+        extra: toml_to_json(&extra),
+    }
+}
+
+fn provider_to_toml(provider: &ModelProvider) -> Result<toml::Table, String> {
+    let mut entry = json_to_toml(&provider.extra)
+        .map_err(|e| format!("Provider {}: {}", provider.id, e))?;
+    let known = [
+        ("name", &provider.name),
+        ("base_url", &provider.base_url),
+        ("env_key", &provider.env_key),
+    ];
+    for (key, value) in known {
+        if let Some(v) = value {
+            entry.insert(key.to_string(), toml::Value::String(v.clone()));
+        }
+    }
+    Ok(entry)
+}
+
+fn profile_from_toml(name: &str, entry: &toml::Table) -> CodexProfile {
+    let mut extra = entry.clone();
+    let approval_policy = read_enum(&extra, "approval_policy");
+    let sandbox_mode = read_enum(&extra, "sandbox_mode");
+    if approval_policy.is_some() {
+        extra.remove("approval_policy");
+    }
+    if sandbox_mode.is_some() {
+        extra.remove("sandbox_mode");
+    }
+    CodexProfile {
+        name: name.to_string(),
+        model: take_string(&mut extra, "model"),
+        model_provider: take_string(&mut extra, "model_provider"),
+        approval_policy,
+        sandbox_mode,
+        extra: toml_to_json(&extra),
+    }
+}
+
+fn profile_to_toml(profile: &CodexProfile) -> Result<toml::Table, String> {
+    let mut entry = json_to_toml(&profile.extra)
+        .map_err(|e| format!("Profile {}: {}", profile.name, e))?;
+    for (key, value) in [("model", &profile.model), ("model_provider", &profile.model_provider)] {
+        if let Some(v) = value {
+            entry.insert(key.to_string(), toml::Value::String(v.clone()));
+        }
+    }
+    write_enum(&mut entry, "approval_policy", profile.approval_policy)?;
+    write_enum(&mut entry, "sandbox_mode", profile.sandbox_mode)?;
+    Ok(entry)
+}
+
+/// Profile names are passed to `codex --profile`, so keep them simple.
+fn validate_profile_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid profile name: {}", name))
+    }
+}
+
+/// Overlays `top` on `base`, merging nested tables key by key.
+fn merge_tables(base: &mut toml::Table, top: &toml::Table) {
+    for (key, value) in top {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(b)), toml::Value::Table(t)) => merge_tables(b, t),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Reads a string enum such as `approval_policy`; unknown values read as unset.
+fn read_enum<T: serde::de::DeserializeOwned>(table: &toml::Table, key: &str) -> Option<T> {
+    table.get(key).cloned().and_then(|v| v.try_into().ok())
+}
+
+/// Sets a string enum, leaving values the hub does not recognise in place
+/// when `value` is unset.
+fn write_enum<T: Serialize + serde::de::DeserializeOwned>(
+    table: &mut toml::Table,
+    key: &str,
+    value: Option<T>,
+) -> Result<(), String> {
+    match value {
+        Some(v) => {
+            let v = toml::Value::try_from(v).map_err(|e| format!("Failed to serialize: {}", e))?;
+            table.insert(key.to_string(), v);
+        }
+        None => {
+            if read_enum::<T>(table, key).is_some() {
+                table.remove(key);
+            }
+        }
+    }
+    Ok(())
 }
 
 impl ConfigAdapter for CodexAdapter {
@@ -193,41 +563,33 @@ impl ConfigAdapter for CodexAdapter {
             .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
 
-    fn read_skills(&self, scope: &ConfigScope) -> Result<Vec<Skill>, String> {
-        let dir = self.skills_dir(scope)?;
-        if !dir.exists() {
-            return Ok(vec![]);
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: CodexTomlConfig = toml::from_str(&content)
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+
+        if config.mcp_servers.contains_key(new) {
+            return Err(format!("Server already exists: {}", new));
         }
+        let entry = config
+            .mcp_servers
+            .remove(old)
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+        config.mcp_servers.insert(new.to_string(), entry);
 
-        let entries = fs::read_dir(&dir)
-            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let out = toml::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(&path, out).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
 
-        let mut skills = Vec::new();
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let skill_file = path.join("SKILL.md");
-            if !skill_file.exists() {
-                continue;
-            }
-            let content = match fs::read_to_string(&skill_file) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-            if let Some((fm, body)) = parse_skill_frontmatter(&content) {
-                skills.push(Skill {
-                    name: fm.name,
-                    description: fm.description,
-                    content: body,
-                    path: path.clone(),
-                });
-            }
-        }
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        self.config_path(scope).ok()
+    }
 
-        skills.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(skills)
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String> {
+        scan_skills_dir(&self.skills_dir(scope)?)
     }
 
     fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String> {
@@ -236,15 +598,7 @@ impl ConfigAdapter for CodexAdapter {
         fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
 
-        let fm = SkillFrontmatter {
-            name: skill.name.clone(),
-            description: skill.description.clone(),
-        };
-        let yaml = serde_yaml::to_string(&fm)
-            .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
-        let yaml_clean = yaml.trim_start_matches("---\n");
-
-        let content = format!("---\n{}---\n\n{}", yaml_clean, skill.content);
+        let content = render_skill_md(skill)?;
         let path = dir.join("SKILL.md");
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
@@ -260,6 +614,100 @@ impl ConfigAdapter for CodexAdapter {
             .map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
     }
 
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        Ok(self.skills_dir(scope)?.join(name))
+    }
+
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        match self.prompts_dir(scope) {
+            Ok(dir) => read_md_commands(&dir),
+            Err(_) => Ok(vec![]),
+        }
+    }
+
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&command.name)?;
+        write_md_command(&self.prompts_dir(scope)?, command)
+    }
+
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(name)?;
+        delete_md_command(&self.prompts_dir(scope)?, name)
+    }
+
+    fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        self.prompts_dir(scope).ok()
+    }
+
+    /// Codex has no per-tool rules, only `approval_policy` and `sandbox_mode`.
+    fn read_permissions(&self, scope: &ConfigScope) -> Result<Permissions, String> {
+        let config = self.load_config(scope)?;
+        Ok(Permissions {
+            approval: read_enum(&config.other, "approval_policy"),
+            sandbox: read_enum(&config.other, "sandbox_mode"),
+            ..Default::default()
+        })
+    }
+
+    fn write_permissions(
+        &self,
+        permissions: &Permissions,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let has_rules = [&permissions.allow, &permissions.ask, &permissions.deny]
+            .iter()
+            .any(|rules| !rules.is_empty());
+        if has_rules {
+            return Err("Codex does not support permission rules".into());
+        }
+        let mut config = self.load_config(scope)?;
+        write_enum(&mut config.other, "approval_policy", permissions.approval)?;
+        write_enum(&mut config.other, "sandbox_mode", permissions.sandbox)?;
+        self.save_keys(&config, &["approval_policy", "sandbox_mode"], scope)
+    }
+
+    fn read_model_settings(&self, scope: &ConfigScope) -> Result<ModelSettings, String> {
+        let config = self.load_config(scope)?;
+        let get = |key: &str| config.other.get(key).and_then(|v| v.as_str()).map(String::from);
+        let providers = match config.other.get("model_providers").and_then(|v| v.as_table()) {
+            Some(table) => table
+                .iter()
+                .filter_map(|(id, v)| Some(provider_from_toml(id, v.as_table()?)))
+                .collect(),
+            None => vec![],
+        };
+        Ok(ModelSettings { model: get("model"), provider: get("model_provider"), providers })
+    }
+
+    fn write_model_settings(
+        &self,
+        settings: &ModelSettings,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let mut config = self.load_config(scope)?;
+        let strings = [("model", &settings.model), ("model_provider", &settings.provider)];
+        for (key, value) in strings {
+            match value {
+                Some(v) => config.other.insert(key.to_string(), toml::Value::String(v.clone())),
+                None => config.other.remove(key),
+            };
+        }
+        let mut providers = toml::Table::new();
+        for provider in &settings.providers {
+            if provider.id.trim().is_empty() {
+                return Err("Provider id cannot be empty".into());
+            }
+            providers.insert(provider.id.clone(), toml::Value::Table(provider_to_toml(provider)?));
+        }
+        if providers.is_empty() {
+            config.other.remove("model_providers");
+        } else {
+            config.other.insert("model_providers".into(), toml::Value::Table(providers));
+        }
+        self.save_keys(&config, &["model", "model_provider", "model_providers"], scope)
+    }
+
     fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
         let path = self.rules_path(scope)?;
         match fs::read_to_string(&path) {
@@ -1,8 +1,15 @@
-use crate::core::{ConfigAdapter, ConfigScope, McpServer, Skill};
+use super::jsonc;
+use crate::core::{
+    disabled_extensions, install_extension, read_extensions, render_skill_md, scan_skills_dir,
+    set_extension_enabled, split_rule, uninstall_extension, ApprovalPolicy, Command,
+    CommandCapabilities, ConfigAdapter, ConfigScope, GeminiExtension, McpCapabilities, McpServer,
+    ModelCapabilities, ModelSettings, PermissionCapabilities, Permissions, RulesCapabilities, Skill,
+    SkillCapabilities, SkillScan, ToolCapabilities, Transport,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn validate_name(name: &str) -> Result<(), String> {
     use std::path::Component;
@@ -17,14 +24,20 @@ fn validate_name(name: &str) -> Result<(), String> {
     }
 }
 
-pub struct GeminiAdapter;
+/// Adapter for Gemini CLI and its forks (Qwen Code, iFlow CLI), which share
+/// the `settings.json` schema but use their own directory and context file.
+pub struct GeminiAdapter {
+    name: &'static str,
+    dir: &'static str,
+    context_file: &'static str,
+}
 
 #[derive(Debug, Deserialize, Serialize, Default)]
 struct GeminiSettings {
     #[serde(default, rename = "mcpServers")]
     mcp_servers: BTreeMap<String, GeminiMcpServerEntry>,
     #[serde(flatten)]
-    other: BTreeMap<String, serde_json::Value>,
+    other: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
@@ -35,33 +48,96 @@ struct GeminiMcpServerEntry {
     args: Vec<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     env: HashMap<String, String>,
+    /// Streamable HTTP endpoint.
     #[serde(default, rename = "httpUrl", skip_serializing_if = "Option::is_none")]
     http_url: Option<String>,
+    /// SSE endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
     #[serde(flatten)]
     extra: BTreeMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct SkillFrontmatter {
-    name: String,
+/// `commands/<name>.toml`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct GeminiCommandFile {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     description: Option<String>,
-}
-
-fn parse_skill_frontmatter(content: &str) -> Option<(SkillFrontmatter, String)> {
-    let content = content.trim_start();
-    if !content.starts_with("---") {
-        return None;
-    }
-    let rest = &content[3..];
-    let end = rest.find("\n---")?;
-    let yaml = &rest[..end];
-    let body = rest[end + 4..].trim_start().to_string();
-    let fm: SkillFrontmatter = serde_yaml::from_str(yaml).ok()?;
-    Some((fm, body))
+    prompt: String,
+    #[serde(flatten)]
+    extra: toml::Table,
 }
 
 impl GeminiAdapter {
+    pub const fn new(name: &'static str, dir: &'static str, context_file: &'static str) -> Self {
+        Self { name, dir, context_file }
+    }
+
+    pub const fn gemini() -> Self {
+        Self::new("Gemini", ".gemini", "GEMINI.md")
+    }
+
+    pub const fn qwen() -> Self {
+        Self::new("Qwen Code", ".qwen", "QWEN.md")
+    }
+
+    pub const fn iflow() -> Self {
+        Self::new("iFlow CLI", ".iflow", "IFLOW.md")
+    }
+
+    /// Shared by the forks.
+    pub fn capabilities() -> ToolCapabilities {
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports: vec![Transport::Stdio, Transport::Http, Transport::Sse],
+                timeouts: true,
+                global: true,
+                project: true,
+                ..Default::default()
+            },
+            skills: SkillCapabilities { global: true, project: true, resources: true },
+            rules: RulesCapabilities { global: true, project: true, imports: true },
+            commands: CommandCapabilities {
+                global: true,
+                project: true,
+                arguments: "{{args}}",
+                ..Default::default()
+            },
+            permissions: PermissionCapabilities {
+                global: true,
+                project: true,
+                rules: true,
+                rule_tools: &[
+                    "Bash", "Read", "Write", "Edit", "Glob", "Grep", "LS", "WebFetch", "WebSearch",
+                ],
+                approvals: &[ApprovalPolicy::Untrusted, ApprovalPolicy::OnRequest],
+                ..Default::default()
+            },
+            models: ModelCapabilities { global: true, project: true, ..Default::default() },
+        }
+    }
+
+    fn load_settings(&self, scope: &ConfigScope) -> Result<GeminiSettings, String> {
+        let path = self.settings_path(scope);
+        match fs::read_to_string(&path) {
+            Ok(c) => serde_json::from_str(&c)
+                .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(GeminiSettings::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save_settings(&self, settings: &GeminiSettings, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.settings_path(scope);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
     fn settings_path(&self, scope: &ConfigScope) -> PathBuf {
         match scope {
             ConfigScope::Global => self.global_config_path().join("settings.json"),
@@ -76,25 +152,155 @@ impl GeminiAdapter {
         }
     }
 
-    fn rules_path(&self, scope: &ConfigScope) -> PathBuf {
+    fn prompts_dir(&self, scope: &ConfigScope) -> PathBuf {
         match scope {
-            ConfigScope::Global => self.global_config_path().join("GEMINI.md"),
-            ConfigScope::Project(p) => p.join("GEMINI.md"),
+            ConfigScope::Global => self.global_config_path().join("commands"),
+            ConfigScope::Project(p) => self.project_config_path(p).join("commands"),
         }
     }
+
+    /// Context file names from `context.fileName` (or the legacy top-level
+    /// `contextFileName`), falling back to the tool's default. Project settings
+    /// override global ones, matching the CLI's merge order.
+    fn context_file_names(&self, scope: &ConfigScope) -> Vec<String> {
+        let mut layers = vec![self.settings_path(&ConfigScope::Global)];
+        if let ConfigScope::Project(_) = scope {
+            layers.push(self.settings_path(scope));
+        }
+        context_file_names(&layers, self.context_file)
+    }
+
+    fn rules_paths(&self, scope: &ConfigScope) -> Vec<PathBuf> {
+        let base = match scope {
+            ConfigScope::Global => self.global_config_path(),
+            ConfigScope::Project(p) => p.clone(),
+        };
+        self.context_file_names(scope).iter().map(|n| base.join(n)).collect()
+    }
+
+    /// User-level extensions; project extensions are not handled.
+    pub fn extensions_dir(&self) -> PathBuf {
+        self.global_config_path().join("extensions")
+    }
+
+    pub fn read_extensions(&self) -> Result<Vec<GeminiExtension>, String> {
+        let disabled = disabled_extensions(&self.settings_path(&ConfigScope::Global))?;
+        read_extensions(&self.extensions_dir(), &disabled)
+    }
+
+    pub fn set_extension_enabled(&self, name: &str, enabled: bool) -> Result<(), String> {
+        if !self.read_extensions()?.iter().any(|e| e.name == name) {
+            return Err(format!("Extension not found: {}", name));
+        }
+        set_extension_enabled(&self.settings_path(&ConfigScope::Global), name, enabled)
+    }
+
+    pub fn install_extension(&self, source: &Path) -> Result<GeminiExtension, String> {
+        let name = install_extension(&self.extensions_dir(), source)?;
+        self.read_extensions()?
+            .into_iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| format!("Extension not found: {}", name))
+    }
+
+    /// Removes the extension's folder and forgets that it was disabled.
+    pub fn uninstall_extension(&self, name: &str) -> Result<(), String> {
+        let settings = self.settings_path(&ConfigScope::Global);
+        let disabled = disabled_extensions(&settings)?;
+        uninstall_extension(&self.extensions_dir(), name)?;
+        if disabled.iter().any(|n| n == name) {
+            set_extension_enabled(&settings, name, true)?;
+        }
+        Ok(())
+    }
+}
+
+/// Built-in tool names and their Claude Code equivalents.
+const TOOL_NAMES: &[(&str, &str)] = &[
+    ("run_shell_command", "Bash"),
+    ("read_file", "Read"),
+    ("write_file", "Write"),
+    ("replace", "Edit"),
+    ("glob", "Glob"),
+    ("search_file_content", "Grep"),
+    ("list_directory", "LS"),
+    ("web_fetch", "WebFetch"),
+    ("google_web_search", "WebSearch"),
+];
+
+/// `run_shell_command(git)` becomes `Bash(git:*)`; unknown tools pass through.
+fn rule_from_gemini(entry: &str) -> String {
+    let (tool, arg) = split_rule(entry);
+    let tool = match tool {
+        "ShellTool" => "Bash",
+        t => TOOL_NAMES.iter().find(|(g, _)| *g == t).map_or(t, |(_, c)| c),
+    };
+    match arg {
+        Some(a) if tool == "Bash" => format!("Bash({}:*)", a),
+        Some(a) => format!("{}({})", tool, a),
+        None => tool.to_string(),
+    }
+}
+
+fn rule_to_gemini(rule: &str) -> String {
+    let (tool, arg) = split_rule(rule);
+    let name = TOOL_NAMES.iter().find(|(_, c)| *c == tool).map_or(tool, |(g, _)| g);
+    match arg {
+        Some(a) if tool == "Bash" => format!("{}({})", name, a.trim_end_matches(":*").trim_end()),
+        Some(a) => format!("{}({})", name, a),
+        None => name.to_string(),
+    }
+}
+
+fn read_command_file(path: &Path) -> Result<GeminiCommandFile, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    toml::from_str(&content).map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))
+}
+
+/// Context file names set by the last of the settings files in `layers`
+/// that sets valid ones, or `default`.
+pub(super) fn context_file_names(layers: &[PathBuf], default: &str) -> Vec<String> {
+    layers
+        .iter()
+        .rev()
+        .find_map(|path| configured_context_files(path))
+        .unwrap_or_else(|| vec![default.to_string()])
+}
+
+fn configured_context_files(settings_path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(settings_path).ok()?;
+    let settings = jsonc::parse(&content).ok()?;
+    let value = settings
+        .pointer("/context/fileName")
+        .or_else(|| settings.get("contextFileName"))?;
+    let names: Vec<String> = match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        _ => return None,
+    };
+    let names: Vec<String> = names.into_iter().filter(|n| validate_name(n).is_ok()).collect();
+    if names.is_empty() {
+        None
+    } else {
+        Some(names)
+    }
 }
 
 impl ConfigAdapter for GeminiAdapter {
     fn tool_name(&self) -> &'static str {
-        "Gemini"
+        self.name
     }
 
     fn global_config_path(&self) -> PathBuf {
-        dirs::home_dir().unwrap_or_default().join(".gemini")
+        dirs::home_dir().unwrap_or_default().join(self.dir)
     }
 
     fn project_config_path(&self, project: &PathBuf) -> PathBuf {
-        project.join(".gemini")
+        project.join(self.dir)
     }
 
     fn read_mcp_servers(&self, scope: &ConfigScope) -> Result<Vec<McpServer>, String> {
@@ -116,7 +322,7 @@ impl ConfigAdapter for GeminiAdapter {
                 command: entry.command.unwrap_or_default(),
                 args: entry.args,
                 env: entry.env,
-                url: entry.http_url,
+                url: entry.http_url.or(entry.url),
                 enabled: true,
             })
             .collect();
@@ -135,11 +341,10 @@ impl ConfigAdapter for GeminiAdapter {
             Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
         };
 
-        let existing_extra = settings
-            .mcp_servers
-            .get(&server.name)
-            .map(|e| e.extra.clone())
-            .unwrap_or_default();
+        let existing = settings.mcp_servers.get(&server.name);
+        let existing_extra = existing.map(|e| e.extra.clone()).unwrap_or_default();
+        // New remote servers use `httpUrl`; an existing SSE entry stays on `url`.
+        let sse = existing.is_some_and(|e| e.http_url.is_none() && e.url.is_some());
 
         settings.mcp_servers.insert(
             server.name.clone(),
@@ -147,7 +352,8 @@ impl ConfigAdapter for GeminiAdapter {
                 command: if server.command.is_empty() { None } else { Some(server.command.clone()) },
                 args: server.args.clone(),
                 env: server.env.clone(),
-                http_url: server.url.clone(),
+                http_url: server.url.clone().filter(|_| !sse),
+                url: server.url.clone().filter(|_| sse),
                 extra: existing_extra,
             },
         );
@@ -181,41 +387,33 @@ impl ConfigAdapter for GeminiAdapter {
         fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
 
-    fn read_skills(&self, scope: &ConfigScope) -> Result<Vec<Skill>, String> {
-        let dir = self.skills_dir(scope);
-        if !dir.exists() {
-            return Ok(vec![]);
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.settings_path(scope);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: GeminiSettings = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+
+        if config.mcp_servers.contains_key(new) {
+            return Err(format!("Server already exists: {}", new));
         }
+        let entry = config
+            .mcp_servers
+            .remove(old)
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+        config.mcp_servers.insert(new.to_string(), entry);
 
-        let entries = fs::read_dir(&dir)
-            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let out = serde_json::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(&path, out).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
 
-        let mut skills = Vec::new();
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let skill_file = path.join("SKILL.md");
-            if !skill_file.exists() {
-                continue;
-            }
-            let content = match fs::read_to_string(&skill_file) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-            if let Some((fm, body)) = parse_skill_frontmatter(&content) {
-                skills.push(Skill {
-                    name: fm.name,
-                    description: fm.description,
-                    content: body,
-                    path: path.clone(),
-                });
-            }
-        }
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.settings_path(scope))
+    }
 
-        skills.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(skills)
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String> {
+        scan_skills_dir(&self.skills_dir(scope))
     }
 
     fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String> {
@@ -224,15 +422,7 @@ impl ConfigAdapter for GeminiAdapter {
         fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
 
-        let fm = SkillFrontmatter {
-            name: skill.name.clone(),
-            description: skill.description.clone(),
-        };
-        let yaml = serde_yaml::to_string(&fm)
-            .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
-        let yaml_clean = yaml.trim_start_matches("---\n");
-
-        let content = format!("---\n{}---\n\n{}", yaml_clean, skill.content);
+        let content = render_skill_md(skill)?;
         let path = dir.join("SKILL.md");
         fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
@@ -246,21 +436,247 @@ impl ConfigAdapter for GeminiAdapter {
         fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
     }
 
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        Ok(self.skills_dir(scope).join(name))
+    }
+
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        let dir = self.prompts_dir(scope);
+        let entries = match fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+        };
+        let mut commands = Vec::new();
+        for path in entries.flatten().map(|e| e.path()) {
+            if !path.is_file() || path.extension().is_none_or(|e| e != "toml") {
+                continue;
+            }
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            // Files the CLI cannot load either are left out.
+            let Ok(file) = read_command_file(&path) else {
+                continue;
+            };
+            commands.push(Command {
+                name,
+                description: file.description,
+                content: file.prompt,
+                path,
+                ..Default::default()
+            });
+        }
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(commands)
+    }
+
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&command.name)?;
+        let dir = self.prompts_dir(scope);
+        let path = dir.join(format!("{}.toml", command.name));
+        // Edited in place so comments and unknown keys in the file survive.
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let mut doc = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+        match &command.description {
+            Some(d) if doc.get("description").and_then(|v| v.as_str()) != Some(d) => {
+                doc["description"] = toml_edit::value(d.as_str());
+            }
+            Some(_) => {}
+            None => {
+                doc.remove("description");
+            }
+        }
+        if doc.get("prompt").and_then(|v| v.as_str()) != Some(&command.content) {
+            doc["prompt"] = toml_edit::value(command.content.as_str());
+        }
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
+        fs::write(&path, doc.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(name)?;
+        let path = self.prompts_dir(scope).join(format!("{}.toml", name));
+        if !path.exists() {
+            return Ok(());
+        }
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+    }
+
+    fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.prompts_dir(scope))
+    }
+
+    /// `tools.core`/`tools.exclude`/`tools.autoAccept`, or the legacy
+    /// top-level `coreTools`/`excludeTools`/`autoAccept`.
+    fn read_permissions(&self, scope: &ConfigScope) -> Result<Permissions, String> {
+        let settings = self.load_settings(scope)?;
+        let get = |nested: &str, legacy: &str| {
+            settings
+                .other
+                .get("tools")
+                .and_then(|t| t.get(nested))
+                .or_else(|| settings.other.get(legacy))
+                .cloned()
+        };
+        let rules = |v: Option<serde_json::Value>| -> Vec<String> {
+            v.and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+                .unwrap_or_default()
+                .iter()
+                .map(|t| rule_from_gemini(t))
+                .collect()
+        };
+        Ok(Permissions {
+            allow: rules(get("core", "coreTools")),
+            deny: rules(get("exclude", "excludeTools")),
+            approval: get("autoAccept", "autoAccept").and_then(|v| v.as_bool()).map(|auto| {
+                if auto {
+                    ApprovalPolicy::OnRequest
+                } else {
+                    ApprovalPolicy::Untrusted
+                }
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn write_permissions(
+        &self,
+        permissions: &Permissions,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        if !permissions.ask.is_empty() {
+            return Err(format!("{} does not support `ask` rules", self.name));
+        }
+        if let Some(sandbox) = permissions.sandbox {
+            return Err(format!("{} has no sandbox mode {:?}", self.name, sandbox));
+        }
+        let auto_accept = match permissions.approval {
+            None => None,
+            Some(ApprovalPolicy::OnRequest) => Some(true),
+            Some(ApprovalPolicy::Untrusted) => Some(false),
+            Some(other) => {
+                return Err(format!("{} has no approval policy {:?}", self.name, other))
+            }
+        };
+        let list = |rules: &[String]| -> Option<serde_json::Value> {
+            if rules.is_empty() {
+                None
+            } else {
+                Some(rules.iter().map(|r| rule_to_gemini(r)).collect::<Vec<_>>().into())
+            }
+        };
+
+        let mut settings = self.load_settings(scope)?;
+        let values = [
+            ("core", "coreTools", list(&permissions.allow)),
+            ("exclude", "excludeTools", list(&permissions.deny)),
+            ("autoAccept", "autoAccept", auto_accept.map(serde_json::Value::from)),
+        ];
+        // Newer settings files group these under `tools`; keep whichever
+        // layout the file already uses.
+        match settings.other.get_mut("tools").and_then(|t| t.as_object_mut()) {
+            Some(tools) => {
+                for (key, _, value) in values {
+                    match value {
+                        Some(v) => tools.insert(key.to_string(), v),
+                        None => tools.shift_remove(key),
+                    };
+                }
+            }
+            None => {
+                for (_, key, value) in values {
+                    match value {
+                        Some(v) => settings.other.insert(key.to_string(), v),
+                        None => settings.other.shift_remove(key),
+                    };
+                }
+            }
+        }
+        self.save_settings(&settings, scope)
+    }
+
+    /// `model.name`, or a legacy top-level `model` string.
+    fn read_model_settings(&self, scope: &ConfigScope) -> Result<ModelSettings, String> {
+        let settings = self.load_settings(scope)?;
+        let model = match settings.other.get("model") {
+            Some(serde_json::Value::String(name)) => Some(name.clone()),
+            Some(v) => v.get("name").and_then(|n| n.as_str()).map(String::from),
+            None => None,
+        };
+        Ok(ModelSettings { model, ..Default::default() })
+    }
+
+    fn write_model_settings(
+        &self,
+        model: &ModelSettings,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        if model.provider.is_some() || !model.providers.is_empty() {
+            return Err(format!("{} does not support model providers", self.name));
+        }
+        let mut settings = self.load_settings(scope)?;
+        match (settings.other.get_mut("model"), &model.model) {
+            (Some(serde_json::Value::Object(obj)), name) => {
+                match name {
+                    Some(n) => obj.insert("name".into(), n.clone().into()),
+                    None => obj.shift_remove("name"),
+                };
+            }
+            (Some(serde_json::Value::String(_)), Some(n)) => {
+                settings.other.insert("model".into(), n.clone().into());
+            }
+            (_, Some(n)) => {
+                settings.other.insert("model".into(), serde_json::json!({ "name": n }));
+            }
+            (_, None) => {
+                settings.other.shift_remove("model");
+            }
+        }
+        self.save_settings(&settings, scope)
+    }
+
+    /// The CLI loads every configured context file, so all that exist are
+    /// shown, in configured order.
     fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
-        let path = self.rules_path(scope);
-        match fs::read_to_string(&path) {
-            Ok(c) => Ok(c),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
-            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        let mut parts = Vec::new();
+        for path in self.rules_paths(scope) {
+            match fs::read_to_string(&path) {
+                Ok(c) => parts.push(c),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+            }
         }
+        Ok(parts.join("\n\n"))
     }
 
+    /// Writes the one existing context file, or the first configured name.
+    /// Rules split across several files are left for the user to edit.
     fn write_rules(&self, content: &str, scope: &ConfigScope) -> Result<(), String> {
-        let path = self.rules_path(scope);
+        let paths = self.rules_paths(scope);
+        let existing: Vec<&PathBuf> = paths.iter().filter(|p| p.is_file()).collect();
+        let path = match existing.as_slice() {
+            [] => paths.first().ok_or("No context file configured")?,
+            [one] => *one,
+            many => {
+                let names: Vec<String> = many.iter().map(|p| p.display().to_string()).collect();
+                return Err(format!("Rules are split across {}", names.join(", ")));
+            }
+        };
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
         }
-        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
 }
@@ -0,0 +1,507 @@
+//! Minimal JSONC support (JSON with `//`, `/* */` comments and trailing commas).
+//!
+//! Reading blanks out comments and trailing commas and hands the text to
+//! serde_json. Editing splices new text into the original document, so
+//! comments and formatting outside the edited value are left untouched.
+
+use serde_json::Value;
+
+pub fn parse(text: &str) -> Result<Value, String> {
+    if text.trim().is_empty() {
+        return Ok(Value::Object(Default::default()));
+    }
+    let stripped = strip_trailing_commas(&strip_comments(text));
+    serde_json::from_str(&stripped).map_err(|e| e.to_string())
+}
+
+/// Sets the value at `path` (a chain of object keys), creating missing
+/// intermediate objects.
+pub fn set(text: &str, path: &[&str], value: &Value) -> Result<String, String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Err("Empty JSON path".into());
+    };
+    let text = if text.trim().is_empty() { "{}\n" } else { text };
+    let stripped = strip_comments(text);
+    let root = Parser::new(stripped.as_bytes()).parse_document()?;
+
+    let mut node = &root;
+    for (i, key) in parents.iter().enumerate() {
+        let Node::Object { members, .. } = node else {
+            return Err(format!("`{}` is not an object", path[..i].join(".")));
+        };
+        match members.iter().find(|m| m.key == *key) {
+            Some(m) => node = &m.value,
+            None => {
+                let nested = path[i + 1..]
+                    .iter()
+                    .rev()
+                    .fold(value.clone(), |acc, k| {
+                        Value::Object([(k.to_string(), acc)].into_iter().collect())
+                    });
+                return insert_member(text, &stripped, node, key, &nested);
+            }
+        }
+    }
+
+    let Node::Object { members, .. } = node else {
+        return Err(format!("`{}` is not an object", parents.join(".")));
+    };
+    match members.iter().find(|m| m.key == *last) {
+        Some(m) => {
+            let (start, end) = m.value.span();
+            let indent = line_indent(text, m.start);
+            Ok(splice(text, start, end, &render(value, indent)))
+        }
+        None => insert_member(text, &stripped, node, last, value),
+    }
+}
+
+/// Removes the member at `path`. Missing keys leave the text unchanged.
+pub fn remove(text: &str, path: &[&str]) -> Result<String, String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Err("Empty JSON path".into());
+    };
+    if text.trim().is_empty() {
+        return Ok(text.to_string());
+    }
+    let stripped = strip_comments(text);
+    let bytes = stripped.as_bytes();
+    let root = Parser::new(bytes).parse_document()?;
+
+    let mut node = &root;
+    for key in parents {
+        let Node::Object { members, .. } = node else {
+            return Ok(text.to_string());
+        };
+        match members.iter().find(|m| m.key == *key) {
+            Some(m) => node = &m.value,
+            None => return Ok(text.to_string()),
+        }
+    }
+    let Node::Object { members, .. } = node else {
+        return Ok(text.to_string());
+    };
+    let Some(idx) = members.iter().position(|m| m.key == *last) else {
+        return Ok(text.to_string());
+    };
+
+    let member = &members[idx];
+    let mut start = member.start;
+    let mut end = member.value.span().1;
+    let after = skip_inline_ws(bytes, end);
+    let has_comma = bytes.get(after) == Some(&b',');
+
+    if !has_comma && idx > 0 {
+        // Last member without a trailing comma: drop the separator before it.
+        let prev_end = members[idx - 1].value.span().1;
+        if let Some(comma) = bytes[prev_end..start].iter().position(|b| *b == b',') {
+            start = prev_end + comma;
+        }
+        return Ok(splice(text, start, end, ""));
+    }
+
+    if has_comma {
+        end = after + 1;
+    }
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    if bytes[line_start..start].iter().all(|b| b.is_ascii_whitespace()) {
+        let tail = skip_inline_ws(bytes, end);
+        if bytes.get(tail) == Some(&b'\n') {
+            start = line_start;
+            end = tail + 1;
+        }
+    }
+    Ok(splice(text, start, end, ""))
+}
+
+fn insert_member(
+    text: &str,
+    stripped: &str,
+    object: &Node,
+    key: &str,
+    value: &Value,
+) -> Result<String, String> {
+    let Node::Object { start, end, members } = object else {
+        return Err(format!("Cannot insert `{}` into a non-object value", key));
+    };
+    let bytes = stripped.as_bytes();
+    let key_json = serde_json::to_string(key).map_err(|e| e.to_string())?;
+    let close = end - 1;
+
+    let Some(last) = members.last() else {
+        let outer = line_indent(text, *start);
+        let inner = format!("{}{}", outer, indent_unit(text));
+        let body = format!("{{\n{}{}: {}\n{}}}", inner, key_json, render(value, &inner), outer);
+        return Ok(splice(text, *start, *end, &body));
+    };
+
+    let last_end = last.value.span().1;
+    let after = skip_inline_ws(bytes, last_end);
+    let has_trailing_comma = bytes[after..close].contains(&b',');
+    let close_line = text[..close].rfind('\n').map_or(0, |i| i + 1);
+    let own_line = close_line > last_end
+        && bytes[close_line..close].iter().all(|b| b.is_ascii_whitespace());
+
+    if !own_line {
+        let compact = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        let sep = if has_trailing_comma { " " } else { ", " };
+        let at = if has_trailing_comma { close } else { last_end };
+        return Ok(splice(text, at, at, &format!("{}{}: {}", sep, key_json, compact)));
+    }
+
+    let indent = line_indent(text, last.start);
+    let mut entry = format!("{}{}: {}", indent, key_json, render(value, indent));
+    if has_trailing_comma {
+        entry.push(',');
+    }
+    entry.push('\n');
+    let out = splice(text, close_line, close_line, &entry);
+    if has_trailing_comma {
+        Ok(out)
+    } else {
+        Ok(splice(&out, last_end, last_end, ","))
+    }
+}
+
+fn splice(text: &str, start: usize, end: usize, with: &str) -> String {
+    let mut out = String::with_capacity(text.len() + with.len());
+    out.push_str(&text[..start]);
+    out.push_str(with);
+    out.push_str(&text[end..]);
+    out
+}
+
+fn render(value: &Value, indent: &str) -> String {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".into());
+    pretty.replace('\n', &format!("\n{}", indent))
+}
+
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line_start = text[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..pos];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn indent_unit(text: &str) -> &str {
+    text.lines()
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .find(|ws| !ws.is_empty())
+        .unwrap_or("  ")
+}
+
+fn skip_inline_ws(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && (bytes[pos] == b' ' || bytes[pos] == b'\t' || bytes[pos] == b'\r') {
+        pos += 1;
+    }
+    pos
+}
+
+/// Replaces comments with spaces, keeping byte offsets and line breaks intact.
+fn strip_comments(text: &str) -> String {
+    let mut out = text.as_bytes().to_vec();
+    let mut i = 0;
+    let mut in_string = false;
+    while i < out.len() {
+        let b = out[i];
+        if in_string {
+            match b {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match (b, out.get(i + 1)) {
+            (b'"', _) => in_string = true,
+            (b'/', Some(b'/')) => {
+                while i < out.len() && out[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                let mut closed = false;
+                while i < out.len() {
+                    if out[i] == b'*' && out.get(i + 1) == Some(&b'/') {
+                        out[i] = b' ';
+                        out[i + 1] = b' ';
+                        i += 2;
+                        closed = true;
+                        break;
+                    }
+                    if out[i] != b'\n' {
+                        out[i] = b' ';
+                    }
+                    i += 1;
+                }
+                if !closed {
+                    break;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    // Only ASCII bytes outside strings were touched, so this stays valid UTF-8.
+    String::from_utf8(out).unwrap_or_default()
+}
+
+fn strip_trailing_commas(text: &str) -> String {
+    let mut out = text.as_bytes().to_vec();
+    let mut in_string = false;
+    let mut i = 0;
+    while i < out.len() {
+        let b = out[i];
+        if in_string {
+            match b {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+        } else if b == b'"' {
+            in_string = true;
+        } else if b == b',' {
+            let next = out[i + 1..].iter().find(|c| !c.is_ascii_whitespace());
+            if matches!(next, Some(b'}') | Some(b']')) {
+                out[i] = b' ';
+            }
+        }
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_default()
+}
+
+enum Node {
+    Object {
+        start: usize,
+        end: usize,
+        members: Vec<Member>,
+    },
+    Other {
+        start: usize,
+        end: usize,
+    },
+}
+
+struct Member {
+    key: String,
+    start: usize,
+    value: Node,
+}
+
+impl Node {
+    fn span(&self) -> (usize, usize) {
+        match self {
+            Node::Object { start, end, .. } | Node::Other { start, end } => (*start, *end),
+        }
+    }
+}
+
+/// Span-tracking parser over comment-stripped text. It only records object
+/// structure; other values are kept as opaque spans.
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a [u8]) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse_document(mut self) -> Result<Node, String> {
+        let node = self.parse_value()?;
+        self.skip_ws();
+        if self.pos != self.src.len() {
+            return Err(format!("Unexpected content at offset {}", self.pos));
+        }
+        Ok(node)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.pos < self.src.len() && self.src[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn parse_value(&mut self) -> Result<Node, String> {
+        self.skip_ws();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b']') => break,
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            self.parse_value()?;
+                        }
+                        None => return Err("Unterminated array".into()),
+                    }
+                }
+                self.pos += 1;
+                Ok(Node::Other { start, end: self.pos })
+            }
+            Some(b'"') => {
+                self.parse_string()?;
+                Ok(Node::Other { start, end: self.pos })
+            }
+            Some(_) => {
+                while let Some(b) = self.peek() {
+                    if b.is_ascii_whitespace() || matches!(b, b',' | b'}' | b']') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(format!("Unexpected character at offset {}", start));
+                }
+                Ok(Node::Other { start, end: self.pos })
+            }
+            None => Err("Unexpected end of input".into()),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut members = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Node::Object { start, end: self.pos, members });
+                }
+                Some(b',') => self.pos += 1,
+                Some(b'"') => {
+                    let key_start = self.pos;
+                    let key = self.parse_string()?;
+                    self.skip_ws();
+                    if self.peek() != Some(b':') {
+                        return Err(format!("Expected `:` at offset {}", self.pos));
+                    }
+                    self.pos += 1;
+                    let value = self.parse_value()?;
+                    members.push(Member { key, start: key_start, value });
+                }
+                Some(_) => return Err(format!("Expected object key at offset {}", self.pos)),
+                None => return Err("Unterminated object".into()),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => self.pos += 1,
+                b'"' => {
+                    let raw = std::str::from_utf8(&self.src[start..self.pos])
+                        .map_err(|e| e.to_string())?;
+                    return serde_json::from_str(raw).map_err(|e| e.to_string());
+                }
+                _ => {}
+            }
+        }
+        Err("Unterminated string".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DOC: &str = r#"// Zed settings
+{
+  "theme": "One Dark", // keep me
+  /* block */
+  "context_servers": {
+    "a": { "command": { "path": "a" } },
+  },
+}
+"#;
+
+    #[test]
+    fn parse_ignores_comments_and_trailing_commas() {
+        let v = parse(DOC).unwrap();
+        assert_eq!(v["theme"], "One Dark");
+        assert_eq!(v["context_servers"]["a"]["command"]["path"], "a");
+    }
+
+    #[test]
+    fn parse_keeps_comment_markers_inside_strings() {
+        let v = parse(r#"{ "url": "http://x//y", "glob": "/*.rs" }"#).unwrap();
+        assert_eq!(v["url"], "http://x//y");
+        assert_eq!(v["glob"], "/*.rs");
+    }
+
+    #[test]
+    fn set_replaces_existing_value_and_keeps_comments() {
+        let out = set(DOC, &["theme"], &json!("Ayu")).unwrap();
+        assert!(out.contains(r#""theme": "Ayu", // keep me"#));
+        assert!(out.contains("/* block */"));
+        assert!(out.starts_with("// Zed settings"));
+    }
+
+    #[test]
+    fn set_inserts_into_existing_object() {
+        let out = set(DOC, &["context_servers", "b"], &json!({ "source": "custom" })).unwrap();
+        let v = parse(&out).unwrap();
+        assert_eq!(v["context_servers"]["b"]["source"], "custom");
+        assert_eq!(v["context_servers"]["a"]["command"]["path"], "a");
+        assert!(out.contains("// keep me"));
+    }
+
+    #[test]
+    fn set_creates_missing_parents() {
+        let out = set("{\n  \"theme\": \"x\"\n}\n", &["context_servers", "a"], &json!(1)).unwrap();
+        let v = parse(&out).unwrap();
+        assert_eq!(v["context_servers"]["a"], 1);
+        assert_eq!(v["theme"], "x");
+    }
+
+    #[test]
+    fn set_on_empty_document() {
+        let out = set("", &["a", "b"], &json!(true)).unwrap();
+        assert_eq!(parse(&out).unwrap()["a"]["b"], true);
+    }
+
+    #[test]
+    fn set_into_single_line_object() {
+        let out = set(r#"{"a": 1}"#, &["b"], &json!(2)).unwrap();
+        assert_eq!(out, r#"{"a": 1, "b": 2}"#);
+    }
+
+    #[test]
+    fn remove_member_and_keeps_rest() {
+        let out = remove(DOC, &["context_servers", "a"]).unwrap();
+        let v = parse(&out).unwrap();
+        assert!(v["context_servers"].as_object().unwrap().is_empty());
+        assert!(out.contains("// keep me"));
+    }
+
+    #[test]
+    fn remove_last_member_drops_separator() {
+        let out = remove("{\n  \"a\": 1,\n  \"b\": 2\n}", &["b"]).unwrap();
+        assert_eq!(out, "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn remove_missing_is_noop() {
+        assert_eq!(remove(DOC, &["nope", "x"]).unwrap(), DOC);
+    }
+}
@@ -0,0 +1,479 @@
+use super::jsonc;
+use crate::core::{
+    delete_md_command, read_md_commands, render_skill_md, scan_skills_dir, write_md_command,
+    Command, CommandCapabilities, ConfigAdapter, ConfigScope, ManifestFormat, McpCapabilities,
+    McpServer, ModelCapabilities, ModelSettings, PermissionCapabilities, Permissions,
+    RulesCapabilities, Skill, SkillCapabilities, SkillScan, ToolCapabilities, ToolManifest,
+    Transport,
+};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table};
+
+/// A server entry as a TOML table, nested tables included.
+fn toml_item(entry: Value) -> Result<Item, String> {
+    let value = toml::Value::try_from(entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+    let text = toml::to_string(&value).map_err(|e| format!("Failed to serialize: {}", e))?;
+    let doc = text.parse::<DocumentMut>().map_err(|e| format!("Failed to serialize: {}", e))?;
+    Ok(Item::Table(doc.as_table().clone()))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    use std::path::Component;
+    let path = std::path::Path::new(name);
+    let components: Vec<_> = path.components().collect();
+    if components.len() != 1 {
+        return Err(format!("Invalid name: {}", name));
+    }
+    match components.first() {
+        Some(Component::Normal(_)) => Ok(()),
+        _ => Err(format!("Invalid name: {}", name)),
+    }
+}
+
+/// Generic adapter driven by a user-supplied [`ToolManifest`].
+pub struct ManifestAdapter {
+    manifest: ToolManifest,
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+impl ManifestAdapter {
+    pub fn new(manifest: ToolManifest) -> Self {
+        Self { manifest }
+    }
+
+    /// Derived from which paths and fields the manifest declares.
+    pub fn capabilities(&self) -> ToolCapabilities {
+        let m = &self.manifest;
+        let mut transports = vec![Transport::Stdio];
+        if m.fields.url.is_some() {
+            transports.push(Transport::Http);
+        }
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports,
+                enabled: m.fields.enabled.is_some(),
+                global: m.config.global.is_some(),
+                project: m.config.project.is_some(),
+                ..Default::default()
+            },
+            skills: SkillCapabilities {
+                global: m.skills.global.is_some(),
+                project: m.skills.project.is_some(),
+                resources: m.skills.global.is_some() || m.skills.project.is_some(),
+            },
+            rules: RulesCapabilities {
+                global: m.rules.global.is_some(),
+                project: m.rules.project.is_some(),
+                imports: false,
+            },
+            commands: CommandCapabilities {
+                global: m.commands.global.is_some(),
+                project: m.commands.project.is_some(),
+                arguments: "$ARGUMENTS",
+                ..Default::default()
+            },
+            permissions: PermissionCapabilities::default(),
+            models: ModelCapabilities::default(),
+        }
+    }
+
+    fn config_path(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        self.manifest
+            .config_path(scope)
+            .ok_or_else(|| format!("{} has no config for this scope", self.manifest.name))
+    }
+
+    fn skills_dir(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        self.manifest
+            .skills_dir(scope)
+            .ok_or_else(|| format!("{} does not support skills in this scope", self.manifest.name))
+    }
+
+    fn prompts_dir(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        self.manifest
+            .commands_dir(scope)
+            .ok_or_else(|| format!("{} does not support commands in this scope", self.manifest.name))
+    }
+
+    fn rules_path(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        self.manifest
+            .rules_path(scope)
+            .ok_or_else(|| format!("{} has no rules file in this scope", self.manifest.name))
+    }
+
+    fn read_text(&self, path: &Path) -> Result<String, String> {
+        match fs::read_to_string(path) {
+            Ok(c) => Ok(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn parse_document(&self, content: &str, path: &Path) -> Result<Value, String> {
+        match self.manifest.format {
+            ManifestFormat::Json | ManifestFormat::Jsonc => jsonc::parse(content)
+                .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+            ManifestFormat::Toml => {
+                let table: toml::Table = toml::from_str(content)
+                    .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+                serde_json::to_value(table)
+                    .map_err(|e| format!("Failed to convert {}: {}", path.display(), e))
+            }
+        }
+    }
+
+    fn server_map<'a>(&self, doc: &'a Value) -> Option<&'a Map<String, Value>> {
+        self.manifest
+            .server_path_segments()
+            .iter()
+            .try_fold(doc, |v, key| v.get(key))?
+            .as_object()
+    }
+
+    fn entry_to_server(&self, name: String, entry: &Value) -> McpServer {
+        let fields = &self.manifest.fields;
+        let (command, mut args) = match entry.get(&fields.command) {
+            Some(Value::String(s)) => (s.clone(), Vec::new()),
+            Some(v @ Value::Array(_)) => {
+                let mut parts = string_list(Some(v));
+                if parts.is_empty() {
+                    (String::new(), Vec::new())
+                } else {
+                    let cmd = parts.remove(0);
+                    (cmd, parts)
+                }
+            }
+            _ => (String::new(), Vec::new()),
+        };
+        if !fields.command_array {
+            args.extend(string_list(entry.get(&fields.args)));
+        }
+        let env = entry
+            .get(&fields.env)
+            .and_then(Value::as_object)
+            .map(|m| {
+                m.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let url = fields
+            .url
+            .as_ref()
+            .and_then(|k| entry.get(k))
+            .and_then(Value::as_str)
+            .map(String::from);
+        let enabled = fields
+            .enabled
+            .as_ref()
+            .and_then(|k| entry.get(k))
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        McpServer { name, command, args, env, url, enabled }
+    }
+
+    fn server_to_entry(&self, server: &McpServer, existing: Option<&Value>) -> Value {
+        let fields = &self.manifest.fields;
+        let mut entry = existing
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+
+        let is_remote = server.url.as_ref().is_some_and(|u| !u.trim().is_empty());
+        match (&fields.url, is_remote) {
+            (Some(key), true) => {
+                entry.insert(key.clone(), Value::String(server.url.clone().unwrap_or_default()));
+            }
+            (Some(key), false) => {
+                entry.shift_remove(key);
+            }
+            (None, _) => {}
+        }
+
+        if server.command.is_empty() && server.args.is_empty() {
+            entry.shift_remove(&fields.command);
+            entry.shift_remove(&fields.args);
+        } else if fields.command_array {
+            let mut parts = vec![server.command.clone()];
+            parts.extend(server.args.iter().cloned());
+            entry.insert(fields.command.clone(), Value::from(parts));
+        } else {
+            entry.insert(fields.command.clone(), Value::String(server.command.clone()));
+            if server.args.is_empty() {
+                entry.shift_remove(&fields.args);
+            } else {
+                entry.insert(fields.args.clone(), Value::from(server.args.clone()));
+            }
+        }
+
+        if server.env.is_empty() {
+            entry.shift_remove(&fields.env);
+        } else {
+            let env: Map<String, Value> = server
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                .collect();
+            entry.insert(fields.env.clone(), Value::Object(env));
+        }
+
+        if let Some(key) = &fields.enabled {
+            entry.insert(key.clone(), Value::Bool(server.enabled));
+        }
+        Value::Object(entry)
+    }
+
+    /// Sets or removes one server entry through `toml_edit`, so comments and
+    /// layout elsewhere in the file survive.
+    fn write_toml_entry(
+        &self,
+        content: &str,
+        path: &Path,
+        name: &str,
+        entry: Option<Value>,
+    ) -> Result<String, String> {
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
+        let mut table = doc.as_table_mut();
+        for key in self.manifest.server_path_segments() {
+            if !table.contains_key(&key) {
+                let mut created = Table::new();
+                created.set_implicit(true);
+                table.insert(&key, Item::Table(created));
+            }
+            table = table[key.as_str()]
+                .as_table_mut()
+                .ok_or_else(|| format!("`{}` is not a table in {}", key, path.display()))?;
+        }
+        match entry {
+            Some(v) => table.insert(name, toml_item(v)?),
+            None => table.remove(name),
+        };
+        Ok(doc.to_string())
+    }
+}
+
+impl ConfigAdapter for ManifestAdapter {
+    fn tool_name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn global_config_path(&self) -> PathBuf {
+        self.manifest.global_root().unwrap_or_default()
+    }
+
+    fn project_config_path(&self, project: &PathBuf) -> PathBuf {
+        match self.manifest.detect_dir() {
+            Some(dir) => project.join(dir),
+            None => project.clone(),
+        }
+    }
+
+    fn read_mcp_servers(&self, scope: &ConfigScope) -> Result<Vec<McpServer>, String> {
+        let Some(path) = self.manifest.config_path(scope) else {
+            return Ok(vec![]);
+        };
+        let content = self.read_text(&path)?;
+        let doc = self.parse_document(&content, &path)?;
+
+        let mut servers: Vec<McpServer> = self
+            .server_map(&doc)
+            .map(|m| {
+                m.iter()
+                    .map(|(name, entry)| self.entry_to_server(name.clone(), entry))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(servers)
+    }
+
+    fn write_mcp_server(&self, server: &McpServer, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope)?;
+        let content = self.read_text(&path)?;
+        let doc = self.parse_document(&content, &path)?;
+        let existing = self.server_map(&doc).and_then(|m| m.get(&server.name));
+        let entry = self.server_to_entry(server, existing);
+
+        let updated = match self.manifest.format {
+            ManifestFormat::Json | ManifestFormat::Jsonc => {
+                let mut keys = self.manifest.server_path_segments();
+                keys.push(server.name.clone());
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                jsonc::set(&content, &keys, &entry)
+                    .map_err(|e| format!("Failed to update {}: {}", path.display(), e))?
+            }
+            ManifestFormat::Toml => self.write_toml_entry(&content, &path, &server.name, Some(entry))?,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn delete_mcp_server(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope)?;
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        let updated = match self.manifest.format {
+            ManifestFormat::Json | ManifestFormat::Jsonc => {
+                let mut keys = self.manifest.server_path_segments();
+                keys.push(name.to_string());
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                jsonc::remove(&content, &keys)
+                    .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?
+            }
+            ManifestFormat::Toml => self.write_toml_entry(&content, &path, name, None)?,
+        };
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope)?;
+        let content = self.read_text(&path)?;
+        let doc = self.parse_document(&content, &path)?;
+        let servers = self.server_map(&doc);
+
+        if servers.is_some_and(|m| m.contains_key(new)) {
+            return Err(format!("Server already exists: {}", new));
+        }
+        let entry = servers
+            .and_then(|m| m.get(old))
+            .cloned()
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+
+        let updated = match self.manifest.format {
+            ManifestFormat::Json | ManifestFormat::Jsonc => {
+                let keys_for = |name: &str| {
+                    let mut keys = self.manifest.server_path_segments();
+                    keys.push(name.to_string());
+                    keys
+                };
+                let (old_keys, new_keys) = (keys_for(old), keys_for(new));
+                let old_keys: Vec<&str> = old_keys.iter().map(String::as_str).collect();
+                let new_keys: Vec<&str> = new_keys.iter().map(String::as_str).collect();
+                jsonc::remove(&content, &old_keys)
+                    .and_then(|c| jsonc::set(&c, &new_keys, &entry))
+                    .map_err(|e| format!("Failed to update {}: {}", path.display(), e))?
+            }
+            ManifestFormat::Toml => {
+                let removed = self.write_toml_entry(&content, &path, old, None)?;
+                self.write_toml_entry(&removed, &path, new, Some(entry))?
+            }
+        };
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        self.config_path(scope).ok()
+    }
+
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String> {
+        match self.manifest.skills_dir(scope) {
+            Some(dir) => scan_skills_dir(&dir),
+            None => Ok(SkillScan::default()),
+        }
+    }
+
+    fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&skill.name)?;
+        let dir = self.skills_dir(scope)?.join(&skill.name);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
+
+        let content = render_skill_md(skill)?;
+        let path = dir.join("SKILL.md");
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn delete_skill(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(name)?;
+        let dir = self.skills_dir(scope)?.join(name);
+        if !dir.exists() {
+            return Ok(());
+        }
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
+    }
+
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        Ok(self.skills_dir(scope)?.join(name))
+    }
+
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        match self.manifest.commands_dir(scope) {
+            Some(dir) => read_md_commands(&dir),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&command.name)?;
+        write_md_command(&self.prompts_dir(scope)?, command)
+    }
+
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(name)?;
+        delete_md_command(&self.prompts_dir(scope)?, name)
+    }
+
+    fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        self.manifest.commands_dir(scope)
+    }
+
+    fn read_permissions(&self, _scope: &ConfigScope) -> Result<Permissions, String> {
+        Ok(Permissions::default())
+    }
+
+    fn write_permissions(
+        &self,
+        _permissions: &Permissions,
+        _scope: &ConfigScope,
+    ) -> Result<(), String> {
+        Err(format!("{} does not support permissions", self.manifest.name))
+    }
+
+    fn read_model_settings(&self, _scope: &ConfigScope) -> Result<ModelSettings, String> {
+        Ok(ModelSettings::default())
+    }
+
+    fn write_model_settings(
+        &self,
+        _settings: &ModelSettings,
+        _scope: &ConfigScope,
+    ) -> Result<(), String> {
+        Err(format!("{} does not support model settings", self.manifest.name))
+    }
+
+    fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
+        let Some(path) = self.manifest.rules_path(scope) else {
+            return Ok(String::new());
+        };
+        self.read_text(&path)
+    }
+
+    fn write_rules(&self, content: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.rules_path(scope)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
@@ -1,11 +1,76 @@
 mod claude;
 mod codex;
 mod gemini;
+mod jsonc;
+mod manifest;
 mod opencode;
 #[cfg(test)]
 mod tests;
+mod zed;
 
 pub use claude::ClaudeAdapter;
 pub use codex::CodexAdapter;
 pub use gemini::GeminiAdapter;
+pub use manifest::ManifestAdapter;
 pub use opencode::OpenCodeAdapter;
+pub use zed::ZedAdapter;
+
+use crate::core::{ConfigFormat, ManifestFormat, ToolDescriptor, ToolManifest, ToolType};
+
+/// Every built-in tool. Adding a tool means adding its entry here.
+pub fn builtin_tools() -> Vec<ToolDescriptor> {
+    use ConfigFormat::{Json, Toml};
+    let gemini = GeminiAdapter::capabilities;
+    vec![
+        ToolDescriptor::new(
+            ToolType::CLAUDE_CODE,
+            "Claude Code",
+            Json,
+            ClaudeAdapter::capabilities(),
+            || Box::new(ClaudeAdapter),
+        ),
+        ToolDescriptor::new(
+            ToolType::CODEX,
+            "Codex",
+            Toml,
+            CodexAdapter::capabilities(),
+            || Box::new(CodexAdapter),
+        ),
+        ToolDescriptor::new(ToolType::GEMINI, "Gemini", Json, gemini(), || {
+            Box::new(GeminiAdapter::gemini())
+        }),
+        ToolDescriptor::new(
+            ToolType::OPENCODE,
+            "OpenCode",
+            Json,
+            OpenCodeAdapter::capabilities(),
+            || Box::new(OpenCodeAdapter),
+        ),
+        ToolDescriptor::new(
+            ToolType::ZED,
+            "Zed",
+            Json,
+            ZedAdapter::capabilities(),
+            || Box::new(ZedAdapter),
+        ),
+        ToolDescriptor::new(ToolType::QWEN, "Qwen Code", Json, gemini(), || {
+            Box::new(GeminiAdapter::qwen())
+        }),
+        ToolDescriptor::new(ToolType::IFLOW, "iFlow CLI", Json, gemini(), || {
+            Box::new(GeminiAdapter::iflow())
+        }),
+    ]
+}
+
+pub fn manifest_tool(manifest: ToolManifest) -> ToolDescriptor {
+    let format = match manifest.format {
+        ManifestFormat::Toml => ConfigFormat::Toml,
+        ManifestFormat::Json | ManifestFormat::Jsonc => ConfigFormat::Json,
+    };
+    let capabilities = ManifestAdapter::new(manifest.clone()).capabilities();
+    let (id, name) = (ToolType::new(manifest.id.clone()), manifest.name.clone());
+    ToolDescriptor::new(id, name, format, capabilities, move || {
+        Box::new(ManifestAdapter::new(manifest.clone()))
+    })
+    .custom()
+}
@@ -1,4 +1,9 @@
-use crate::core::{ConfigAdapter, ConfigScope, McpServer, Skill};
+use crate::core::{
+    render_skill_md, scan_skills_dir, split_rule, Command, CommandCapabilities, ConfigAdapter,
+    ConfigScope, McpCapabilities, McpServer, ModelCapabilities, ModelProvider, ModelSettings,
+    PermissionCapabilities, Permissions, RulesCapabilities, Skill, SkillCapabilities, SkillScan,
+    ToolCapabilities, Transport,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
@@ -23,10 +28,25 @@ pub struct OpenCodeAdapter;
 struct OpenCodeConfig {
     #[serde(default)]
     mcp: BTreeMap<String, OpenCodeMcpEntry>,
+    /// Raw entries, parsed one at a time so a bad command breaks nothing else.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    command: serde_json::Map<String, serde_json::Value>,
     #[serde(flatten)]
     other: BTreeMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct OpenCodeCommandEntry {
+    template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    /// `agent`, `subtask` and anything newer.
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 struct OpenCodeMcpEntry {
     #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
@@ -43,26 +63,6 @@ struct OpenCodeMcpEntry {
     extra: BTreeMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct SkillFrontmatter {
-    name: String,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
-}
-
-fn parse_skill_frontmatter(content: &str) -> Option<(SkillFrontmatter, String)> {
-    let content = content.trim_start();
-    if !content.starts_with("---") {
-        return None;
-    }
-    let rest = &content[3..];
-    let end = rest.find("\n---")?;
-    let yaml = &rest[..end];
-    let body = rest[end + 4..].trim_start().to_string();
-    let fm: SkillFrontmatter = serde_yaml::from_str(yaml).ok()?;
-    Some((fm, body))
-}
-
 impl OpenCodeAdapter {
     fn config_path(&self, scope: &ConfigScope) -> PathBuf {
         match scope {
@@ -96,6 +96,207 @@ impl OpenCodeAdapter {
     fn rules_fallback_path(&self) -> PathBuf {
         dirs::home_dir().unwrap_or_default().join(".claude").join("CLAUDE.md")
     }
+
+    fn load_config(&self, scope: &ConfigScope) -> Result<OpenCodeConfig, String> {
+        let path = self.config_path(scope);
+        match fs::read_to_string(&path) {
+            Ok(c) => serde_json::from_str(&c)
+                .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(OpenCodeConfig::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save_config(&self, config: &OpenCodeConfig, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(config)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn capabilities() -> ToolCapabilities {
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports: vec![Transport::Stdio, Transport::Http],
+                headers: true,
+                timeouts: true,
+                global: true,
+                project: true,
+                ..Default::default()
+            },
+            skills: SkillCapabilities { global: true, project: true, resources: true },
+            rules: RulesCapabilities { global: true, project: true, imports: false },
+            commands: CommandCapabilities {
+                global: true,
+                project: true,
+                arguments: "$ARGUMENTS",
+                positional: true,
+                model: true,
+                ..Default::default()
+            },
+            permissions: PermissionCapabilities {
+                global: true,
+                project: true,
+                rules: true,
+                ask: true,
+                rule_tools: &["Edit", "Bash", "WebFetch"],
+                ..Default::default()
+            },
+            models: ModelCapabilities {
+                global: true,
+                project: true,
+                provider: true,
+                providers: true,
+            },
+        }
+    }
+}
+
+/// `permission` keys and their Claude Code tool names.
+const PERMISSION_KEYS: &[(&str, &str)] =
+    &[("edit", "Edit"), ("bash", "Bash"), ("webfetch", "WebFetch")];
+
+/// A `permission.bash` pattern as a rule: `git *` becomes `Bash(git:*)` and
+/// `*` the bare tool.
+fn pattern_rule(tool: &str, pattern: &str) -> String {
+    if pattern.trim() == "*" {
+        return tool.to_string();
+    }
+    match pattern.strip_suffix('*') {
+        Some(prefix) if tool == "Bash" => format!("Bash({}:*)", prefix.trim_end()),
+        _ => format!("{}({})", tool, pattern),
+    }
+}
+
+fn rule_pattern(arg: Option<&str>) -> String {
+    match arg {
+        None => "*".into(),
+        Some(a) => match a.strip_suffix(":*") {
+            Some(prefix) => format!("{} *", prefix),
+            None => a.to_string(),
+        },
+    }
+}
+
+fn read_permission_object(value: Option<&serde_json::Value>) -> Permissions {
+    let mut permissions = Permissions::default();
+    let Some(obj) = value.and_then(|v| v.as_object()) else {
+        return permissions;
+    };
+    for (key, value) in obj {
+        let tool = PERMISSION_KEYS.iter().find(|(k, _)| k == key).map_or(key.as_str(), |(_, t)| t);
+        let entries: Vec<(String, &str)> = match value {
+            serde_json::Value::String(action) => vec![(tool.to_string(), action.as_str())],
+            serde_json::Value::Object(patterns) => patterns
+                .iter()
+                .filter_map(|(p, a)| Some((pattern_rule(tool, p), a.as_str()?)))
+                .collect(),
+            _ => continue,
+        };
+        for (rule, action) in entries {
+            match action {
+                "allow" => permissions.allow.push(rule),
+                "ask" => permissions.ask.push(rule),
+                "deny" => permissions.deny.push(rule),
+                _ => {}
+            }
+        }
+    }
+    permissions
+}
+
+fn permission_object(permissions: &Permissions) -> serde_json::Map<String, serde_json::Value> {
+    let mut patterns: BTreeMap<String, serde_json::Map<String, serde_json::Value>> =
+        BTreeMap::new();
+    let lists = [
+        ("allow", &permissions.allow),
+        ("ask", &permissions.ask),
+        ("deny", &permissions.deny),
+    ];
+    for (action, rules) in lists {
+        for rule in rules {
+            let (tool, arg) = split_rule(rule);
+            let key = PERMISSION_KEYS.iter().find(|(_, t)| *t == tool).map_or(tool, |(k, _)| k);
+            patterns
+                .entry(key.to_string())
+                .or_default()
+                .insert(rule_pattern(arg), action.into());
+        }
+    }
+    patterns
+        .into_iter()
+        .map(|(key, mut map)| {
+            // A lone catch-all pattern is written as a plain action.
+            let value = match map.shift_remove("*") {
+                Some(action) if map.is_empty() => action,
+                Some(action) => {
+                    map.insert("*".into(), action);
+                    map.into()
+                }
+                None => map.into(),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// A `provider.<id>` entry. `options.baseURL` and an `options.apiKey` of the
+/// form `{env:NAME}` are lifted out; everything else stays in `extra`.
+fn provider_from_json(id: &str, entry: &serde_json::Value) -> ModelProvider {
+    let mut extra = entry.as_object().cloned().unwrap_or_default();
+    let name = match extra.shift_remove("name") {
+        Some(serde_json::Value::String(n)) => Some(n),
+        Some(other) => {
+            extra.insert("name".into(), other);
+            None
+        }
+        None => None,
+    };
+    let (mut base_url, mut env_key) = (None, None);
+    if let Some(options) = extra.get_mut("options").and_then(|o| o.as_object_mut()) {
+        if let Some(url) = options.get("baseURL").and_then(|v| v.as_str()).map(String::from) {
+            options.shift_remove("baseURL");
+            base_url = Some(url);
+        }
+        let key = options
+            .get("apiKey")
+            .and_then(|v| v.as_str())
+            .and_then(|k| k.strip_prefix("{env:")?.strip_suffix('}'))
+            .map(String::from);
+        if let Some(key) = key {
+            options.shift_remove("apiKey");
+            env_key = Some(key);
+        }
+        if options.is_empty() {
+            extra.shift_remove("options");
+        }
+    }
+    ModelProvider { id: id.to_string(), name, base_url, env_key, extra }
+}
+
+fn provider_to_json(provider: &ModelProvider) -> serde_json::Value {
+    let mut entry = provider.extra.clone();
+    if let Some(name) = &provider.name {
+        entry.insert("name".into(), name.clone().into());
+    }
+    if provider.base_url.is_some() || provider.env_key.is_some() {
+        let options = entry
+            .entry("options")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if let Some(options) = options.as_object_mut() {
+            if let Some(url) = &provider.base_url {
+                options.insert("baseURL".into(), url.clone().into());
+            }
+            if let Some(key) = &provider.env_key {
+                options.insert("apiKey".into(), format!("{{env:{}}}", key).into());
+            }
+        }
+    }
+    entry.into()
 }
 
 impl ConfigAdapter for OpenCodeAdapter {
@@ -215,7 +416,32 @@ impl ConfigAdapter for OpenCodeAdapter {
         fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
 
-    fn read_skills(&self, scope: &ConfigScope) -> Result<Vec<Skill>, String> {
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.config_path(scope);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: OpenCodeConfig = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+
+        if config.mcp.contains_key(new) {
+            return Err(format!("Server already exists: {}", new));
+        }
+        let entry = config
+            .mcp
+            .remove(old)
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+        config.mcp.insert(new.to_string(), entry);
+
+        let out = serde_json::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(&path, out).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.config_path(scope))
+    }
+
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String> {
         let dirs: Vec<PathBuf> = match scope {
             ConfigScope::Global => {
                 let mut all = vec![self.primary_skills_dir(scope)];
@@ -225,37 +451,32 @@ impl ConfigAdapter for OpenCodeAdapter {
             ConfigScope::Project(_) => vec![self.primary_skills_dir(scope)],
         };
 
-        let mut skills = Vec::new();
+        let mut scan = SkillScan::default();
         let mut seen_names = std::collections::HashSet::new();
+        let mut seen_dirs = std::collections::HashSet::new();
+        let mut seen_diagnostics = std::collections::HashSet::new();
 
         for dir in dirs {
-            if !dir.is_dir() {
+            // Fallback dirs may be the same folder under another path.
+            if !seen_dirs.insert(dir.canonicalize().unwrap_or_else(|_| dir.clone())) {
                 continue;
             }
-            let Ok(entries) = fs::read_dir(&dir) else { continue };
-
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if !path.is_dir() {
-                    continue;
+            let found = scan_skills_dir(&dir)?;
+            for skill in found.skills {
+                if seen_names.insert(skill.name.clone()) {
+                    scan.skills.push(skill);
                 }
-                let skill_file = path.join("SKILL.md");
-                let Ok(content) = fs::read_to_string(&skill_file) else { continue };
-                if let Some((fm, body)) = parse_skill_frontmatter(&content) {
-                    if seen_names.insert(fm.name.clone()) {
-                        skills.push(Skill {
-                            name: fm.name,
-                            description: fm.description,
-                            content: body,
-                            path: path.clone(),
-                        });
-                    }
+            }
+            for diagnostic in found.diagnostics {
+                let path = &diagnostic.path;
+                if seen_diagnostics.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+                    scan.diagnostics.push(diagnostic);
                 }
             }
         }
 
-        skills.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(skills)
+        scan.skills.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(scan)
     }
 
     fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String> {
@@ -264,12 +485,7 @@ impl ConfigAdapter for OpenCodeAdapter {
         fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
 
-        let fm = SkillFrontmatter { name: skill.name.clone(), description: skill.description.clone() };
-        let yaml = serde_yaml::to_string(&fm)
-            .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
-        let yaml_clean = yaml.trim_start_matches("---\n");
-
-        let content = format!("---\n{}---\n\n{}", yaml_clean, skill.content);
+        let content = render_skill_md(skill)?;
         let path = dir.join("SKILL.md");
         fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
     }
@@ -283,6 +499,138 @@ impl ConfigAdapter for OpenCodeAdapter {
         fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
     }
 
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String> {
+        validate_name(name)?;
+        Ok(self.primary_skills_dir(scope).join(name))
+    }
+
+    /// Entries OpenCode would reject, such as one without a `template`, are
+    /// skipped.
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        let config = self.load_config(scope)?;
+        Ok(config
+            .command
+            .into_iter()
+            .filter_map(|(name, entry)| {
+                let entry: OpenCodeCommandEntry = serde_json::from_value(entry).ok()?;
+                Some(Command {
+                    name,
+                    description: entry.description,
+                    model: entry.model,
+                    content: entry.template,
+                    ..Default::default()
+                })
+            })
+            .collect())
+    }
+
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String> {
+        validate_name(&command.name)?;
+        let mut config = self.load_config(scope)?;
+        let extra = match config.command.get(&command.name) {
+            Some(serde_json::Value::Object(entry)) => entry
+                .iter()
+                .filter(|(key, _)| !["template", "description", "model"].contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        let entry = OpenCodeCommandEntry {
+            template: command.content.clone(),
+            description: command.description.clone(),
+            model: command.model.clone(),
+            extra,
+        };
+        let entry = serde_json::to_value(entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+        config.command.insert(command.name.clone(), entry);
+        self.save_config(&config, scope)
+    }
+
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        if !self.config_path(scope).exists() {
+            return Ok(());
+        }
+        let mut config = self.load_config(scope)?;
+        if config.command.shift_remove(name).is_none() {
+            return Ok(());
+        }
+        self.save_config(&config, scope)
+    }
+
+    fn commands_dir(&self, _scope: &ConfigScope) -> Option<PathBuf> {
+        None
+    }
+
+    fn read_permissions(&self, scope: &ConfigScope) -> Result<Permissions, String> {
+        Ok(read_permission_object(self.load_config(scope)?.other.get("permission")))
+    }
+
+    fn write_permissions(
+        &self,
+        permissions: &Permissions,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        if permissions.approval.is_some() || permissions.sandbox.is_some() {
+            return Err("OpenCode does not support approval policies or sandbox modes".into());
+        }
+        let mut config = self.load_config(scope)?;
+        let object = permission_object(permissions);
+        if object.is_empty() {
+            config.other.remove("permission");
+        } else {
+            config.other.insert("permission".into(), object.into());
+        }
+        self.save_config(&config, scope)
+    }
+
+    /// `model` is stored as `<provider>/<model>`.
+    fn read_model_settings(&self, scope: &ConfigScope) -> Result<ModelSettings, String> {
+        let config = self.load_config(scope)?;
+        let (provider, model) = match config.other.get("model").and_then(|v| v.as_str()) {
+            Some(m) => match m.split_once('/') {
+                Some((p, m)) => (Some(p.to_string()), Some(m.to_string())),
+                None => (None, Some(m.to_string())),
+            },
+            None => (None, None),
+        };
+        let providers = match config.other.get("provider").and_then(|v| v.as_object()) {
+            Some(map) => map.iter().map(|(id, v)| provider_from_json(id, v)).collect(),
+            None => vec![],
+        };
+        Ok(ModelSettings { model, provider, providers })
+    }
+
+    fn write_model_settings(
+        &self,
+        settings: &ModelSettings,
+        scope: &ConfigScope,
+    ) -> Result<(), String> {
+        let model = match (&settings.provider, &settings.model) {
+            (Some(p), Some(m)) => Some(format!("{}/{}", p, m)),
+            (None, Some(m)) => Some(m.clone()),
+            (Some(_), None) => return Err("A provider needs a model".into()),
+            (None, None) => None,
+        };
+        let mut config = self.load_config(scope)?;
+        match model {
+            Some(m) => config.other.insert("model".into(), m.into()),
+            None => config.other.remove("model"),
+        };
+        let mut providers = serde_json::Map::new();
+        for provider in &settings.providers {
+            if provider.id.trim().is_empty() {
+                return Err("Provider id cannot be empty".into());
+            }
+            providers.insert(provider.id.clone(), provider_to_json(provider));
+        }
+        if providers.is_empty() {
+            config.other.remove("provider");
+        } else {
+            config.other.insert("provider".into(), providers.into());
+        }
+        self.save_config(&config, scope)
+    }
+
     fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
         let path = self.rules_path(scope);
         match fs::read_to_string(&path) {
@@ -1,4 +1,6 @@
-use crate::adapters::{ClaudeAdapter, CodexAdapter, GeminiAdapter, OpenCodeAdapter};
+use crate::adapters::{
+    ClaudeAdapter, CodexAdapter, GeminiAdapter, ManifestAdapter, OpenCodeAdapter, ZedAdapter,
+};
 use crate::core::{ConfigAdapter, ConfigScope, McpServer, Skill};
 use std::collections::HashMap;
 use std::fs;
@@ -24,6 +26,7 @@ fn make_skill(name: &str) -> Skill {
         description: Some("Test skill desc".into()),
         content: "# Test content".into(),
         path: PathBuf::new(),
+        ..Default::default()
     }
 }
 
@@ -107,7 +110,7 @@ mod claude_adapter_tests {
         let skill = make_skill("test-skill");
         ClaudeAdapter.write_skill(&skill, &scope).unwrap();
 
-        let skills = ClaudeAdapter.read_skills(&scope).unwrap();
+        let skills = ClaudeAdapter.read_skills(&scope).unwrap().skills;
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "test-skill");
         assert_eq!(skills[0].description, Some("Test skill desc".into()));
@@ -124,7 +127,7 @@ mod claude_adapter_tests {
         ClaudeAdapter.write_skill(&skill, &scope).unwrap();
         ClaudeAdapter.delete_skill("del-skill", &scope).unwrap();
 
-        let skills = ClaudeAdapter.read_skills(&scope).unwrap();
+        let skills = ClaudeAdapter.read_skills(&scope).unwrap().skills;
         assert!(skills.is_empty());
     }
 
@@ -203,7 +206,7 @@ mod codex_adapter_tests {
         let skill = make_skill("codex-skill");
         CodexAdapter.write_skill(&skill, &skill_scope).unwrap();
 
-        let skills = CodexAdapter.read_skills(&skill_scope).unwrap();
+        let skills = CodexAdapter.read_skills(&skill_scope).unwrap().skills;
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "codex-skill");
     }
@@ -234,7 +237,7 @@ mod gemini_adapter_tests {
     #[test]
     fn mcp_read_empty() {
         let (_tmp, scope) = setup();
-        let servers = GeminiAdapter.read_mcp_servers(&scope).unwrap();
+        let servers = GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap();
         assert!(servers.is_empty());
     }
 
@@ -242,9 +245,9 @@ mod gemini_adapter_tests {
     fn mcp_write_and_read() {
         let (tmp, scope) = setup();
         let server = make_mcp_server("gemini-server");
-        GeminiAdapter.write_mcp_server(&server, &scope).unwrap();
+        GeminiAdapter::gemini().write_mcp_server(&server, &scope).unwrap();
 
-        let servers = GeminiAdapter.read_mcp_servers(&scope).unwrap();
+        let servers = GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap();
         assert_eq!(servers.len(), 1);
         assert_eq!(servers[0].name, "gemini-server");
         assert_eq!(servers[0].command, "test-cmd");
@@ -256,9 +259,9 @@ mod gemini_adapter_tests {
     #[test]
     fn mcp_delete() {
         let (_tmp, scope) = setup();
-        GeminiAdapter.write_mcp_server(&make_mcp_server("del"), &scope).unwrap();
-        GeminiAdapter.delete_mcp_server("del", &scope).unwrap();
-        assert!(GeminiAdapter.read_mcp_servers(&scope).unwrap().is_empty());
+        GeminiAdapter::gemini().write_mcp_server(&make_mcp_server("del"), &scope).unwrap();
+        GeminiAdapter::gemini().delete_mcp_server("del", &scope).unwrap();
+        assert!(GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap().is_empty());
     }
 
     #[test]
@@ -267,9 +270,9 @@ mod gemini_adapter_tests {
         fs::create_dir_all(tmp.path().join(".gemini/skills")).unwrap();
         let scope = ConfigScope::Project(tmp.path().clone());
         let skill = make_skill("gemini-skill");
-        GeminiAdapter.write_skill(&skill, &scope).unwrap();
+        GeminiAdapter::gemini().write_skill(&skill, &scope).unwrap();
 
-        let skills = GeminiAdapter.read_skills(&scope).unwrap();
+        let skills = GeminiAdapter::gemini().read_skills(&scope).unwrap().skills;
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "gemini-skill");
     }
@@ -277,9 +280,9 @@ mod gemini_adapter_tests {
     #[test]
     fn rules_write_and_read() {
         let (tmp, scope) = setup();
-        GeminiAdapter.write_rules("# Gemini rules", &scope).unwrap();
+        GeminiAdapter::gemini().write_rules("# Gemini rules", &scope).unwrap();
 
-        let rules = GeminiAdapter.read_rules(&scope).unwrap();
+        let rules = GeminiAdapter::gemini().read_rules(&scope).unwrap();
         assert!(rules.contains("# Gemini rules"));
 
         let file = tmp.path().join("GEMINI.md");
@@ -354,7 +357,7 @@ mod opencode_adapter_tests {
         let skill = make_skill("oc-skill");
         OpenCodeAdapter.write_skill(&skill, &scope).unwrap();
 
-        let skills = OpenCodeAdapter.read_skills(&scope).unwrap();
+        let skills = OpenCodeAdapter.read_skills(&scope).unwrap().skills;
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "oc-skill");
     }
@@ -372,9 +375,335 @@ mod opencode_adapter_tests {
     }
 }
 
+mod gemini_fork_tests {
+    use super::*;
+    use crate::adapters::gemini::context_file_names;
+
+    fn setup() -> (TempDir, ConfigScope) {
+        let tmp = TempDir::new("qwen");
+        fs::create_dir_all(tmp.path().join(".qwen")).unwrap();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        (tmp, scope)
+    }
+
+    #[test]
+    fn qwen_uses_own_dir_and_context_file() {
+        let (tmp, scope) = setup();
+        let qwen = GeminiAdapter::qwen();
+        qwen.write_mcp_server(&make_mcp_server("qwen-server"), &scope).unwrap();
+        qwen.write_rules("# Qwen rules", &scope).unwrap();
+
+        assert!(tmp.path().join(".qwen/settings.json").exists());
+        assert!(tmp.path().join("QWEN.md").exists());
+        assert!(!tmp.path().join(".gemini").exists());
+        assert_eq!(qwen.read_mcp_servers(&scope).unwrap()[0].name, "qwen-server");
+        assert!(GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap().is_empty());
+    }
+
+    #[test]
+    fn context_file_name_setting_is_honored() {
+        let tmp = TempDir::new("gemini-ctx");
+        fs::create_dir_all(tmp.path().join(".gemini")).unwrap();
+        fs::write(
+            tmp.path().join(".gemini/settings.json"),
+            r#"{ "context": { "fileName": ["AGENTS.md", "GEMINI.md"] } }"#,
+        )
+        .unwrap();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let gemini = GeminiAdapter::gemini();
+
+        gemini.write_rules("# Shared rules", &scope).unwrap();
+        assert!(tmp.path().join("AGENTS.md").exists());
+        assert!(!tmp.path().join("GEMINI.md").exists());
+        assert_eq!(gemini.read_rules(&scope).unwrap(), "# Shared rules");
+
+        fs::write(tmp.path().join("GEMINI.md"), "# Gemini only").unwrap();
+        assert_eq!(gemini.read_rules(&scope).unwrap(), "# Shared rules\n\n# Gemini only");
+        assert!(gemini.write_rules("x", &scope).unwrap_err().contains("split across"));
+
+        fs::remove_file(tmp.path().join("AGENTS.md")).unwrap();
+        gemini.write_rules("# Edited", &scope).unwrap();
+        assert!(!tmp.path().join("AGENTS.md").exists());
+        assert_eq!(gemini.read_rules(&scope).unwrap(), "# Edited");
+    }
+
+    #[test]
+    fn legacy_context_file_name_and_invalid_names() {
+        let tmp = TempDir::new("gemini-legacy");
+        fs::create_dir_all(tmp.path().join(".gemini")).unwrap();
+        let settings = tmp.path().join(".gemini/settings.json");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let gemini = GeminiAdapter::gemini();
+
+        fs::write(&settings, r#"{ "contextFileName": "RULES.md" }"#).unwrap();
+        gemini.write_rules("legacy", &scope).unwrap();
+        assert!(tmp.path().join("RULES.md").exists());
+
+    }
+
+    #[test]
+    fn context_file_names_fall_back_per_layer() {
+        let tmp = TempDir::new("gemini-layers");
+        let global = tmp.path().join("global.json");
+        let project = tmp.path().join("project.json");
+        let missing = tmp.path().join("missing.json");
+        let jsonc = "{\n  // shared\n  \"context\": { \"fileName\": \"SHARED.md\" },\n}";
+        fs::write(&global, jsonc).unwrap();
+        fs::write(&project, r#"{ "context": { "fileName": "../escape.md" } }"#).unwrap();
+
+        let names = context_file_names(&[global.clone(), project.clone()], "GEMINI.md");
+        assert_eq!(names, vec!["SHARED.md"]);
+        assert_eq!(context_file_names(&[missing, project], "GEMINI.md"), vec!["GEMINI.md"]);
+        fs::write(&global, r#"{ "context": { "fileName": ["A.md", "B.md"] } }"#).unwrap();
+        assert_eq!(context_file_names(&[global], "GEMINI.md"), vec!["A.md", "B.md"]);
+    }
+}
+
+mod zed_adapter_tests {
+    use super::*;
+
+    fn setup() -> (TempDir, ConfigScope) {
+        let tmp = TempDir::new("zed");
+        fs::create_dir_all(tmp.path().join(".zed")).unwrap();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        (tmp, scope)
+    }
+
+    #[test]
+    fn mcp_read_empty() {
+        let (_tmp, scope) = setup();
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert!(servers.is_empty());
+    }
+
+    #[test]
+    fn mcp_write_and_read_nested_command() {
+        let (tmp, scope) = setup();
+        let server = make_mcp_server("zed-server");
+        ZedAdapter.write_mcp_server(&server, &scope).unwrap();
+
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].command, "test-cmd");
+        assert_eq!(servers[0].args, vec!["--arg1", "value"]);
+        assert_eq!(servers[0].env.get("KEY"), Some(&"VAL".to_string()));
+
+        let file = fs::read_to_string(tmp.path().join(".zed/settings.json")).unwrap();
+        assert!(file.contains("context_servers"));
+        assert!(file.contains("\"source\": \"custom\""));
+        assert!(file.contains("\"path\": \"test-cmd\""));
+    }
+
+    #[test]
+    fn mcp_write_preserves_comments() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".zed/settings.json");
+        fs::write(
+            &path,
+            "// my settings\n{\n  \"theme\": \"One Dark\", // theme\n  \"context_servers\": {\n    \"old\": {\n      \"source\": \"custom\",\n      \"command\": { \"path\": \"old-cmd\", \"args\": [] },\n    },\n  },\n}\n",
+        )
+        .unwrap();
+
+        ZedAdapter.write_mcp_server(&make_mcp_server("new"), &scope).unwrap();
+        ZedAdapter.delete_mcp_server("old", &scope).unwrap();
+
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.starts_with("// my settings"));
+        assert!(file.contains("\"theme\": \"One Dark\", // theme"));
+
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "new");
+    }
+
+    #[test]
+    fn mcp_reads_flat_command() {
+        let (tmp, scope) = setup();
+        fs::write(
+            tmp.path().join(".zed/settings.json"),
+            r#"{ "context_servers": { "flat": { "source": "custom", "command": "npx", "args": ["-y", "pkg"], "env": { "A": "1" } } } }"#,
+        )
+        .unwrap();
+
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers[0].command, "npx");
+        assert_eq!(servers[0].args, vec!["-y", "pkg"]);
+        assert_eq!(servers[0].env.get("A"), Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn mcp_write_remote() {
+        let (_tmp, scope) = setup();
+        let mut server = make_mcp_server("remote");
+        server.url = Some("https://example.com/mcp".into());
+        ZedAdapter.write_mcp_server(&server, &scope).unwrap();
+
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers[0].url, Some("https://example.com/mcp".into()));
+        assert!(servers[0].command.is_empty());
+    }
+
+    #[test]
+    fn rules_write_and_read() {
+        let (tmp, scope) = setup();
+        ZedAdapter.write_rules("# Zed rules", &scope).unwrap();
+
+        assert_eq!(ZedAdapter.read_rules(&scope).unwrap(), "# Zed rules");
+        assert!(tmp.path().join(".rules").exists());
+    }
+
+    #[test]
+    fn skills_unsupported() {
+        let (_tmp, scope) = setup();
+        assert!(ZedAdapter.read_skills(&scope).unwrap().skills.is_empty());
+        assert!(ZedAdapter.write_skill(&make_skill("s"), &scope).is_err());
+    }
+}
+
+mod manifest_adapter_tests {
+    use super::*;
+    use crate::core::ToolManifest;
+
+    fn manifest(src: &str) -> ToolManifest {
+        toml::from_str(src).unwrap()
+    }
+
+    fn setup(src: &str) -> (TempDir, ConfigScope, ManifestAdapter) {
+        let tmp = TempDir::new("manifest");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        (tmp, scope, ManifestAdapter::new(manifest(src)))
+    }
+
+    const JSONC_TOOL: &str = r#"
+        id = "acme"
+        name = "Acme Agent"
+        format = "jsonc"
+        servers_path = "/agent/mcpServers"
+
+        [config]
+        project = ".acme/settings.json"
+
+        [skills]
+        project = ".acme/skills"
+
+        [rules]
+        project = "ACME.md"
+
+        [fields]
+        url = "url"
+        enabled = "enabled"
+    "#;
+
+    const TOML_TOOL: &str = r#"
+        id = "tomlish"
+        name = "Tomlish"
+        format = "toml"
+        servers_path = "mcp.servers"
+
+        [config]
+        project = ".tomlish/config.toml"
+
+        [fields]
+        command_array = true
+        env = "environment"
+    "#;
+
+    #[test]
+    fn jsonc_write_and_read_preserves_comments() {
+        let (tmp, scope, adapter) = setup(JSONC_TOOL);
+        let path = tmp.path().join(".acme/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{\n  // keep me\n  \"theme\": \"dark\",\n}\n").unwrap();
+
+        let mut server = make_mcp_server("srv");
+        server.enabled = false;
+        adapter.write_mcp_server(&server, &scope).unwrap();
+
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.contains("// keep me"));
+        let servers = adapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].command, "test-cmd");
+        assert_eq!(servers[0].args, vec!["--arg1", "value"]);
+        assert_eq!(servers[0].env.get("KEY"), Some(&"VAL".to_string()));
+        assert!(!servers[0].enabled);
+
+        adapter.delete_mcp_server("srv", &scope).unwrap();
+        assert!(adapter.read_mcp_servers(&scope).unwrap().is_empty());
+    }
+
+    #[test]
+    fn toml_command_array_round_trip() {
+        let (tmp, scope, adapter) = setup(TOML_TOOL);
+        adapter.write_mcp_server(&make_mcp_server("srv"), &scope).unwrap();
+
+        let file = fs::read_to_string(tmp.path().join(".tomlish/config.toml")).unwrap();
+        assert!(file.contains("[mcp.servers.srv"));
+        assert!(file.contains("environment"));
+
+        let servers = adapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers[0].command, "test-cmd");
+        assert_eq!(servers[0].args, vec!["--arg1", "value"]);
+        assert_eq!(servers[0].env.get("KEY"), Some(&"VAL".to_string()));
+    }
+
+    #[test]
+    fn toml_writes_keep_comments() {
+        let (tmp, scope, adapter) = setup(TOML_TOOL);
+        let path = tmp.path().join(".tomlish/config.toml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# user notes\ntheme = \"dark\" # keep\n").unwrap();
+
+        adapter.write_mcp_server(&make_mcp_server("srv"), &scope).unwrap();
+        adapter.rename_mcp_server("srv", "renamed", &scope).unwrap();
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.starts_with("# user notes\ntheme = \"dark\" # keep\n"), "{}", file);
+        assert_eq!(adapter.read_mcp_servers(&scope).unwrap()[0].name, "renamed");
+
+        adapter.delete_mcp_server("renamed", &scope).unwrap();
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.contains("# user notes") && !file.contains("renamed"));
+    }
+
+    #[test]
+    fn write_keeps_unknown_entry_keys() {
+        let (tmp, scope, adapter) = setup(JSONC_TOOL);
+        let path = tmp.path().join(".acme/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"agent":{"mcpServers":{"srv":{"command":"old","timeout":30}}}}"#).unwrap();
+
+        adapter.write_mcp_server(&make_mcp_server("srv"), &scope).unwrap();
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.contains("\"timeout\": 30") || file.contains("\"timeout\":30"));
+        assert!(file.contains("test-cmd"));
+    }
+
+    #[test]
+    fn skills_and_rules() {
+        let (tmp, scope, adapter) = setup(JSONC_TOOL);
+        adapter.write_skill(&make_skill("my-skill"), &scope).unwrap();
+        let skills = adapter.read_skills(&scope).unwrap().skills;
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].description.as_deref(), Some("Test skill desc"));
+
+        adapter.write_rules("# rules", &scope).unwrap();
+        assert_eq!(adapter.read_rules(&scope).unwrap(), "# rules");
+        assert!(tmp.path().join("ACME.md").exists());
+    }
+
+    #[test]
+    fn unsupported_sections_error_on_write() {
+        let (_tmp, scope, adapter) = setup(TOML_TOOL);
+        assert!(adapter.read_skills(&scope).unwrap().skills.is_empty());
+        assert!(adapter.write_skill(&make_skill("s"), &scope).is_err());
+        assert_eq!(adapter.read_rules(&scope).unwrap(), "");
+        assert!(adapter.write_rules("x", &scope).is_err());
+    }
+}
+
 mod cross_tool_copy_tests {
     use super::*;
-    use crate::core::{convert_mcp_server, ToolType};
+    use crate::core::{convert_mcp_server, copy_skill_dir, ToolType};
 
     fn setup_multi() -> (TempDir, ConfigScope) {
         let tmp = TempDir::new("cross");
@@ -395,7 +724,8 @@ mod cross_tool_copy_tests {
         let servers = ClaudeAdapter.read_mcp_servers(&scope).unwrap();
         assert_eq!(servers.len(), 1);
 
-        let result = convert_mcp_server(&servers[0], ToolType::ClaudeCode, ToolType::Codex);
+        let result =
+            convert_mcp_server(&servers[0], ToolType::CLAUDE_CODE, ToolType::CODEX, &scope);
         CodexAdapter.write_mcp_server(&result.server, &scope).unwrap();
 
         let codex_servers = CodexAdapter.read_mcp_servers(&scope).unwrap();
@@ -405,7 +735,7 @@ mod cross_tool_copy_tests {
     }
 
     #[test]
-    fn copy_mcp_codex_to_gemini_with_url_warning() {
+    fn copy_mcp_codex_to_gemini_keeps_url() {
         let (_tmp, scope) = setup_multi();
         let mut server = make_mcp_server("remote-srv");
         server.url = Some("http://remote:8080".into());
@@ -413,16 +743,44 @@ mod cross_tool_copy_tests {
         CodexAdapter.write_mcp_server(&server, &scope).unwrap();
 
         let servers = CodexAdapter.read_mcp_servers(&scope).unwrap();
-        let result = convert_mcp_server(&servers[0], ToolType::Codex, ToolType::Gemini);
+        let result = convert_mcp_server(&servers[0], ToolType::CODEX, ToolType::GEMINI, &scope);
 
-        assert!(result.warnings.iter().any(|w| w.contains("url")));
+        assert!(!result.warnings.iter().any(|w| w.contains("url")));
         assert!(result.warnings.iter().any(|w| w.contains("enabled")));
-        assert!(result.server.url.is_none());
         assert!(result.server.enabled);
 
-        GeminiAdapter.write_mcp_server(&result.server, &scope).unwrap();
-        let gemini_servers = GeminiAdapter.read_mcp_servers(&scope).unwrap();
+        GeminiAdapter::gemini().write_mcp_server(&result.server, &scope).unwrap();
+        let gemini_servers = GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap();
         assert_eq!(gemini_servers.len(), 1);
+        assert_eq!(gemini_servers[0].url.as_deref(), Some("http://remote:8080"));
+    }
+
+    #[test]
+    fn gemini_sse_servers_keep_their_key() {
+        let (tmp, scope) = setup_multi();
+        let path = tmp.path().join(".gemini/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"mcpServers":{"events":{"url":"http://localhost/sse"}}}"#).unwrap();
+
+        let adapter = GeminiAdapter::gemini();
+        let mut server = adapter.read_mcp_servers(&scope).unwrap().remove(0);
+        assert_eq!(server.url.as_deref(), Some("http://localhost/sse"));
+        server.url = Some("http://localhost:9000/sse".into());
+        adapter.write_mcp_server(&server, &scope).unwrap();
+        adapter.write_mcp_server(&make_mcp_server("local"), &scope).unwrap();
+
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            settings["mcpServers"]["events"],
+            serde_json::json!({"url": "http://localhost:9000/sse"})
+        );
+        let mut remote = make_mcp_server("remote");
+        remote.url = Some("https://example.com/mcp".into());
+        adapter.write_mcp_server(&remote, &scope).unwrap();
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(settings["mcpServers"]["remote"]["httpUrl"], "https://example.com/mcp");
     }
 
     #[test]
@@ -432,7 +790,8 @@ mod cross_tool_copy_tests {
         OpenCodeAdapter.write_mcp_server(&server, &scope).unwrap();
 
         let servers = OpenCodeAdapter.read_mcp_servers(&scope).unwrap();
-        let result = convert_mcp_server(&servers[0], ToolType::OpenCode, ToolType::ClaudeCode);
+        let result =
+            convert_mcp_server(&servers[0], ToolType::OPENCODE, ToolType::CLAUDE_CODE, &scope);
 
         ClaudeAdapter.write_mcp_server(&result.server, &scope).unwrap();
         let claude_servers = ClaudeAdapter.read_mcp_servers(&scope).unwrap();
@@ -449,25 +808,49 @@ mod cross_tool_copy_tests {
         let skill = make_skill("shared-skill");
         ClaudeAdapter.write_skill(&skill, &scope).unwrap();
 
-        let skills = ClaudeAdapter.read_skills(&scope).unwrap();
+        let skills = ClaudeAdapter.read_skills(&scope).unwrap().skills;
         assert_eq!(skills.len(), 1);
 
-        GeminiAdapter.write_skill(&skills[0], &scope).unwrap();
+        GeminiAdapter::gemini().write_skill(&skills[0], &scope).unwrap();
 
-        let gemini_skills = GeminiAdapter.read_skills(&scope).unwrap();
+        let gemini_skills = GeminiAdapter::gemini().read_skills(&scope).unwrap().skills;
         assert_eq!(gemini_skills.len(), 1);
         assert_eq!(gemini_skills[0].name, "shared-skill");
         assert_eq!(gemini_skills[0].description, Some("Test skill desc".into()));
     }
 
+    #[test]
+    fn copy_skill_dir_claude_to_codex_keeps_resources() {
+        let (tmp, scope) = setup_multi();
+        ClaudeAdapter.write_skill(&make_skill("full-skill"), &scope).unwrap();
+        let src = ClaudeAdapter.skill_dir("full-skill", &scope).unwrap();
+        fs::create_dir_all(src.join("scripts")).unwrap();
+        fs::write(src.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+
+        let skill = &ClaudeAdapter.read_skills(&scope).unwrap().skills[0];
+        let dest = CodexAdapter.skill_dir(&skill.name, &scope).unwrap();
+        let files = copy_skill_dir(&skill.path, &dest, &[]).unwrap();
+
+        assert_eq!(files, vec!["SKILL.md", "scripts/run.sh"]);
+        assert!(tmp.path().join(".agents/skills/full-skill/scripts/run.sh").exists());
+        assert_eq!(CodexAdapter.read_skills(&scope).unwrap().skills[0].name, "full-skill");
+    }
+
+    #[test]
+    fn skill_dir_rejects_bad_names() {
+        let (_tmp, scope) = setup_multi();
+        assert!(ClaudeAdapter.skill_dir("../escape", &scope).is_err());
+        assert!(ZedAdapter.skill_dir("ok", &scope).is_err());
+    }
+
     #[test]
     fn copy_mcp_gemini_to_opencode() {
         let (_tmp, scope) = setup_multi();
         let server = make_mcp_server("gem-srv");
-        GeminiAdapter.write_mcp_server(&server, &scope).unwrap();
+        GeminiAdapter::gemini().write_mcp_server(&server, &scope).unwrap();
 
-        let servers = GeminiAdapter.read_mcp_servers(&scope).unwrap();
-        let result = convert_mcp_server(&servers[0], ToolType::Gemini, ToolType::OpenCode);
+        let servers = GeminiAdapter::gemini().read_mcp_servers(&scope).unwrap();
+        let result = convert_mcp_server(&servers[0], ToolType::GEMINI, ToolType::OPENCODE, &scope);
 
         OpenCodeAdapter.write_mcp_server(&result.server, &scope).unwrap();
         let oc_servers = OpenCodeAdapter.read_mcp_servers(&scope).unwrap();
@@ -485,7 +868,7 @@ mod cross_tool_copy_tests {
         CodexAdapter.write_mcp_server(&server, &scope).unwrap();
 
         let servers = CodexAdapter.read_mcp_servers(&scope).unwrap();
-        let result = convert_mcp_server(&servers[0], ToolType::Codex, ToolType::OpenCode);
+        let result = convert_mcp_server(&servers[0], ToolType::CODEX, ToolType::OPENCODE, &scope);
 
         assert!(result.warnings.is_empty() || result.warnings.iter().all(|w| !w.contains("url")));
         OpenCodeAdapter.write_mcp_server(&result.server, &scope).unwrap();
@@ -496,6 +879,88 @@ mod cross_tool_copy_tests {
     }
 }
 
+mod skill_frontmatter_tests {
+    use super::*;
+    use crate::core::AllowedTools;
+
+    const SKILL_MD: &str = "---\nname: rich-skill\ndescription: Has everything\nlicense: Apache-2.0\nallowed-tools:\n- Read\n- Bash(git:*)\nmetadata:\n  owner: docs\nmodel: sonnet\nuser-invocable: false\n---\n\n# Body\n";
+
+    fn adapters() -> Vec<(&'static str, Box<dyn ConfigAdapter>)> {
+        vec![
+            ("claude", Box::new(ClaudeAdapter)),
+            ("codex", Box::new(CodexAdapter)),
+            ("gemini", Box::new(GeminiAdapter::gemini())),
+            ("opencode", Box::new(OpenCodeAdapter)),
+        ]
+    }
+
+    #[test]
+    fn every_adapter_round_trips_frontmatter() {
+        for (label, adapter) in adapters() {
+            let tmp = TempDir::new(&format!("fm-{}", label));
+            let scope = ConfigScope::Project(tmp.path().clone());
+            let dir = adapter.skill_dir("rich-skill", &scope).unwrap();
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), SKILL_MD).unwrap();
+
+            let skill = adapter.read_skills(&scope).unwrap().skills.remove(0);
+            assert_eq!(skill.license.as_deref(), Some("Apache-2.0"), "{}", label);
+            assert!(matches!(skill.allowed_tools, Some(AllowedTools::List(ref l)) if l.len() == 2));
+            assert_eq!(skill.model.as_deref(), Some("sonnet"));
+
+            adapter.write_skill(&skill, &scope).unwrap();
+            let written = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+            assert_eq!(written, SKILL_MD, "{}", label);
+        }
+    }
+}
+
+mod skill_diagnostics_tests {
+    use super::*;
+
+    #[test]
+    fn broken_skills_are_reported() {
+        let tmp = TempDir::new("skill-diag");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        ClaudeAdapter.write_skill(&make_skill("good"), &scope).unwrap();
+        let root = tmp.path().join(".claude/skills");
+        fs::create_dir_all(root.join("no-file")).unwrap();
+        fs::create_dir_all(root.join("bad-yaml")).unwrap();
+        fs::write(root.join("bad-yaml/SKILL.md"), "---\nname: [oops\n---\nbody").unwrap();
+        fs::create_dir_all(root.join("no-name")).unwrap();
+        fs::write(root.join("no-name/SKILL.md"), "---\ndescription: d\n---\nbody").unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+
+        let scan = ClaudeAdapter.read_skills(&scope).unwrap();
+        assert_eq!(scan.skills.len(), 1);
+        assert_eq!(scan.diagnostics.len(), 3);
+
+        let reason = |dir: &str| {
+            scan.diagnostics
+                .iter()
+                .find(|d| d.path == root.join(dir).join("SKILL.md"))
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(reason("no-file").reason, "Missing SKILL.md");
+        assert!(reason("bad-yaml").line.is_some());
+        assert!(reason("no-name").reason.contains("name"));
+    }
+
+    #[test]
+    fn crlf_skill_is_loaded() {
+        let tmp = TempDir::new("skill-crlf");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let dir = GeminiAdapter::gemini().skill_dir("win", &scope).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "\u{feff}---\r\nname: win\r\n---\r\nbody").unwrap();
+
+        let scan = GeminiAdapter::gemini().read_skills(&scope).unwrap();
+        assert!(scan.diagnostics.is_empty());
+        assert_eq!(scan.skills[0].name, "win");
+    }
+}
+
 mod skill_name_validation_tests {
     use super::*;
 
@@ -540,7 +1005,7 @@ mod skill_name_validation_tests {
         let (_tmp, scope) = setup();
         let mut skill = make_skill("valid");
         skill.name = "../evil".into();
-        let result = GeminiAdapter.write_skill(&skill, &scope);
+        let result = GeminiAdapter::gemini().write_skill(&skill, &scope);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid name"));
     }
@@ -550,7 +1015,7 @@ mod skill_name_validation_tests {
         let (_tmp, scope) = setup();
         let mut skill = make_skill("valid");
         skill.name = "a/b".into();
-        let result = GeminiAdapter.write_skill(&skill, &scope);
+        let result = GeminiAdapter::gemini().write_skill(&skill, &scope);
         assert!(result.is_err());
     }
 
@@ -575,7 +1040,7 @@ mod skill_name_validation_tests {
     #[test]
     fn gemini_delete_rejects_invalid_name() {
         let (_tmp, scope) = setup();
-        let result = GeminiAdapter.delete_skill("../evil", &scope);
+        let result = GeminiAdapter::gemini().delete_skill("../evil", &scope);
         assert!(result.is_err());
     }
 
@@ -586,3 +1051,928 @@ mod skill_name_validation_tests {
         assert!(result.is_err());
     }
 }
+
+mod rename_tests {
+    use super::*;
+    use crate::core::{find_skill_dir, rename_skill_dir};
+
+    fn setup() -> (TempDir, ConfigScope) {
+        let tmp = TempDir::new("rename");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        (tmp, scope)
+    }
+
+    #[test]
+    fn codex_mcp_rename_keeps_extra_fields() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".codex/config.toml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "[mcp_servers.old]\ncommand = \"x\"\nstartup_timeout_sec = 20\n").unwrap();
+
+        CodexAdapter.rename_mcp_server("old", "new", &scope).unwrap();
+        let servers = CodexAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "new");
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.contains("startup_timeout_sec = 20"));
+    }
+
+    #[test]
+    fn mcp_rename_rejects_missing_and_taken_names() {
+        let (_tmp, scope) = setup();
+        let adapter = GeminiAdapter::gemini();
+        adapter.write_mcp_server(&make_mcp_server("a"), &scope).unwrap();
+        adapter.write_mcp_server(&make_mcp_server("b"), &scope).unwrap();
+
+        assert!(adapter.rename_mcp_server("a", "b", &scope).unwrap_err().contains("exists"));
+        assert!(adapter.rename_mcp_server("nope", "c", &scope).unwrap_err().contains("not found"));
+        assert_eq!(adapter.read_mcp_servers(&scope).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn zed_mcp_rename_keeps_comments() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".zed/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "{\n  // servers\n  \"context_servers\": {\n    \"old\": { \"source\": \"custom\", \"command\": \"npx\", \"settings\": { \"k\": 1 } },\n  },\n}\n",
+        )
+        .unwrap();
+
+        ZedAdapter.rename_mcp_server("old", "new", &scope).unwrap();
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.contains("// servers"));
+        assert!(file.contains("\"settings\""));
+        let servers = ZedAdapter.read_mcp_servers(&scope).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "new");
+    }
+
+    #[test]
+    fn claude_mcp_rename_keeps_http_entries() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".claude/.mcp.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let config = serde_json::json!({
+            "mcpServers": {
+                "first": { "command": "npx" },
+                "old": {
+                    "type": "http",
+                    "url": "https://example.com/mcp",
+                    "headers": { "Authorization": "Bearer x" }
+                },
+                "last": { "command": "uvx" }
+            }
+        });
+        fs::write(&path, config.to_string()).unwrap();
+
+        ClaudeAdapter.rename_mcp_server("old", "new", &scope).unwrap();
+        let file: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let servers = file["mcpServers"].as_object().unwrap();
+        assert_eq!(servers.keys().collect::<Vec<_>>(), ["first", "new", "last"]);
+        assert_eq!(servers["new"], config["mcpServers"]["old"]);
+    }
+
+    #[test]
+    fn skill_rename_moves_folder_and_updates_name() {
+        let (_tmp, scope) = setup();
+        let adapter = ClaudeAdapter;
+        adapter.write_skill(&make_skill("old-skill"), &scope).unwrap();
+        let old_dir = adapter.skill_dir("old-skill", &scope).unwrap();
+        fs::create_dir_all(old_dir.join("scripts")).unwrap();
+        fs::write(old_dir.join("scripts/run.sh"), "echo").unwrap();
+
+        let dir = find_skill_dir(&adapter, "old-skill", &scope).unwrap();
+        rename_skill_dir(&dir, "new-skill").unwrap();
+        let skills = adapter.read_skills(&scope).unwrap().skills;
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "new-skill");
+        assert_eq!(skills[0].description.as_deref(), Some("Test skill desc"));
+        assert!(skills[0].path.join("scripts/run.sh").is_file());
+        assert!(!old_dir.exists());
+    }
+
+    #[test]
+    fn skill_rename_uses_the_scanned_folder() {
+        let (tmp, scope) = setup();
+        let adapter = ClaudeAdapter;
+        let legacy = tmp.path().join(".claude/skills/legacy-folder");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("SKILL.md"), "---\nname: nice\ndescription: d\n---\nBody\n")
+            .unwrap();
+
+        let dir = find_skill_dir(&adapter, "nice", &scope).unwrap();
+        assert_eq!(dir, legacy);
+        rename_skill_dir(&dir, "nicer").unwrap();
+        let skills = adapter.read_skills(&scope).unwrap().skills;
+        assert_eq!(skills[0].name, "nicer");
+        assert_eq!(skills[0].path, tmp.path().join(".claude/skills/nicer"));
+        assert!(!legacy.exists());
+        assert!(find_skill_dir(&adapter, "nice", &scope).unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn skill_rename_refuses_existing_target() {
+        let (_tmp, scope) = setup();
+        let adapter = ClaudeAdapter;
+        adapter.write_skill(&make_skill("a"), &scope).unwrap();
+        adapter.write_skill(&make_skill("b"), &scope).unwrap();
+        let dir = find_skill_dir(&adapter, "a", &scope).unwrap();
+        assert!(rename_skill_dir(&dir, "b").is_err());
+        assert!(rename_skill_dir(&dir, "../b").is_err());
+        assert_eq!(adapter.read_skills(&scope).unwrap().skills.len(), 2);
+    }
+}
+
+mod command_tests {
+    use super::*;
+    use crate::core::{convert_command, Command, ToolType};
+
+    fn setup() -> (TempDir, ConfigScope) {
+        let tmp = TempDir::new("commands");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        (tmp, scope)
+    }
+
+    fn make_command(name: &str, content: &str) -> Command {
+        Command {
+            name: name.into(),
+            description: Some("Review a file".into()),
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn claude_markdown_round_trip_keeps_extra_keys() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".claude/commands/review.md");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "---\nallowed-tools: Read\ndescription: old\n---\nold").unwrap();
+
+        let mut cmd = make_command("review", "Review $ARGUMENTS");
+        cmd.argument_hint = Some("<file>".into());
+        ClaudeAdapter.write_command(&cmd, &scope).unwrap();
+
+        let commands = ClaudeAdapter.read_commands(&scope).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].argument_hint.as_deref(), Some("<file>"));
+        assert_eq!(commands[0].content, "Review $ARGUMENTS");
+        assert!(fs::read_to_string(&path).unwrap().contains("allowed-tools: Read"));
+
+        ClaudeAdapter.delete_command("review", &scope).unwrap();
+        assert!(ClaudeAdapter.read_commands(&scope).unwrap().is_empty());
+    }
+
+    #[test]
+    fn gemini_toml_round_trip_keeps_extra_keys() {
+        let (tmp, scope) = setup();
+        let adapter = GeminiAdapter::gemini();
+        let path = tmp.path().join(".gemini/commands/review.toml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# team prompt\nprompt = \"old\"\nextra = 1 # keep\n").unwrap();
+        fs::write(path.with_file_name("broken.toml"), "not toml =").unwrap();
+
+        adapter.write_command(&make_command("review", "Review {{args}}"), &scope).unwrap();
+        let commands = adapter.read_commands(&scope).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].description.as_deref(), Some("Review a file"));
+        assert_eq!(commands[0].content, "Review {{args}}");
+        let file = fs::read_to_string(&path).unwrap();
+        assert!(file.starts_with("# team prompt\n"), "{}", file);
+        assert!(file.contains("extra = 1 # keep"));
+
+        let multiline = make_command("review", "Line one\nLine two\n");
+        adapter.write_command(&multiline, &scope).unwrap();
+        assert_eq!(adapter.read_commands(&scope).unwrap()[0].content, "Line one\nLine two\n");
+    }
+
+    #[test]
+    fn command_names_are_validated() {
+        let (_tmp, scope) = setup();
+        let command = make_command("../escape", "x");
+        assert!(OpenCodeAdapter.write_command(&command, &scope).is_err());
+        assert!(GeminiAdapter::gemini().write_command(&command, &scope).is_err());
+    }
+
+    #[test]
+    fn opencode_config_entries() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".opencode/opencode.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"command":{"review":{"template":"old","agent":"plan"}}}"#).unwrap();
+
+        let mut cmd = make_command("review", "Review $ARGUMENTS");
+        cmd.model = Some("anthropic/claude".into());
+        OpenCodeAdapter.write_command(&cmd, &scope).unwrap();
+        let commands = OpenCodeAdapter.read_commands(&scope).unwrap();
+        assert_eq!(commands[0].model.as_deref(), Some("anthropic/claude"));
+        assert!(fs::read_to_string(&path).unwrap().contains("\"agent\": \"plan\""));
+
+        OpenCodeAdapter.delete_command("review", &scope).unwrap();
+        assert!(OpenCodeAdapter.read_commands(&scope).unwrap().is_empty());
+    }
+
+    #[test]
+    fn opencode_skips_bad_command_entries() {
+        let (tmp, scope) = setup();
+        let path = tmp.path().join(".opencode/opencode.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"mcp":{"fs":{"type":"local","command":["npx"]}},"command":{"broken":{"agent":"plan"},"ok":{"template":"Hi"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(OpenCodeAdapter.read_mcp_servers(&scope).unwrap().len(), 1);
+        let commands = OpenCodeAdapter.read_commands(&scope).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "ok");
+
+        OpenCodeAdapter.write_mcp_server(&make_mcp_server("git"), &scope).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["command"]["broken"]["agent"], "plan");
+
+        // Saving over the bad entry repairs it and keeps its other keys.
+        OpenCodeAdapter.write_command(&make_command("broken", "Fixed"), &scope).unwrap();
+        assert_eq!(OpenCodeAdapter.read_commands(&scope).unwrap().len(), 2);
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["command"]["broken"]["agent"], "plan");
+    }
+
+    #[test]
+    fn codex_prompts_are_global_only() {
+        let (_tmp, scope) = setup();
+        assert!(CodexAdapter.read_commands(&scope).unwrap().is_empty());
+        assert!(CodexAdapter.write_command(&make_command("a", "x"), &scope).is_err());
+        assert!(CodexAdapter.commands_dir(&scope).is_none());
+        assert!(ZedAdapter.write_command(&make_command("a", "x"), &scope).is_err());
+    }
+
+    #[test]
+    fn copy_claude_to_qwen_converts_placeholder() {
+        let (tmp, scope) = setup();
+        ClaudeAdapter.write_command(&make_command("review", "Check $ARGUMENTS"), &scope).unwrap();
+        let cmd = &ClaudeAdapter.read_commands(&scope).unwrap()[0];
+
+        let result = convert_command(cmd, ToolType::CLAUDE_CODE, ToolType::QWEN);
+        let qwen = GeminiAdapter::qwen();
+        qwen.write_command(&result.command, &scope).unwrap();
+        let copied = &qwen.read_commands(&scope).unwrap()[0];
+        assert_eq!(copied.content, "Check {{args}}");
+        assert_eq!(copied.path, tmp.path().join(".qwen/commands/review.toml"));
+    }
+}
+
+mod agent_tests {
+    use super::*;
+    use crate::core::{Agent, AllowedTools};
+
+    fn make_agent(name: &str) -> Agent {
+        Agent {
+            name: name.into(),
+            description: Some("Reviews diffs".into()),
+            tools: Some(AllowedTools::Text("Read, Grep".into())),
+            content: "You review code.".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn write_read_delete() {
+        let tmp = TempDir::new("agents");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        ClaudeAdapter.write_agent(&make_agent("reviewer"), &scope).unwrap();
+
+        let scan = ClaudeAdapter.read_agents(&scope).unwrap();
+        assert_eq!(scan.agents.len(), 1);
+        assert_eq!(scan.agents[0].tool_names(), vec!["Read", "Grep"]);
+        assert_eq!(scan.agents[0].path, tmp.path().join(".claude/agents/reviewer.md"));
+
+        ClaudeAdapter.delete_agent("reviewer", &scope).unwrap();
+        assert!(ClaudeAdapter.read_agents(&scope).unwrap().agents.is_empty());
+    }
+
+    #[test]
+    fn agents_are_found_by_frontmatter_name() {
+        let tmp = TempDir::new("agents-name");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let dir = tmp.path().join(".claude/agents");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Code Reviewer.md"), "---\nname: reviewer\ncolor: red\n---\nold")
+            .unwrap();
+        fs::write(dir.join("broken.md"), "no frontmatter").unwrap();
+
+        let scan = ClaudeAdapter.read_agents(&scope).unwrap();
+        assert_eq!(scan.agents.len(), 1);
+        assert_eq!(scan.diagnostics.len(), 1);
+
+        let mut agent = scan.agents[0].clone();
+        agent.content = "new".into();
+        ClaudeAdapter.write_agent(&agent, &scope).unwrap();
+        let written = fs::read_to_string(dir.join("Code Reviewer.md")).unwrap();
+        assert!(written.contains("color: red") && written.ends_with("new"));
+        assert!(!dir.join("reviewer.md").exists());
+    }
+}
+
+mod hook_tests {
+    use super::*;
+    use crate::core::{HookConfig, HookGroup, HookHandler};
+
+    #[test]
+    fn hooks_write_keeps_other_settings() {
+        let tmp = TempDir::new("hooks");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = tmp.path().join(".claude/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"model":"opus","hooks":{"Stop":[{"hooks":[{"type":"command","command":"a","x":1}]}]}}"#,
+        )
+        .unwrap();
+
+        let mut hooks = ClaudeAdapter.read_hooks(&scope).unwrap();
+        assert_eq!(hooks["Stop"][0].hooks[0].command.as_deref(), Some("a"));
+        hooks.insert(
+            "PreToolUse".into(),
+            vec![HookGroup {
+                matcher: Some("Bash".into()),
+                hooks: vec![HookHandler {
+                    kind: "command".into(),
+                    command: Some("check.sh".into()),
+                    timeout: Some(5),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        );
+        ClaudeAdapter.write_hooks(&hooks, &scope).unwrap();
+
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(settings["model"], "opus");
+        assert_eq!(settings["hooks"]["Stop"][0]["hooks"][0]["x"], 1);
+        assert_eq!(settings["hooks"]["PreToolUse"][0]["matcher"], "Bash");
+        assert_eq!(ClaudeAdapter.read_hooks(&scope).unwrap(), hooks);
+
+        ClaudeAdapter.write_hooks(&HookConfig::new(), &scope).unwrap();
+        let settings = ClaudeAdapter.read_settings(&scope).unwrap();
+        assert!(!settings.contains_key("hooks") && settings.contains_key("model"));
+    }
+}
+
+mod permission_tests {
+    use super::*;
+    use crate::core::{ApprovalPolicy, Permissions, SandboxMode};
+
+    fn write(path: PathBuf, content: &str) -> PathBuf {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn read_json(path: &PathBuf) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn claude_permissions_round_trip() {
+        let tmp = TempDir::new("perm-claude");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".claude/settings.json"),
+            r#"{"model":"opus","permissions":{"allow":["Read"],"defaultMode":"acceptEdits","additionalDirectories":["../lib"]}}"#,
+        );
+
+        let mut p = ClaudeAdapter.read_permissions(&scope).unwrap();
+        assert_eq!(p.allow, vec!["Read"]);
+        assert_eq!(p.approval, Some(ApprovalPolicy::OnRequest));
+
+        p.deny.push("Bash(rm:*)".into());
+        p.approval = None;
+        p.sandbox = Some(SandboxMode::ReadOnly);
+        ClaudeAdapter.write_permissions(&p, &scope).unwrap();
+        let json = read_json(&path);
+        assert_eq!(json["model"], "opus");
+        assert_eq!(json["permissions"]["defaultMode"], "plan");
+        assert_eq!(json["permissions"]["additionalDirectories"][0], "../lib");
+        assert_eq!(ClaudeAdapter.read_permissions(&scope).unwrap(), p);
+
+        p.approval = Some(ApprovalPolicy::OnFailure);
+        p.sandbox = None;
+        assert!(ClaudeAdapter.write_permissions(&p, &scope).is_err());
+    }
+
+    #[test]
+    fn claude_keeps_unknown_mode() {
+        let tmp = TempDir::new("perm-claude-mode");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".claude/settings.json"),
+            r#"{"permissions":{"defaultMode":"someFutureMode"}}"#,
+        );
+        let p = ClaudeAdapter.read_permissions(&scope).unwrap();
+        assert_eq!(p, Permissions::default());
+        ClaudeAdapter.write_permissions(&p, &scope).unwrap();
+        assert_eq!(read_json(&path)["permissions"]["defaultMode"], "someFutureMode");
+    }
+
+    #[test]
+    fn codex_approval_and_sandbox() {
+        let tmp = TempDir::new("perm-codex");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".codex/config.toml"),
+            "# defaults\nmodel = \"o3\"\napproval_policy = \"on-failure\"\n",
+        );
+
+        let mut p = CodexAdapter.read_permissions(&scope).unwrap();
+        assert_eq!(p.approval, Some(ApprovalPolicy::OnFailure));
+        assert!(p.sandbox.is_none());
+
+        p.sandbox = Some(SandboxMode::WorkspaceWrite);
+        CodexAdapter.write_permissions(&p, &scope).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("sandbox_mode = \"workspace-write\""));
+        assert!(content.starts_with("# defaults\nmodel = \"o3\"\n"));
+
+        p.allow.push("Read".into());
+        assert!(CodexAdapter.write_permissions(&p, &scope).is_err());
+    }
+
+    #[test]
+    fn gemini_maps_tool_names() {
+        let tmp = TempDir::new("perm-gemini");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let adapter = GeminiAdapter::gemini();
+        let path = write(
+            tmp.path().join(".gemini/settings.json"),
+            r#"{"coreTools":["read_file","run_shell_command(git)","custom_tool"],"autoAccept":true}"#,
+        );
+
+        let mut p = adapter.read_permissions(&scope).unwrap();
+        assert_eq!(p.allow, vec!["Read", "Bash(git:*)", "custom_tool"]);
+        assert_eq!(p.approval, Some(ApprovalPolicy::OnRequest));
+
+        p.deny = vec!["WebFetch".into()];
+        p.approval = Some(ApprovalPolicy::Untrusted);
+        adapter.write_permissions(&p, &scope).unwrap();
+        let json = read_json(&path);
+        assert_eq!(json["coreTools"][1], "run_shell_command(git)");
+        assert_eq!(json["excludeTools"][0], "web_fetch");
+        assert_eq!(json["autoAccept"], false);
+
+        // The nested layout is kept when the file already uses it.
+        write(path.clone(), r#"{"tools":{"exclude":["write_file"],"sandbox":true}}"#);
+        let mut p = adapter.read_permissions(&scope).unwrap();
+        assert_eq!(p.deny, vec!["Write"]);
+        p.allow = vec!["Grep".into()];
+        adapter.write_permissions(&p, &scope).unwrap();
+        let json = read_json(&path);
+        assert_eq!(json["tools"]["core"][0], "search_file_content");
+        assert_eq!(json["tools"]["sandbox"], true);
+        assert!(json.get("coreTools").is_none());
+    }
+
+    #[test]
+    fn opencode_permission_object() {
+        let tmp = TempDir::new("perm-opencode");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".opencode/opencode.json"),
+            r#"{"permission":{"edit":"ask","bash":{"git *":"allow","rm -rf":"deny","*":"ask"}}}"#,
+        );
+
+        let p = OpenCodeAdapter.read_permissions(&scope).unwrap();
+        assert_eq!(p.allow, vec!["Bash(git:*)"]);
+        assert_eq!(p.deny, vec!["Bash(rm -rf)"]);
+        assert!(p.ask.contains(&"Edit".to_string()) && p.ask.contains(&"Bash".to_string()));
+
+        OpenCodeAdapter.write_permissions(&p, &scope).unwrap();
+        let json = read_json(&path);
+        assert_eq!(json["permission"]["edit"], "ask");
+        assert_eq!(json["permission"]["bash"]["git *"], "allow");
+        assert_eq!(json["permission"]["bash"]["*"], "ask");
+        assert_eq!(OpenCodeAdapter.read_permissions(&scope).unwrap().deny, p.deny);
+    }
+}
+
+mod model_settings_tests {
+    use super::*;
+    use crate::core::{ModelProvider, ModelSettings};
+
+    fn write(path: PathBuf, content: &str) -> PathBuf {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn codex_model_and_providers() {
+        let tmp = TempDir::new("model-codex");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".codex/config.toml"),
+            r#"model = "o3" # pinned
+approval_policy = "never"
+
+# corporate endpoint
+[model_providers.azure]
+name = "Azure"
+base_url = "https://example.openai.azure.com/openai"
+env_key = "AZURE_OPENAI_API_KEY"
+query_params = { api-version = "2025-04-01-preview" }
+"#,
+        );
+
+        let mut settings = CodexAdapter.read_model_settings(&scope).unwrap();
+        assert_eq!(settings.model.as_deref(), Some("o3"));
+        let azure = &settings.providers[0];
+        assert_eq!(azure.env_key.as_deref(), Some("AZURE_OPENAI_API_KEY"));
+        assert!(azure.extra.contains_key("query_params"));
+
+        settings.provider = Some("azure".into());
+        settings.providers.push(ModelProvider {
+            id: "ollama".into(),
+            base_url: Some("http://localhost:11434/v1".into()),
+            ..Default::default()
+        });
+        CodexAdapter.write_model_settings(&settings, &scope).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("approval_policy = \"never\""));
+        assert!(content.contains("model_provider = \"azure\""));
+        assert!(content.contains("api-version"));
+        assert!(content.contains("model = \"o3\" # pinned"));
+        assert!(content.contains("# corporate endpoint\n[model_providers.azure]"));
+        assert!(content.contains("[model_providers.ollama]"));
+        assert_eq!(CodexAdapter.read_model_settings(&scope).unwrap(), settings);
+    }
+
+    #[test]
+    fn opencode_model_is_provider_qualified() {
+        let tmp = TempDir::new("model-opencode");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = write(
+            tmp.path().join(".opencode/opencode.json"),
+            r#"{"model":"lmstudio/qwen3","provider":{"lmstudio":{"npm":"@ai-sdk/openai-compatible","options":{"baseURL":"http://127.0.0.1:1234/v1","apiKey":"{env:LM_KEY}","timeout":5}}}}"#,
+        );
+
+        let settings = OpenCodeAdapter.read_model_settings(&scope).unwrap();
+        assert_eq!(settings.provider.as_deref(), Some("lmstudio"));
+        assert_eq!(settings.model.as_deref(), Some("qwen3"));
+        let p = &settings.providers[0];
+        assert_eq!(p.base_url.as_deref(), Some("http://127.0.0.1:1234/v1"));
+        assert_eq!(p.env_key.as_deref(), Some("LM_KEY"));
+        assert_eq!(p.extra["options"]["timeout"], 5);
+
+        OpenCodeAdapter.write_model_settings(&settings, &scope).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["model"], "lmstudio/qwen3");
+        assert_eq!(json["provider"]["lmstudio"]["options"]["apiKey"], "{env:LM_KEY}");
+        assert_eq!(json["provider"]["lmstudio"]["npm"], "@ai-sdk/openai-compatible");
+    }
+
+    #[test]
+    fn gemini_and_claude_model_only() {
+        let tmp = TempDir::new("model-gemini");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let adapter = GeminiAdapter::gemini();
+        let path = write(
+            tmp.path().join(".gemini/settings.json"),
+            r#"{"model":{"name":"gemini-2.5-pro","maxSessionTurns":10}}"#,
+        );
+        let mut settings = adapter.read_model_settings(&scope).unwrap();
+        assert_eq!(settings.model.as_deref(), Some("gemini-2.5-pro"));
+        settings.model = Some("gemini-2.5-flash".into());
+        adapter.write_model_settings(&settings, &scope).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["model"]["name"], "gemini-2.5-flash");
+        assert_eq!(json["model"]["maxSessionTurns"], 10);
+
+        let claude = ModelSettings { model: Some("opus".into()), ..Default::default() };
+        ClaudeAdapter.write_model_settings(&claude, &scope).unwrap();
+        assert_eq!(ClaudeAdapter.read_model_settings(&scope).unwrap(), claude);
+        let with_provider = ModelSettings { provider: Some("x".into()), ..claude };
+        assert!(ClaudeAdapter.write_model_settings(&with_provider, &scope).is_err());
+    }
+}
+
+mod codex_profile_tests {
+    use super::*;
+    use crate::core::{ApprovalPolicy, CodexProfile, SandboxMode};
+
+    const CONFIG: &str = r#"model = "o3"
+approval_policy = "on-request"
+profile = "fast"
+
+[mcp_servers.fs]
+command = "npx"
+
+[profiles.fast]
+model = "gpt-5-mini"
+approval_policy = "never"
+model_reasoning_effort = "low"
+
+[profiles.fast.tools]
+web_search = true
+"#;
+
+    fn setup(name: &str) -> (TempDir, ConfigScope, PathBuf) {
+        let tmp = TempDir::new(name);
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let path = tmp.path().join(".codex/config.toml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, CONFIG).unwrap();
+        (tmp, scope, path)
+    }
+
+    #[test]
+    fn reads_and_writes_profiles() {
+        let (_tmp, scope, path) = setup("codex-profiles");
+        let profiles = CodexAdapter.read_profiles(&scope).unwrap();
+        assert_eq!(profiles.default.as_deref(), Some("fast"));
+        let fast = &profiles.profiles[0];
+        assert_eq!(fast.approval_policy, Some(ApprovalPolicy::Never));
+        assert_eq!(fast.extra["model_reasoning_effort"], "low");
+        assert_eq!(fast.extra["tools"]["web_search"], true);
+
+        let safe = CodexProfile {
+            name: "safe".into(),
+            sandbox_mode: Some(SandboxMode::ReadOnly),
+            ..Default::default()
+        };
+        CodexAdapter.write_profile(&safe, &scope).unwrap();
+        let profiles = CodexAdapter.read_profiles(&scope).unwrap().profiles;
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[1], safe);
+        assert_eq!(CodexAdapter.read_mcp_servers(&scope).unwrap().len(), 1);
+
+        CodexAdapter.delete_profile("fast", &scope).unwrap();
+        let profiles = CodexAdapter.read_profiles(&scope).unwrap();
+        assert!(profiles.default.is_none());
+        assert_eq!(profiles.profiles.len(), 1);
+        assert!(!fs::read_to_string(&path).unwrap().contains("model_reasoning_effort"));
+
+        let bad = CodexProfile { name: "a b".into(), ..Default::default() };
+        assert!(CodexAdapter.write_profile(&bad, &scope).is_err());
+    }
+
+    #[test]
+    fn profile_writes_keep_comments() {
+        let (_tmp, scope, path) = setup("codex-profile-comments");
+        let commented = CONFIG.replace("[profiles.fast]\n", "# tuned for speed\n[profiles.fast]\n");
+        fs::write(&path, format!("# my codex setup\n{}", commented)).unwrap();
+
+        let safe = CodexProfile { name: "safe".into(), ..Default::default() };
+        CodexAdapter.write_profile(&safe, &scope).unwrap();
+        let mut fast = CodexAdapter.read_profiles(&scope).unwrap().profiles.remove(0);
+        fast.model = Some("gpt-5".into());
+        CodexAdapter.write_profile(&fast, &scope).unwrap();
+        CodexAdapter.set_default_profile(Some("safe"), &scope).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# my codex setup\n"));
+        assert!(content.contains("# tuned for speed\n[profiles.fast]\nmodel = \"gpt-5\"\n"));
+        assert!(content.contains("[profiles.fast.tools]"));
+        assert!(content.contains("[profiles.safe]"));
+        assert_eq!(CodexAdapter.read_profiles(&scope).unwrap().default.as_deref(), Some("safe"));
+    }
+
+    #[test]
+    fn default_profile_must_exist() {
+        let (_tmp, scope, _path) = setup("codex-default-profile");
+        assert!(CodexAdapter.set_default_profile(Some("missing"), &scope).is_err());
+        CodexAdapter.set_default_profile(None, &scope).unwrap();
+        assert!(CodexAdapter.read_profiles(&scope).unwrap().default.is_none());
+    }
+
+    #[test]
+    fn effective_config_merges_profile() {
+        let (_tmp, scope, _path) = setup("codex-effective");
+        let merged = CodexAdapter.effective_config(None, &scope).unwrap();
+        assert_eq!(merged["model"], "gpt-5-mini");
+        assert_eq!(merged["approval_policy"], "never");
+        assert_eq!(merged["tools"]["web_search"], true);
+        assert!(!merged.contains_key("profiles") && !merged.contains_key("profile"));
+
+        CodexAdapter.set_default_profile(None, &scope).unwrap();
+        let base = CodexAdapter.effective_config(None, &scope).unwrap();
+        assert_eq!(base["model"], "o3");
+        assert!(CodexAdapter.effective_config(Some("nope"), &scope).is_err());
+    }
+}
+
+mod settings_layer_tests {
+    use super::*;
+    use crate::core::SettingsLayer;
+
+    #[test]
+    fn reads_and_writes_project_layers() {
+        let tmp = TempDir::new("settings-layers");
+        let project = tmp.path().as_path();
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(project.join(".claude/settings.json"), r#"{"model":"sonnet"}"#).unwrap();
+        fs::write(project.join(".claude/settings.local.json"), "{oops").unwrap();
+
+        let layers = ClaudeAdapter.read_settings_layers(Some(project));
+        let kinds: Vec<_> = layers.iter().map(|l| l.layer).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SettingsLayer::User,
+                SettingsLayer::Project,
+                SettingsLayer::Local,
+                SettingsLayer::Managed,
+            ]
+        );
+        assert_eq!(layers[1].settings["model"], "sonnet");
+        assert!(layers[2].exists && layers[2].error.is_some());
+
+        fs::remove_file(project.join(".claude/settings.local.json")).unwrap();
+        ClaudeAdapter
+            .write_layer_setting(SettingsLayer::Local, Some(project), "model", Some("opus".into()))
+            .unwrap();
+        let layers = ClaudeAdapter.read_settings_layers(Some(project));
+        assert_eq!(layers[2].settings["model"], "opus");
+        assert_eq!(layers[1].settings["model"], "sonnet");
+
+        assert!(ClaudeAdapter
+            .write_layer_setting(SettingsLayer::Managed, Some(project), "model", None)
+            .is_err());
+        assert!(ClaudeAdapter.settings_layer_path(SettingsLayer::Local, None).is_err());
+        assert_eq!(ClaudeAdapter.read_settings_layers(None).len(), 2);
+    }
+
+    #[test]
+    fn writes_keep_key_order() {
+        let tmp = TempDir::new("settings-order");
+        let project = tmp.path().as_path();
+        let path = project.join(".claude/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"zeta":1,"model":"sonnet","alpha":{"z":1,"a":2},"env":{}}"#).unwrap();
+
+        ClaudeAdapter
+            .write_layer_setting(SettingsLayer::Project, Some(project), "model", Some("opus".into()))
+            .unwrap();
+        ClaudeAdapter
+            .write_layer_setting(SettingsLayer::Project, Some(project), "zeta", None)
+            .unwrap();
+        let content: String = fs::read_to_string(&path).unwrap().split_whitespace().collect();
+        assert_eq!(content, r#"{"model":"opus","alpha":{"z":1,"a":2},"env":{}}"#);
+    }
+}
+
+mod claude_mcp_approval_tests {
+    use super::*;
+
+    fn settings(project: &std::path::Path, file: &str) -> serde_json::Value {
+        let path = project.join(".claude").join(file);
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn reads_disabled_servers_from_any_project_layer() {
+        let tmp = TempDir::new("claude-approval-read");
+        let scope = ConfigScope::Project(tmp.path().clone());
+        ClaudeAdapter.write_mcp_server(&make_mcp_server("a"), &scope).unwrap();
+        ClaudeAdapter.write_mcp_server(&make_mcp_server("b"), &scope).unwrap();
+        fs::write(
+            tmp.path().join(".claude/settings.json"),
+            r#"{"disabledMcpjsonServers":["b"]}"#,
+        )
+        .unwrap();
+
+        let servers = ClaudeAdapter.read_mcp_servers(&scope).unwrap();
+        assert!(servers[0].enabled);
+        assert!(!servers[1].enabled);
+    }
+
+    #[test]
+    fn unapproved_servers_are_pending() {
+        let tmp = TempDir::new("claude-approval-pending");
+        let project = tmp.path().as_path();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(
+            project.join(".claude/.mcp.json"),
+            r#"{"mcpServers":{"new":{"command":"npx","args":[]}}}"#,
+        )
+        .unwrap();
+
+        let mut server = ClaudeAdapter.read_mcp_servers(&scope).unwrap().remove(0);
+        assert!(!server.enabled);
+        assert_eq!(ClaudeAdapter.project_server_approval(project, "new"), None);
+
+        // Editing it keeps it pending rather than rejecting it.
+        server.command = "node".into();
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+        assert_eq!(ClaudeAdapter.project_server_approval(project, "new"), None);
+        assert!(!project.join(".claude/settings.local.json").exists());
+
+        fs::write(project.join(".claude/settings.json"), r#"{"enableAllProjectMcpServers":true}"#)
+            .unwrap();
+        assert!(ClaudeAdapter.read_mcp_servers(&scope).unwrap()[0].enabled);
+    }
+
+    #[test]
+    fn toggling_updates_the_listing_file() {
+        let tmp = TempDir::new("claude-approval-toggle");
+        let project = tmp.path().as_path();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let mut server = make_mcp_server("srv");
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+        assert_eq!(
+            settings(project, "settings.local.json")["enabledMcpjsonServers"],
+            serde_json::json!(["srv"])
+        );
+
+        server.enabled = false;
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+        let local = settings(project, "settings.local.json");
+        assert_eq!(local["disabledMcpjsonServers"], serde_json::json!(["srv"]));
+        assert!(local.get("enabledMcpjsonServers").is_none());
+        assert!(!ClaudeAdapter.read_mcp_servers(&scope).unwrap()[0].enabled);
+
+        // A rejection in the shared file is lifted there, not shadowed locally.
+        fs::write(project.join(".claude/settings.local.json"), "{}").unwrap();
+        fs::write(
+            project.join(".claude/settings.json"),
+            r#"{"enableAllProjectMcpServers":true,"disabledMcpjsonServers":["srv"]}"#,
+        )
+        .unwrap();
+        server.enabled = true;
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+        assert!(settings(project, "settings.json").get("disabledMcpjsonServers").is_none());
+        assert_eq!(settings(project, "settings.local.json"), serde_json::json!({}));
+        assert!(ClaudeAdapter.read_mcp_servers(&scope).unwrap()[0].enabled);
+    }
+
+    #[test]
+    fn rename_and_delete_follow_the_lists() {
+        let tmp = TempDir::new("claude-approval-rename");
+        let project = tmp.path().as_path();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        let mut server = make_mcp_server("old");
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+        server.enabled = false;
+        ClaudeAdapter.write_mcp_server(&server, &scope).unwrap();
+
+        ClaudeAdapter.rename_mcp_server("old", "new", &scope).unwrap();
+        assert_eq!(
+            settings(project, "settings.local.json")["disabledMcpjsonServers"],
+            serde_json::json!(["new"])
+        );
+        assert!(!ClaudeAdapter.read_mcp_servers(&scope).unwrap()[0].enabled);
+
+        ClaudeAdapter.delete_mcp_server("new", &scope).unwrap();
+        assert_eq!(settings(project, "settings.local.json"), serde_json::json!({}));
+    }
+
+    #[test]
+    fn global_servers_cannot_be_disabled() {
+        let mut server = make_mcp_server("srv");
+        server.enabled = false;
+        assert!(ClaudeAdapter.write_mcp_server(&server, &ConfigScope::Global).is_err());
+    }
+}
+
+mod claude_plugin_tests {
+    use super::*;
+    use crate::core::SettingsLayer;
+
+    #[test]
+    fn toggles_the_layer_that_decides() {
+        let tmp = TempDir::new("claude-plugins");
+        let project = tmp.path().as_path();
+        let scope = ConfigScope::Project(tmp.path().clone());
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(
+            project.join(".claude/settings.local.json"),
+            r#"{"enabledPlugins":{"lint@acme":false}}"#,
+        )
+        .unwrap();
+
+        ClaudeAdapter.set_plugin_enabled("lint@acme", true, Some(project)).unwrap();
+        ClaudeAdapter.set_plugin_enabled("docs@acme", false, Some(project)).unwrap();
+        let local = ClaudeAdapter
+            .read_settings_layers(Some(project))
+            .into_iter()
+            .find(|l| l.layer == SettingsLayer::Local)
+            .unwrap();
+        assert_eq!(local.settings["enabledPlugins"], serde_json::json!({"lint@acme": true}));
+        let settings = ClaudeAdapter.read_settings(&scope).unwrap();
+        assert_eq!(settings["enabledPlugins"], serde_json::json!({"docs@acme": false}));
+
+        let enabled = ClaudeAdapter.enabled_plugins(Some(project));
+        assert_eq!(enabled["lint@acme"], true);
+        assert_eq!(enabled["docs@acme"], false);
+        assert!(ClaudeAdapter.set_plugin_enabled(" ", true, Some(project)).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use super::jsonc;
+use crate::core::{
+    Command, CommandCapabilities, ConfigAdapter, ConfigScope, McpCapabilities, McpServer,
+    ModelCapabilities, ModelSettings, PermissionCapabilities, Permissions, RulesCapabilities, Skill,
+    SkillCapabilities, SkillScan, ToolCapabilities, Transport,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct ZedAdapter;
+
+#[derive(Debug, Deserialize, Default)]
+struct ZedSettings {
+    #[serde(default)]
+    context_servers: BTreeMap<String, ZedContextServer>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct ZedContextServer {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    command: Option<ZedCommand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Zed accepts both `"command": { "path", "args", "env" }` and the flat
+/// `"command": "npx"` form with `args`/`env` next to it. We always write the
+/// nested form.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum ZedCommand {
+    Nested {
+        path: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        env: Option<HashMap<String, String>>,
+    },
+    Flat(String),
+}
+
+impl ZedContextServer {
+    fn into_mcp_server(mut self, name: String) -> McpServer {
+        let (command, args, env) = match self.command {
+            Some(ZedCommand::Nested { path, args, env }) => (path, args, env.unwrap_or_default()),
+            Some(ZedCommand::Flat(path)) => {
+                let args = self
+                    .extra
+                    .remove("args")
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default();
+                let env = self
+                    .extra
+                    .remove("env")
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default();
+                (path, args, env)
+            }
+            None => (String::new(), Vec::new(), HashMap::new()),
+        };
+        McpServer {
+            name,
+            command,
+            args,
+            env,
+            url: self.url,
+            enabled: true,
+        }
+    }
+}
+
+impl ZedAdapter {
+    fn settings_path(&self, scope: &ConfigScope) -> PathBuf {
+        match scope {
+            ConfigScope::Global => self.global_config_path().join("settings.json"),
+            ConfigScope::Project(p) => self.project_config_path(p).join("settings.json"),
+        }
+    }
+
+    fn rules_path(&self, scope: &ConfigScope) -> Result<PathBuf, String> {
+        match scope {
+            ConfigScope::Global => Err("Zed has no global rules file".into()),
+            ConfigScope::Project(p) => Ok(p.join(".rules")),
+        }
+    }
+
+    fn read_settings_text(&self, path: &Path) -> Result<String, String> {
+        match fs::read_to_string(path) {
+            Ok(c) => Ok(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn parse_settings(&self, content: &str, path: &Path) -> Result<ZedSettings, String> {
+        let value = jsonc::parse(content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+        serde_json::from_value(value)
+            .map_err(|e| format!("Invalid settings in {}: {}", path.display(), e))
+    }
+
+    pub fn capabilities() -> ToolCapabilities {
+        ToolCapabilities {
+            mcp: McpCapabilities {
+                transports: vec![Transport::Stdio, Transport::Http],
+                global: true,
+                project: true,
+                ..Default::default()
+            },
+            skills: SkillCapabilities::default(),
+            rules: RulesCapabilities { global: false, project: true, imports: false },
+            commands: CommandCapabilities::default(),
+            permissions: PermissionCapabilities::default(),
+            models: ModelCapabilities::default(),
+        }
+    }
+}
+
+impl ConfigAdapter for ZedAdapter {
+    fn tool_name(&self) -> &'static str {
+        "Zed"
+    }
+
+    fn global_config_path(&self) -> PathBuf {
+        dirs::home_dir().unwrap_or_default().join(".config").join("zed")
+    }
+
+    fn project_config_path(&self, project: &PathBuf) -> PathBuf {
+        project.join(".zed")
+    }
+
+    fn read_mcp_servers(&self, scope: &ConfigScope) -> Result<Vec<McpServer>, String> {
+        let path = self.settings_path(scope);
+        let content = self.read_settings_text(&path)?;
+        let settings = self.parse_settings(&content, &path)?;
+
+        let mut servers: Vec<McpServer> = settings
+            .context_servers
+            .into_iter()
+            .map(|(name, entry)| entry.into_mcp_server(name))
+            .collect();
+
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(servers)
+    }
+
+    fn write_mcp_server(&self, server: &McpServer, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.settings_path(scope);
+        let content = self.read_settings_text(&path)?;
+        let mut settings = self.parse_settings(&content, &path)?;
+
+        let mut entry = settings.context_servers.remove(&server.name).unwrap_or_default();
+        entry.source = Some("custom".into());
+        entry.extra.remove("args");
+        entry.extra.remove("env");
+        match server.url.as_ref().filter(|u| !u.trim().is_empty()) {
+            Some(url) => {
+                entry.url = Some(url.clone());
+                entry.command = None;
+            }
+            None => {
+                entry.url = None;
+                entry.command = Some(ZedCommand::Nested {
+                    path: server.command.clone(),
+                    args: server.args.clone(),
+                    env: if server.env.is_empty() { None } else { Some(server.env.clone()) },
+                });
+            }
+        }
+
+        let value = serde_json::to_value(&entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+        let updated = jsonc::set(&content, &["context_servers", &server.name], &value)
+            .map_err(|e| format!("Failed to update {}: {}", path.display(), e))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn delete_mcp_server(&self, name: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.settings_path(scope);
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        let updated = jsonc::remove(&content, &["context_servers", name])
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.settings_path(scope);
+        let content = self.read_settings_text(&path)?;
+        let settings = self.parse_settings(&content, &path)?;
+
+        if settings.context_servers.contains_key(new) {
+            return Err(format!("Server already exists: {}", new));
+        }
+        let entry = settings
+            .context_servers
+            .get(old)
+            .ok_or_else(|| format!("Server not found: {}", old))?;
+        let value = serde_json::to_value(entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+
+        let updated = jsonc::remove(&content, &["context_servers", old])
+            .and_then(|c| jsonc::set(&c, &["context_servers", new], &value))
+            .map_err(|e| format!("Failed to update {}: {}", path.display(), e))?;
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        Some(self.settings_path(scope))
+    }
+
+    fn read_skills(&self, _scope: &ConfigScope) -> Result<SkillScan, String> {
+        Ok(SkillScan::default())
+    }
+
+    fn write_skill(&self, _skill: &Skill, _scope: &ConfigScope) -> Result<(), String> {
+        Err("Zed does not support skills".into())
+    }
+
+    fn delete_skill(&self, _name: &str, _scope: &ConfigScope) -> Result<(), String> {
+        Err("Zed does not support skills".into())
+    }
+
+    fn skill_dir(&self, _name: &str, _scope: &ConfigScope) -> Result<PathBuf, String> {
+        Err("Zed does not support skills".into())
+    }
+
+    fn read_commands(&self, _scope: &ConfigScope) -> Result<Vec<Command>, String> {
+        Ok(vec![])
+    }
+
+    fn write_command(&self, _command: &Command, _scope: &ConfigScope) -> Result<(), String> {
+        Err("Zed does not support commands".into())
+    }
+
+    fn delete_command(&self, _name: &str, _scope: &ConfigScope) -> Result<(), String> {
+        Err("Zed does not support commands".into())
+    }
+
+    fn commands_dir(&self, _scope: &ConfigScope) -> Option<PathBuf> {
+        None
+    }
+
+    fn read_permissions(&self, _scope: &ConfigScope) -> Result<Permissions, String> {
+        Ok(Permissions::default())
+    }
+
+    fn write_permissions(
+        &self,
+        _permissions: &Permissions,
+        _scope: &ConfigScope,
+    ) -> Result<(), String> {
+        Err("Zed does not support permissions".into())
+    }
+
+    fn read_model_settings(&self, _scope: &ConfigScope) -> Result<ModelSettings, String> {
+        Ok(ModelSettings::default())
+    }
+
+    fn write_model_settings(
+        &self,
+        _settings: &ModelSettings,
+        _scope: &ConfigScope,
+    ) -> Result<(), String> {
+        Err("Zed does not support model settings".into())
+    }
+
+    fn read_rules(&self, scope: &ConfigScope) -> Result<String, String> {
+        let Ok(path) = self.rules_path(scope) else {
+            return Ok(String::new());
+        };
+        match fs::read_to_string(&path) {
+            Ok(c) => Ok(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn write_rules(&self, content: &str, scope: &ConfigScope) -> Result<(), String> {
+        let path = self.rules_path(scope)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
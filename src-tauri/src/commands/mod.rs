@@ -1,10 +1,28 @@
-use crate::adapters::{ClaudeAdapter, CodexAdapter, GeminiAdapter, OpenCodeAdapter};
-use crate::core::{convert_mcp_server, ConfigAdapter, ConfigScope, McpServer, Project, Skill, ToolType};
-use crate::db::ProjectRepo;
+use crate::core::{
+    adapter_for, add_to_gitignore, add_to_library, check_skill_name, checkout_skill,
+    claude_json_mcp_servers, claude_json_path, convert_command, convert_mcp_server,
+    convert_permissions, copy_skill_dir, find_skill_dir, find_tool, has_errors, head_commit,
+    is_gitignored, library_dir, link_state, link_to_library, lint_duplicates, lint_skill,
+    lint_skill_files, local_changes, manifests, merge_permissions, merge_settings,
+    read_skill_archive, reload_manifests, rename_skill_dir, resolve_mcp, resolve_repo, run_hook,
+    sample_hook_payload, scan_skills_dir, skill_changed, skill_diff, tools, unlink_from_library,
+    validate_agent, validate_hooks, write_skill_archive, Agent, AgentScan, ArchivedSkill,
+    ClaudePlugins, CodexProfile, CodexProfiles, Command, ConfigAdapter, ConfigScope, FileWatcher,
+    GeminiExtension, HookConfig, HookFinding, HookRun, InstalledSkill, LayeredSettings,
+    LintSeverity, ManifestLoadReport, McpLayer, McpServer, ModelSettings, Permissions, Project,
+    ResolvedMcpServer, ScopeKind, SettingsLayer, Skill, SkillConflict, SkillCopyMode, SkillFinding,
+    SkillLinkState, SkillScan, SkillSource, SourceSkill, ToolCapabilities, ToolDescriptor,
+    ToolManifest, ToolType, TrustLevel, DEFAULT_HOOK_TIMEOUT_SECS, EXTENSION_MANIFEST,
+    LOCAL_SETTINGS_ENTRY, SKILL_FILE,
+};
+use crate::adapters::{ClaudeAdapter, CodexAdapter, GeminiAdapter};
+use crate::db::{ProjectRepo, SkillSourceRepo};
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::Duration;
 use tauri::State;
 
 pub struct DbState(pub Mutex<Connection>);
@@ -23,23 +41,14 @@ fn scope_from(project_path: Option<String>) -> ConfigScope {
     }
 }
 
-fn get_adapter(tool: ToolType) -> Box<dyn ConfigAdapter> {
-    match tool {
-        ToolType::ClaudeCode => Box::new(ClaudeAdapter),
-        ToolType::Codex => Box::new(CodexAdapter),
-        ToolType::Gemini => Box::new(GeminiAdapter),
-        ToolType::OpenCode => Box::new(OpenCodeAdapter),
-    }
-}
-
 #[tauri::command]
 pub fn get_mcp_servers(tool: ToolType, project_path: Option<String>) -> Result<Vec<McpServer>, String> {
-    get_adapter(tool).read_mcp_servers(&scope_from(project_path))
+    adapter_for(&tool)?.read_mcp_servers(&scope_from(project_path))
 }
 
 #[tauri::command]
 pub fn save_mcp_server(tool: ToolType, server: McpServer, project_path: Option<String>) -> Result<(), String> {
-    get_adapter(tool).write_mcp_server(&server, &scope_from(project_path))
+    adapter_for(&tool)?.write_mcp_server(&server, &scope_from(project_path))
 }
 
 #[tauri::command]
@@ -48,17 +57,57 @@ pub fn delete_mcp_server(tool: ToolType, name: String, project_path: Option<Stri
     if name.is_empty() {
         return Err("Server name cannot be empty".into());
     }
-    get_adapter(tool).delete_mcp_server(name, &scope_from(project_path))
+    adapter_for(&tool)?.delete_mcp_server(name, &scope_from(project_path))
 }
 
 #[tauri::command]
-pub fn get_skills(tool: ToolType, project_path: Option<String>) -> Result<Vec<Skill>, String> {
-    get_adapter(tool).read_skills(&scope_from(project_path))
+pub fn get_skills(tool: ToolType, project_path: Option<String>) -> Result<SkillScan, String> {
+    adapter_for(&tool)?.read_skills(&scope_from(project_path))
 }
 
+/// Lint errors only block creating a skill; an existing one is saved anyway
+/// so it can be fixed in steps, and the findings come back with it.
 #[tauri::command]
-pub fn save_skill(tool: ToolType, skill: Skill, project_path: Option<String>) -> Result<(), String> {
-    get_adapter(tool).write_skill(&skill, &scope_from(project_path))
+pub fn save_skill(
+    tool: ToolType,
+    skill: Skill,
+    project_path: Option<String>,
+) -> Result<Vec<SkillFinding>, String> {
+    let adapter = adapter_for(&tool)?;
+    let scope = scope_from(project_path);
+    let dest = adapter.skill_dir(&skill.name, &scope)?;
+
+    // Resources are checked where they currently live, before they are copied.
+    let source = if skill.path.is_dir() { skill.path.as_path() } else { dest.as_path() };
+    let mut findings = lint_skill(&skill, source);
+    findings.retain(|f| f.rule != "name-matches-dir");
+    if let ConfigScope::Project(_) = scope {
+        let global = adapter.read_skills(&ConfigScope::Global)?.skills;
+        findings.extend(lint_duplicates(&global, std::slice::from_ref(&skill)));
+    }
+    if !dest.join(SKILL_FILE).is_file() && has_errors(&findings) {
+        let errors: Vec<_> = findings
+            .iter()
+            .filter(|f| f.severity == LintSeverity::Error)
+            .map(|f| f.message.as_str())
+            .collect();
+        return Err(format!("Invalid skill: {}", errors.join("; ")));
+    }
+
+    adapter.write_skill(&skill, &scope)?;
+
+    // Saving a skill that came from elsewhere also brings its other files along.
+    if skill.path.is_dir() && !same_dir(&skill.path, &dest) {
+        copy_skill_dir(&skill.path, &dest, &[SKILL_FILE])?;
+    }
+    Ok(findings)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
 }
 
 #[tauri::command]
@@ -67,17 +116,41 @@ pub fn delete_skill(tool: ToolType, name: String, project_path: Option<String>)
     if name.is_empty() {
         return Err("Skill name cannot be empty".into());
     }
-    get_adapter(tool).delete_skill(name, &scope_from(project_path))
+    adapter_for(&tool)?.delete_skill(name, &scope_from(project_path))
 }
 
 #[tauri::command]
 pub fn get_rules(tool: ToolType, project_path: Option<String>) -> Result<String, String> {
-    get_adapter(tool).read_rules(&scope_from(project_path))
+    adapter_for(&tool)?.read_rules(&scope_from(project_path))
 }
 
 #[tauri::command]
 pub fn save_rules(tool: ToolType, content: String, project_path: Option<String>) -> Result<(), String> {
-    get_adapter(tool).write_rules(&content, &scope_from(project_path))
+    adapter_for(&tool)?.write_rules(&content, &scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn get_commands(tool: ToolType, project_path: Option<String>) -> Result<Vec<Command>, String> {
+    adapter_for(&tool)?.read_commands(&scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn save_command(tool: ToolType, command: Command, project_path: Option<String>) -> Result<(), String> {
+    let mut command = command;
+    command.name = command.name.trim().to_string();
+    if command.name.is_empty() {
+        return Err("Command name cannot be empty".into());
+    }
+    adapter_for(&tool)?.write_command(&command, &scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn delete_command(tool: ToolType, name: String, project_path: Option<String>) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Command name cannot be empty".into());
+    }
+    adapter_for(&tool)?.delete_command(name, &scope_from(project_path))
 }
 
 #[tauri::command]
@@ -108,6 +181,338 @@ pub fn remove_project(id: i64, db: State<'_, DbState>) -> Result<(), String> {
     ProjectRepo::new(&conn).remove(id)
 }
 
+#[tauri::command]
+pub fn list_skill_sources(db: State<'_, DbState>) -> Result<Vec<SkillSource>, String> {
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn).list()
+}
+
+#[tauri::command]
+pub fn add_skill_source(url: String, db: State<'_, DbState>) -> Result<SkillSource, String> {
+    resolve_repo(&url)?;
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn).add(&url)
+}
+
+#[tauri::command]
+pub fn remove_skill_source(id: i64, db: State<'_, DbState>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn).remove(id)
+}
+
+fn source_repo(db: &State<'_, DbState>, id: i64) -> Result<PathBuf, String> {
+    let source = {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        SkillSourceRepo::new(&conn).get(id)?
+    };
+    resolve_repo(&source.ok_or_else(|| format!("Skill source not found: {}", id))?.url)
+}
+
+#[tauri::command]
+pub fn list_source_skills(source_id: i64, db: State<'_, DbState>) -> Result<Vec<SourceSkill>, String> {
+    let repo = source_repo(&db, source_id)?;
+    crate::core::list_source_skills(&repo, &head_commit(&repo)?)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillInstallResult {
+    /// Skill folder in the source, as requested.
+    pub path: String,
+    pub tool: ToolType,
+    pub installed: Option<InstalledSkill>,
+    pub error: Option<String>,
+}
+
+/// Installs the skills at `paths` (folders relative to the repo root) from
+/// the source's HEAD into each of `tools`. Each skill and tool succeeds or
+/// fails on its own; a failure does not stop the rest.
+#[tauri::command]
+pub fn install_source_skills(
+    source_id: i64,
+    paths: Vec<String>,
+    tools: Vec<ToolType>,
+    project_path: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<Vec<SkillInstallResult>, String> {
+    let repo = source_repo(&db, source_id)?;
+    let head = head_commit(&repo)?;
+    let available = crate::core::list_source_skills(&repo, &head)?;
+    let scope = scope_from(project_path.clone());
+    let project = project_path.as_deref().map(str::trim).filter(|p| !p.is_empty());
+    let installs = {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        SkillSourceRepo::new(&conn).list_installs()?
+    };
+
+    // The DB is only locked to record each install, not while git runs.
+    let install = |path: &str, tool: &ToolType| -> Result<InstalledSkill, String> {
+        let skill = available
+            .iter()
+            .find(|s| s.path == path.trim())
+            .ok_or_else(|| format!("Skill not found in source: {}", path))?;
+        let dest = adapter_for(tool)?.skill_dir(&skill.name, &scope)?;
+        let tracked = installs.iter().find(|i| {
+            i.tool == *tool && i.name == skill.name && i.project_path.as_deref() == project
+        });
+        match tracked {
+            Some(install) => refuse_local_changes(&repo, install, &dest, false)?,
+            None if dest.exists() => {
+                return Err(format!("Skill {} already exists in {}", skill.name, tool))
+            }
+            None => {}
+        }
+        checkout_skill(&repo, &head, &skill.path, &dest)?;
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        SkillSourceRepo::new(&conn).record_install(
+            source_id,
+            &skill.name,
+            &skill.path,
+            tool,
+            project,
+            &head,
+        )
+    };
+
+    let mut results = Vec::new();
+    for path in &paths {
+        for tool in &tools {
+            let (installed, error) = match install(path, tool) {
+                Ok(i) => (Some(i), None),
+                Err(e) => (None, Some(e)),
+            };
+            results.push(SkillInstallResult {
+                path: path.clone(),
+                tool: tool.clone(),
+                installed,
+                error,
+            });
+        }
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub fn list_installed_skills(db: State<'_, DbState>) -> Result<Vec<InstalledSkill>, String> {
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn).list_installs()
+}
+
+/// Errors when files in `dest` were edited since `install` was checked out,
+/// unless `force` allows overwriting them.
+fn refuse_local_changes(
+    repo: &Path,
+    install: &InstalledSkill,
+    dest: &Path,
+    force: bool,
+) -> Result<(), String> {
+    if force {
+        return Ok(());
+    }
+    let changed = local_changes(repo, &install.commit, &install.skill_path, dest)?;
+    if changed.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Skill {} in {} has local changes ({}); update with force to overwrite them",
+        install.name,
+        install.tool,
+        changed.join(", ")
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillUpdate {
+    pub install: InstalledSkill,
+    /// Source HEAD the skill would be updated to.
+    pub head: String,
+}
+
+/// Installed skills whose folder changed between their commit and the
+/// source's HEAD. Sources that cannot be read are left out.
+#[tauri::command]
+pub fn check_skill_updates(db: State<'_, DbState>) -> Result<Vec<SkillUpdate>, String> {
+    let installs = {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        SkillSourceRepo::new(&conn).list_installs()?
+    };
+    let mut heads: HashMap<i64, Option<(PathBuf, String)>> = HashMap::new();
+    let mut updates = Vec::new();
+    for install in installs {
+        let source = heads.entry(install.source_id).or_insert_with(|| {
+            let repo = source_repo(&db, install.source_id).ok()?;
+            let head = head_commit(&repo).ok()?;
+            Some((repo, head))
+        });
+        let Some((repo, head)) = source else {
+            continue;
+        };
+        if *head != install.commit
+            && skill_changed(repo, &install.commit, head, &install.skill_path).unwrap_or(false)
+        {
+            updates.push(SkillUpdate { head: head.clone(), install });
+        }
+    }
+    Ok(updates)
+}
+
+fn installed_skill(db: &State<'_, DbState>, id: i64) -> Result<InstalledSkill, String> {
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn)
+        .get_install(id)?
+        .ok_or_else(|| format!("Installed skill not found: {}", id))
+}
+
+/// Diff of the installed skill's folder from its commit to the source HEAD.
+#[tauri::command]
+pub fn preview_skill_update(install_id: i64, db: State<'_, DbState>) -> Result<String, String> {
+    let install = installed_skill(&db, install_id)?;
+    let repo = source_repo(&db, install.source_id)?;
+    skill_diff(&repo, &install.commit, &head_commit(&repo)?, &install.skill_path)
+}
+
+#[tauri::command]
+pub fn update_installed_skill(
+    install_id: i64,
+    force: bool,
+    db: State<'_, DbState>,
+) -> Result<InstalledSkill, String> {
+    let install = installed_skill(&db, install_id)?;
+    let repo = source_repo(&db, install.source_id)?;
+    let head = head_commit(&repo)?;
+    let scope = scope_from(install.project_path.clone());
+    let dest = adapter_for(&install.tool)?.skill_dir(&install.name, &scope)?;
+    refuse_local_changes(&repo, &install, &dest, force)?;
+    checkout_skill(&repo, &head, &install.skill_path, &dest)?;
+
+    let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+    SkillSourceRepo::new(&conn).record_install(
+        install.source_id,
+        &install.name,
+        &install.skill_path,
+        &install.tool,
+        install.project_path.as_deref(),
+        &head,
+    )
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameTarget {
+    pub tool: ToolType,
+    pub project_path: Option<String>,
+}
+
+/// Where a rename applies: just `tool` in the given scope, or with
+/// `propagate` every tool in the global scope and every registered project
+/// where `exists` finds the item.
+fn rename_targets(
+    db: &State<'_, DbState>,
+    tool: ToolType,
+    project_path: Option<String>,
+    propagate: bool,
+    exists: impl Fn(&dyn ConfigAdapter, &ConfigScope) -> bool,
+) -> Result<Vec<RenameTarget>, String> {
+    if !propagate {
+        return Ok(vec![RenameTarget { tool, project_path }]);
+    }
+    let mut scopes = vec![None];
+    {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        scopes.extend(ProjectRepo::new(&conn).list()?.into_iter().map(|p| Some(p.path)));
+    }
+    let mut targets = Vec::new();
+    for t in tools() {
+        let adapter = t.adapter();
+        for scope_path in &scopes {
+            if exists(adapter.as_ref(), &scope_from(scope_path.clone())) {
+                targets.push(RenameTarget {
+                    tool: t.id.clone(),
+                    project_path: scope_path.clone(),
+                });
+            }
+        }
+    }
+    Ok(targets)
+}
+
+#[tauri::command]
+pub fn rename_skill(
+    tool: ToolType,
+    old_name: String,
+    new_name: String,
+    project_path: Option<String>,
+    propagate: bool,
+    db: State<'_, DbState>,
+) -> Result<Vec<RenameTarget>, String> {
+    let (old, new) = (old_name.trim(), new_name.trim());
+    if new.is_empty() {
+        return Err("Skill name cannot be empty".into());
+    }
+    if let Some(msg) = check_skill_name(new) {
+        return Err(msg);
+    }
+
+    let has = |adapter: &dyn ConfigAdapter, scope: &ConfigScope, name: &str| {
+        adapter
+            .read_skills(scope)
+            .is_ok_and(|scan| scan.skills.iter().any(|s| s.name == name))
+    };
+    let targets = rename_targets(&db, tool, project_path, propagate, |a, s| has(a, s, old))?;
+    // Check every target first so a rename is not left half done. Tools that
+    // read the same folder share one rename.
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for t in &targets {
+        let adapter = adapter_for(&t.tool)?;
+        let scope = scope_from(t.project_path.clone());
+        adapter.skill_dir(new, &scope)?;
+        if has(adapter.as_ref(), &scope, new) {
+            return Err(format!("Skill {} already exists in {}", new, t.tool));
+        }
+        let dir = find_skill_dir(adapter.as_ref(), old, &scope)
+            .map_err(|e| format!("{} in {}", e, t.tool))?;
+        if dir.with_file_name(new).symlink_metadata().is_ok() {
+            return Err(format!("Skill {} already exists in {}", new, t.tool));
+        }
+        if !dirs.iter().any(|d| same_dir(d, &dir)) {
+            dirs.push(dir);
+        }
+    }
+    for dir in &dirs {
+        rename_skill_dir(dir, new)?;
+    }
+    Ok(targets)
+}
+
+#[tauri::command]
+pub fn rename_mcp_server(
+    tool: ToolType,
+    old_name: String,
+    new_name: String,
+    project_path: Option<String>,
+    propagate: bool,
+    db: State<'_, DbState>,
+) -> Result<Vec<RenameTarget>, String> {
+    let (old, new) = (old_name.trim(), new_name.trim());
+    if new.is_empty() {
+        return Err("Server name cannot be empty".into());
+    }
+
+    let has = |adapter: &dyn ConfigAdapter, scope: &ConfigScope, name: &str| {
+        adapter
+            .read_mcp_servers(scope)
+            .is_ok_and(|servers| servers.iter().any(|s| s.name == name))
+    };
+    let targets = rename_targets(&db, tool, project_path, propagate, |a, s| has(a, s, old))?;
+    for t in &targets {
+        if has(adapter_for(&t.tool)?.as_ref(), &scope_from(t.project_path.clone()), new) {
+            return Err(format!("Server {} already exists in {}", new, t.tool));
+        }
+    }
+    for t in &targets {
+        adapter_for(&t.tool)?.rename_mcp_server(old, new, &scope_from(t.project_path.clone()))?;
+    }
+    Ok(targets)
+}
+
 #[tauri::command]
 pub fn copy_mcp_to_tool(
     from_tool: ToolType,
@@ -120,26 +525,74 @@ pub fn copy_mcp_to_tool(
         return Err("Server name cannot be empty".into());
     }
     let scope = scope_from(project_path);
-    let to_adapter = get_adapter(to_tool);
+    let to_adapter = adapter_for(&to_tool)?;
 
     if to_adapter.read_mcp_servers(&scope)?.iter().any(|s| s.name == name) {
         return Ok(CopyResult { server: None, warnings: vec![], skipped: true });
     }
 
-    let server = get_adapter(from_tool)
+    let server = adapter_for(&from_tool)?
         .read_mcp_servers(&scope)?
         .into_iter()
         .find(|s| s.name == name)
         .ok_or_else(|| format!("MCP server not found: {}", name))?;
 
-    let result = convert_mcp_server(&server, from_tool, to_tool);
+    let result = convert_mcp_server(&server, from_tool, to_tool, &scope);
     to_adapter.write_mcp_server(&result.server, &scope)?;
     Ok(CopyResult { server: Some(result.server), warnings: result.warnings, skipped: false })
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandCopyResult {
+    pub command: Option<Command>,
+    pub warnings: Vec<String>,
+    pub skipped: bool,
+}
+
+/// Copies a command within one scope, rewriting its argument placeholder for
+/// the target tool.
+#[tauri::command]
+pub fn copy_command_to_tool(
+    from_tool: ToolType,
+    to_tool: ToolType,
+    name: String,
+    project_path: Option<String>,
+) -> Result<CommandCopyResult, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Command name cannot be empty".into());
+    }
+    let scope = scope_from(project_path);
+    let to_adapter = adapter_for(&to_tool)?;
+
+    if to_adapter.read_commands(&scope)?.iter().any(|c| c.name == name) {
+        return Ok(CommandCopyResult { command: None, warnings: vec![], skipped: true });
+    }
+
+    let command = adapter_for(&from_tool)?
+        .read_commands(&scope)?
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| format!("Command not found: {}", name))?;
+
+    let result = convert_command(&command, from_tool, to_tool);
+    to_adapter.write_command(&result.command, &scope)?;
+    Ok(CommandCopyResult {
+        command: Some(result.command),
+        warnings: result.warnings,
+        skipped: false,
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillCopyResult {
     pub skipped: bool,
+    /// Files written, relative to the new skill folder.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// The target is a symlink into the skill library rather than a copy.
+    #[serde(default)]
+    pub linked: bool,
 }
 
 #[tauri::command]
@@ -148,39 +601,613 @@ pub fn copy_skill_to_tool(
     to_tool: ToolType,
     skill_name: String,
     project_path: Option<String>,
+    mode: Option<SkillCopyMode>,
 ) -> Result<SkillCopyResult, String> {
     let name = skill_name.trim();
     if name.is_empty() {
         return Err("Skill name cannot be empty".into());
     }
     let scope = scope_from(project_path);
-    let to_adapter = get_adapter(to_tool);
+    let to_adapter = adapter_for(&to_tool)?;
 
-    if to_adapter.read_skills(&scope)?.iter().any(|s| s.name == name) {
-        return Ok(SkillCopyResult { skipped: true });
+    if to_adapter.read_skills(&scope)?.skills.iter().any(|s| s.name == name) {
+        return Ok(SkillCopyResult { skipped: true, files: vec![], linked: false });
     }
 
-    let skill = get_adapter(from_tool)
+    let from_adapter = adapter_for(&from_tool)?;
+    let skill = from_adapter
         .read_skills(&scope)?
+        .skills
         .into_iter()
         .find(|s| s.name == name)
         .ok_or_else(|| format!("Skill not found: {}", name))?;
 
-    to_adapter.write_skill(&skill, &scope)?;
-    Ok(SkillCopyResult { skipped: false })
+    let dest = to_adapter.skill_dir(&skill.name, &scope)?;
+    if mode.unwrap_or_default() == SkillCopyMode::Link {
+        // The source becomes a link as well, so neither side can drift. A
+        // skill read through a fallback dir belongs to another tool.
+        if !same_dir(&skill.path, &from_adapter.skill_dir(&skill.name, &scope)?) {
+            return Err(format!(
+                "{} is not in {}'s own skills dir; link it from the tool that owns it",
+                skill.path.display(),
+                from_tool
+            ));
+        }
+        let entry = add_to_library(&library_dir()?, &skill.path, &skill.name)?;
+        link_to_library(&entry, &skill.path)?;
+        link_to_library(&entry, &dest)?;
+        return Ok(SkillCopyResult { skipped: false, files: vec![], linked: true });
+    }
+    let files = copy_skill_dir(&skill.path, &dest, &[])?;
+    Ok(SkillCopyResult { skipped: false, files, linked: false })
 }
 
-fn detect_tools(project_path: &Path) -> Vec<ToolType> {
-    let checks: &[(&str, ToolType)] = &[
-        (".claude", ToolType::ClaudeCode),
-        (".codex", ToolType::Codex),
-        (".gemini", ToolType::Gemini),
-        (".opencode", ToolType::OpenCode),
-    ];
-    checks
+#[tauri::command]
+pub fn get_agents(project_path: Option<String>) -> Result<AgentScan, String> {
+    ClaudeAdapter.read_agents(&scope_from(project_path))
+}
+
+/// Saves the agent and returns what `validate_agent` warned about.
+#[tauri::command]
+pub fn save_agent(agent: Agent, project_path: Option<String>) -> Result<Vec<String>, String> {
+    let mut agent = agent;
+    agent.name = agent.name.trim().to_string();
+    let warnings = validate_agent(&agent).map_err(|e| format!("Invalid agent: {}", e))?;
+    ClaudeAdapter.write_agent(&agent, &scope_from(project_path))?;
+    Ok(warnings)
+}
+
+#[tauri::command]
+pub fn delete_agent(name: String, project_path: Option<String>) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Agent name cannot be empty".into());
+    }
+    ClaudeAdapter.delete_agent(name, &scope_from(project_path))
+}
+
+/// Copies an agent between the global scope and a project (or between
+/// projects). Returns `false` when the target already has it.
+#[tauri::command]
+pub fn copy_agent(
+    name: String,
+    from_project: Option<String>,
+    to_project: Option<String>,
+) -> Result<bool, String> {
+    let name = name.trim();
+    let (from, to) = (scope_from(from_project), scope_from(to_project));
+    if from == to {
+        return Err("Source and target scope are the same".into());
+    }
+    if ClaudeAdapter.read_agents(&to)?.agents.iter().any(|a| a.name == name) {
+        return Ok(false);
+    }
+    let agent = ClaudeAdapter
+        .read_agents(&from)?
+        .agents
+        .into_iter()
+        .find(|a| a.name == name)
+        .ok_or_else(|| format!("Agent not found: {}", name))?;
+    ClaudeAdapter.write_agent(&agent, &to)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_hooks(project_path: Option<String>) -> Result<HookConfig, String> {
+    ClaudeAdapter.read_hooks(&scope_from(project_path))
+}
+
+fn hook_dir(scope: &ConfigScope) -> Option<PathBuf> {
+    match scope {
+        ConfigScope::Project(p) => Some(p.clone()),
+        ConfigScope::Global => dirs::home_dir(),
+    }
+}
+
+/// Saves the hooks unless validation finds errors; warnings are returned.
+#[tauri::command]
+pub fn save_hooks(hooks: HookConfig, project_path: Option<String>) -> Result<Vec<HookFinding>, String> {
+    let scope = scope_from(project_path);
+    let findings = validate_hooks(&hooks, hook_dir(&scope).as_deref());
+    let errors: Vec<_> = findings
         .iter()
-        .filter(|(dir, _)| project_path.join(dir).is_dir())
-        .map(|(_, t)| *t)
+        .filter(|f| f.severity == LintSeverity::Error)
+        .map(|f| format!("{}: {}", f.event, f.message))
+        .collect();
+    if !errors.is_empty() {
+        return Err(format!("Invalid hooks: {}", errors.join("; ")));
+    }
+    ClaudeAdapter.write_hooks(&hooks, &scope)?;
+    Ok(findings)
+}
+
+#[tauri::command]
+pub fn validate_hook_config(hooks: HookConfig, project_path: Option<String>) -> Vec<HookFinding> {
+    validate_hooks(&hooks, hook_dir(&scope_from(project_path)).as_deref())
+}
+
+/// Runs a hook command locally with a sample payload for `event` (or
+/// `payload`, if given) on stdin. Runs off the main thread since a hook may
+/// take up to its timeout.
+#[tauri::command(async)]
+pub fn test_hook(
+    command: String,
+    event: String,
+    payload: Option<String>,
+    timeout: Option<u64>,
+    project_path: Option<String>,
+) -> Result<HookRun, String> {
+    let cwd = hook_dir(&scope_from(project_path)).ok_or("HOME directory not found")?;
+    let payload = match payload.filter(|p| !p.trim().is_empty()) {
+        Some(p) => {
+            serde_json::from_str::<serde_json::Value>(&p)
+                .map_err(|e| format!("Payload is not valid JSON: {}", e))?;
+            p
+        }
+        None => sample_hook_payload(&event, &cwd).to_string(),
+    };
+    let timeout = Duration::from_secs(timeout.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS).max(1));
+    run_hook(&command, &payload, &cwd, timeout)
+}
+
+fn project_dir(project_path: &Option<String>) -> Option<&Path> {
+    project_path.as_deref().map(str::trim).filter(|p| !p.is_empty()).map(Path::new)
+}
+
+/// Every Claude Code settings layer and the merged result.
+#[tauri::command]
+pub fn get_claude_settings(project_path: Option<String>) -> LayeredSettings {
+    let project = project_dir(&project_path);
+    let layers = ClaudeAdapter.read_settings_layers(project);
+    let effective = merge_settings(
+        &layers.iter().map(|l| (l.layer, &l.settings)).collect::<Vec<_>>(),
+    );
+    LayeredSettings {
+        layers,
+        effective,
+        local_gitignored: project.map(|p| is_gitignored(p, LOCAL_SETTINGS_ENTRY)),
+    }
+}
+
+/// Sets (or with `None`, removes) a top-level key in one layer.
+#[tauri::command]
+pub fn save_claude_setting(
+    layer: SettingsLayer,
+    key: String,
+    value: Option<serde_json::Value>,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Setting key cannot be empty".into());
+    }
+    ClaudeAdapter.write_layer_setting(layer, project_dir(&project_path), key, value)
+}
+
+/// Adds `.claude/settings.local.json` to the project's `.gitignore`.
+/// Returns false if it was already ignored.
+#[tauri::command]
+pub fn gitignore_local_settings(project_path: String) -> Result<bool, String> {
+    let project = Path::new(project_path.trim());
+    if !project.is_dir() {
+        return Err(format!("Not a directory: {}", project_path));
+    }
+    add_to_gitignore(project, LOCAL_SETTINGS_ENTRY)
+}
+
+/// The MCP servers `tool` loads in a project: global and project entries
+/// merged with project precedence, shadowed duplicates flagged. Codex reads
+/// a project's own config only once the project is trusted.
+#[tauri::command]
+pub fn resolve_effective_mcp(
+    tool: ToolType,
+    project_path: String,
+) -> Result<Vec<ResolvedMcpServer>, String> {
+    let project = PathBuf::from(project_path.trim());
+    if !project.is_dir() {
+        return Err(format!("Not a directory: {}", project_path));
+    }
+    let adapter = adapter_for(&tool)?;
+    let project_scope = ConfigScope::Project(project);
+    let ignored = if tool == ToolType::CODEX {
+        let trusted = CodexAdapter.trust_levels()?.get(&trust_key(&project_path))
+            == Some(&TrustLevel::Trusted);
+        (!trusted).then(|| "Project is not trusted by Codex".to_string())
+    } else {
+        None
+    };
+    let project_dir = Path::new(project_path.trim());
+    let mut layers = Vec::new();
+    if tool == ToolType::CLAUDE_CODE {
+        for plugin in ClaudeAdapter.read_plugins(Some(project_dir)).plugins {
+            layers.push(McpLayer {
+                scope: ScopeKind::Global,
+                source: Some(plugin.path),
+                servers: plugin.mcp_servers,
+                ignored: (!plugin.enabled).then(|| format!("Plugin {} is disabled", plugin.id)),
+            });
+        }
+    }
+    if tool == ToolType::GEMINI {
+        // Extension servers load first; settings.json entries replace them.
+        for extension in GeminiAdapter::gemini().read_extensions()? {
+            layers.push(McpLayer {
+                scope: ScopeKind::Global,
+                source: Some(extension.path.join(EXTENSION_MANIFEST)),
+                servers: extension.mcp_servers,
+                ignored: (!extension.enabled)
+                    .then(|| format!("Extension {} is disabled", extension.name)),
+            });
+        }
+    }
+    if tool == ToolType::CLAUDE_CODE {
+        layers.extend(claude_mcp_layers(project_dir)?);
+        return Ok(resolve_mcp(layers));
+    }
+    for (scope, kind, ignored) in [
+        (ConfigScope::Global, ScopeKind::Global, None),
+        (project_scope, ScopeKind::Project, ignored),
+    ] {
+        layers.push(McpLayer {
+            servers: adapter.read_mcp_servers(&scope)?,
+            source: adapter.mcp_config_path(&scope),
+            scope: kind,
+            ignored,
+        });
+    }
+    Ok(resolve_mcp(layers))
+}
+
+/// Claude Code's `claude mcp add` scopes, lowest first: user, the project's
+/// `.mcp.json`, then local. Project servers load only once approved, and
+/// pending or rejected ones shadow nothing.
+fn claude_mcp_layers(project: &Path) -> Result<Vec<McpLayer>, String> {
+    let global = ClaudeAdapter.mcp_config_path(&ConfigScope::Global);
+    let mcp_json = project.join(".mcp.json");
+    let (mut approved, mut pending, mut rejected) = (Vec::new(), Vec::new(), Vec::new());
+    for server in claude_json_mcp_servers(&mcp_json, None)? {
+        match ClaudeAdapter.project_server_approval(project, &server.name) {
+            Some(true) => approved.push(server),
+            Some(false) => rejected.push(server),
+            None => pending.push(server),
+        }
+    }
+    let project_layer = |servers, ignored: Option<&str>| McpLayer {
+        scope: ScopeKind::Project,
+        source: Some(mcp_json.clone()),
+        servers,
+        ignored: ignored.map(String::from),
+    };
+    let path = claude_json_path();
+    Ok(vec![
+        McpLayer {
+            scope: ScopeKind::Global,
+            servers: ClaudeAdapter.read_mcp_servers(&ConfigScope::Global)?,
+            source: global,
+            ignored: Some("Claude Code does not read this file".into()),
+        },
+        McpLayer {
+            scope: ScopeKind::User,
+            source: Some(path.clone()),
+            servers: claude_json_mcp_servers(&path, None)?,
+            ignored: None,
+        },
+        project_layer(pending, Some("Awaiting approval")),
+        project_layer(rejected, Some("Rejected in project settings")),
+        project_layer(approved, None),
+        McpLayer {
+            scope: ScopeKind::Local,
+            servers: claude_json_mcp_servers(&path, Some(project))?,
+            source: Some(path),
+            ignored: None,
+        },
+    ])
+}
+
+#[tauri::command]
+pub fn get_permissions(tool: ToolType, project_path: Option<String>) -> Result<Permissions, String> {
+    adapter_for(&tool)?.read_permissions(&scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn save_permissions(
+    tool: ToolType,
+    permissions: Permissions,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    adapter_for(&tool)?.write_permissions(&permissions, &scope_from(project_path))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolPermissions {
+    pub tool: ToolType,
+    pub permissions: Option<Permissions>,
+    pub error: Option<String>,
+}
+
+/// Permissions of every tool that has them in the scope, side by side. A
+/// tool whose config fails to parse is reported with its error.
+#[tauri::command]
+pub fn compare_permissions(project_path: Option<String>) -> Vec<ToolPermissions> {
+    let scope = scope_from(project_path);
+    tools()
+        .into_iter()
+        .filter(|t| match scope {
+            ConfigScope::Global => t.capabilities.permissions.global,
+            ConfigScope::Project(_) => t.capabilities.permissions.project,
+        })
+        .map(|t| {
+            let read = adapter_for(&t.id).and_then(|a| a.read_permissions(&scope));
+            ToolPermissions {
+                tool: t.id,
+                permissions: read.as_ref().ok().cloned(),
+                error: read.err(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCopyResult {
+    pub permissions: Permissions,
+    pub warnings: Vec<String>,
+}
+
+/// Merges one tool's permissions into another's within a scope. Rules are
+/// added to the target's; approval and sandbox settings replace them.
+#[tauri::command]
+pub fn copy_permissions(
+    from_tool: ToolType,
+    to_tool: ToolType,
+    project_path: Option<String>,
+) -> Result<PermissionCopyResult, String> {
+    let scope = scope_from(project_path);
+    let source = adapter_for(&from_tool)?.read_permissions(&scope)?;
+    let result = convert_permissions(&source, from_tool, to_tool.clone());
+
+    let to_adapter = adapter_for(&to_tool)?;
+    let mut permissions = to_adapter.read_permissions(&scope)?;
+    merge_permissions(&mut permissions, &result.permissions);
+    to_adapter.write_permissions(&permissions, &scope)?;
+    Ok(PermissionCopyResult { permissions, warnings: result.warnings })
+}
+
+/// Moves a tool's copy of a skill into the library and links it back.
+#[tauri::command]
+pub fn link_skill(tool: ToolType, name: String, project_path: Option<String>) -> Result<(), String> {
+    let name = name.trim();
+    let dir = adapter_for(&tool)?.skill_dir(name, &scope_from(project_path))?;
+    if !dir.is_dir() {
+        return Err(format!("Skill not found: {}", name));
+    }
+    let entry = add_to_library(&library_dir()?, &dir, name)?;
+    link_to_library(&entry, &dir)
+}
+
+/// Turns a tool's link into the library back into its own copy.
+#[tauri::command]
+pub fn unlink_skill(
+    tool: ToolType,
+    name: String,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let name = name.trim();
+    let dir = adapter_for(&tool)?.skill_dir(name, &scope_from(project_path))?;
+    unlink_from_library(&library_dir()?.join(name), &dir)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillLinkStatus {
+    pub tool: ToolType,
+    pub name: String,
+    pub state: SkillLinkState,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillLinkStates {
+    pub states: Vec<SkillLinkStatus>,
+    /// Tools whose skills could not be read, with the reason.
+    pub errors: Vec<String>,
+}
+
+/// Library state of every skill in every tool, for showing which copies are
+/// linked and which have diverged. Skills a tool only reads through a
+/// fallback dir are reported by the tool that owns them.
+#[tauri::command]
+pub fn get_skill_link_states(project_path: Option<String>) -> Result<SkillLinkStates, String> {
+    let library = library_dir()?;
+    let scope = scope_from(project_path);
+    let mut result = SkillLinkStates::default();
+    for tool in tools() {
+        let adapter = tool.adapter();
+        let scan = match adapter.read_skills(&scope) {
+            Ok(scan) => scan,
+            Err(e) => {
+                result.errors.push(format!("{}: {}", tool.name, e));
+                continue;
+            }
+        };
+        for skill in scan.skills {
+            let owned = adapter
+                .skill_dir(&skill.name, &scope)
+                .is_ok_and(|dir| same_dir(&skill.path, &dir));
+            if !owned {
+                continue;
+            }
+            result.states.push(SkillLinkStatus {
+                tool: tool.id.clone(),
+                state: link_state(&library, &skill.path, &skill.name),
+                name: skill.name,
+            });
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn list_library_skills() -> Result<SkillScan, String> {
+    scan_skills_dir(&library_dir()?)
+}
+
+/// Lints every skill in the scope. Project scope also flags names that
+/// shadow a global skill.
+#[tauri::command]
+pub fn lint_skills(tool: ToolType, project_path: Option<String>) -> Result<Vec<SkillFinding>, String> {
+    let adapter = adapter_for(&tool)?;
+    let scope = scope_from(project_path);
+    let scan = adapter.read_skills(&scope)?;
+
+    let mut findings: Vec<SkillFinding> = scan.diagnostics.iter().map(SkillFinding::from).collect();
+    for skill in &scan.skills {
+        findings.extend(lint_skill(skill, &skill.path));
+    }
+    if let ConfigScope::Project(_) = scope {
+        let global = adapter.read_skills(&ConfigScope::Global)?.skills;
+        findings.extend(lint_duplicates(&global, &scan.skills));
+    }
+    Ok(findings)
+}
+
+#[tauri::command]
+pub fn export_skills(
+    tool: ToolType,
+    names: Vec<String>,
+    project_path: Option<String>,
+    dest: String,
+) -> Result<Vec<String>, String> {
+    let skills = adapter_for(&tool)?.read_skills(&scope_from(project_path))?.skills;
+    let selected = names
+        .iter()
+        .map(|name| {
+            skills
+                .iter()
+                .find(|s| s.name == name.trim())
+                .cloned()
+                .ok_or_else(|| format!("Skill not found: {}", name))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    write_skill_archive(&selected, Path::new(dest.trim()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillArchivePreview {
+    pub name: String,
+    pub description: Option<String>,
+    pub files: Vec<String>,
+    /// A skill with this name already exists in the target scope.
+    pub exists: bool,
+    pub findings: Vec<SkillFinding>,
+}
+
+#[tauri::command]
+pub fn preview_skill_archive(
+    tool: ToolType,
+    archive: String,
+    project_path: Option<String>,
+) -> Result<Vec<SkillArchivePreview>, String> {
+    let existing = adapter_for(&tool)?.read_skills(&scope_from(project_path))?.skills;
+    Ok(read_skill_archive(Path::new(archive.trim()))?
+        .into_iter()
+        .map(|a| SkillArchivePreview {
+            exists: existing.iter().any(|s| s.name == a.skill.name),
+            findings: lint_archived(&a),
+            files: a.files(),
+            name: a.skill.name,
+            description: a.skill.description,
+        })
+        .collect())
+}
+
+/// Lints an archived skill as if it were saved under its own name. The
+/// folder does not exist yet, so references are checked against the archive.
+fn lint_archived(archived: &ArchivedSkill) -> Vec<SkillFinding> {
+    let files = archived.files();
+    let exists = |target: &str| {
+        files.iter().any(|f| f == target || f.starts_with(&format!("{}/", target)))
+    };
+    lint_skill_files(&archived.skill, Some(&archived.skill.name), exists)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillImportResult {
+    pub name: String,
+    /// Set when the skill was renamed to avoid a conflict.
+    pub imported_as: Option<String>,
+    pub skipped: bool,
+    pub files: Vec<String>,
+}
+
+#[tauri::command]
+pub fn import_skill_archive(
+    tool: ToolType,
+    archive: String,
+    project_path: Option<String>,
+    conflict: SkillConflict,
+) -> Result<Vec<SkillImportResult>, String> {
+    let adapter = adapter_for(&tool)?;
+    let scope = scope_from(project_path);
+    let archived = read_skill_archive(Path::new(archive.trim()))?;
+
+    // Nothing is written unless every skill in the archive is valid.
+    for a in &archived {
+        let findings = lint_archived(a);
+        if has_errors(&findings) {
+            let errors: Vec<_> = findings
+                .iter()
+                .filter(|f| f.severity == LintSeverity::Error)
+                .map(|f| f.message.as_str())
+                .collect();
+            return Err(format!("Invalid skill {}: {}", a.skill.name, errors.join("; ")));
+        }
+    }
+
+    let mut taken: Vec<String> =
+        adapter.read_skills(&scope)?.skills.into_iter().map(|s| s.name).collect();
+    let mut results = Vec::new();
+    for a in archived {
+        let name = a.skill.name.clone();
+        let mut skill = a.skill.clone();
+        let exists = taken.contains(&name);
+        match conflict {
+            _ if !exists => {}
+            SkillConflict::Skip => {
+                results.push(SkillImportResult {
+                    name,
+                    imported_as: None,
+                    skipped: true,
+                    files: vec![],
+                });
+                continue;
+            }
+            SkillConflict::Overwrite => adapter.delete_skill(&name, &scope)?,
+            SkillConflict::Rename => {
+                skill.name = (2..)
+                    .map(|n| format!("{}-{}", name, n))
+                    .find(|candidate| !taken.contains(candidate))
+                    .unwrap_or_default();
+            }
+        }
+
+        adapter.write_skill(&skill, &scope)?;
+        let mut files = vec![SKILL_FILE.to_string()];
+        files.extend(a.extract_resources(&adapter.skill_dir(&skill.name, &scope)?)?);
+        files.sort();
+        taken.push(skill.name.clone());
+        results.push(SkillImportResult {
+            imported_as: (skill.name != name).then(|| skill.name.clone()),
+            name,
+            skipped: false,
+            files,
+        });
+    }
+    Ok(results)
+}
+
+fn detect_tools(project_path: &Path) -> Vec<ToolType> {
+    tools()
+        .into_iter()
+        .filter(|t| t.is_used_in(project_path))
+        .map(|t| t.id)
         .collect()
 }
 
@@ -211,31 +1238,362 @@ pub fn get_project_config_summary(path: String) -> Result<Vec<ProjectConfigSumma
     let scope = ConfigScope::Project(PathBuf::from(path.trim()));
     let mut summaries = Vec::new();
     for tool in tools {
-        let adapter = get_adapter(tool);
+        let adapter = adapter_for(&tool)?;
         let mcp_count = adapter.read_mcp_servers(&scope).unwrap_or_default().len();
-        let skills_count = adapter.read_skills(&scope).unwrap_or_default().len();
+        let skills_count = adapter.read_skills(&scope).unwrap_or_default().skills.len();
         let has_rules = adapter.read_rules(&scope).map(|r| !r.trim().is_empty()).unwrap_or(false);
         summaries.push(ProjectConfigSummary { tool, mcp_count, skills_count, has_rules });
     }
     Ok(summaries)
 }
 
+#[tauri::command]
+pub fn get_codex_profiles(project_path: Option<String>) -> Result<CodexProfiles, String> {
+    CodexAdapter.read_profiles(&scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn save_codex_profile(profile: CodexProfile, project_path: Option<String>) -> Result<(), String> {
+    let mut profile = profile;
+    profile.name = profile.name.trim().to_string();
+    CodexAdapter.write_profile(&profile, &scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn delete_codex_profile(name: String, project_path: Option<String>) -> Result<(), String> {
+    CodexAdapter.delete_profile(name.trim(), &scope_from(project_path))
+}
+
+/// Copies a profile under a new name; fails if the name is taken.
+#[tauri::command]
+pub fn clone_codex_profile(
+    name: String,
+    new_name: String,
+    project_path: Option<String>,
+) -> Result<CodexProfile, String> {
+    let scope = scope_from(project_path);
+    let (name, new_name) = (name.trim(), new_name.trim());
+    let profiles = CodexAdapter.read_profiles(&scope)?.profiles;
+    if profiles.iter().any(|p| p.name == new_name) {
+        return Err(format!("Profile already exists: {}", new_name));
+    }
+    let mut profile = profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Profile not found: {}", name))?;
+    profile.name = new_name.to_string();
+    CodexAdapter.write_profile(&profile, &scope)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn set_default_codex_profile(
+    name: Option<String>,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let name = name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    CodexAdapter.set_default_profile(name, &scope_from(project_path))
+}
+
+/// Base settings merged with `profile` (or the default profile).
+#[tauri::command]
+pub fn get_codex_effective_config(
+    profile: Option<String>,
+    project_path: Option<String>,
+) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let profile = profile.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    CodexAdapter.effective_config(profile, &scope_from(project_path))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodexTrustEntry {
+    pub path: String,
+    /// The registered project at this path, if any.
+    pub project_id: Option<i64>,
+    pub trust_level: Option<TrustLevel>,
+    /// False for trust entries left behind by deleted or moved directories.
+    pub exists: bool,
+}
+
+/// Codex keys trust by the resolved directory path.
+fn trust_key(path: &str) -> String {
+    let path = Path::new(path.trim());
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Registered projects with their Codex trust level, followed by trust
+/// entries for directories that are not registered.
+#[tauri::command]
+pub fn get_codex_trust(db: State<'_, DbState>) -> Result<Vec<CodexTrustEntry>, String> {
+    let projects = {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        ProjectRepo::new(&conn).list()?
+    };
+    let mut levels = CodexAdapter.trust_levels()?;
+    let mut entries: Vec<CodexTrustEntry> = projects
+        .into_iter()
+        .map(|p| {
+            let key = trust_key(&p.path);
+            CodexTrustEntry {
+                trust_level: levels.remove(&key),
+                exists: Path::new(&key).is_dir(),
+                project_id: Some(p.id),
+                path: key,
+            }
+        })
+        .collect();
+    entries.extend(levels.into_iter().map(|(path, level)| CodexTrustEntry {
+        exists: Path::new(&path).is_dir(),
+        project_id: None,
+        trust_level: Some(level),
+        path,
+    }));
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn set_codex_trust(path: String, trust_level: Option<TrustLevel>) -> Result<(), String> {
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".into());
+    }
+    CodexAdapter.set_trust_levels(&[(trust_key(&path), trust_level)])
+}
+
+/// Marks every registered project that still exists as trusted. Returns the
+/// paths that changed.
+#[tauri::command]
+pub fn trust_all_projects(db: State<'_, DbState>) -> Result<Vec<String>, String> {
+    let projects = {
+        let conn = db.0.lock().map_err(|_| "DB lock poisoned")?;
+        ProjectRepo::new(&conn).list()?
+    };
+    let levels = CodexAdapter.trust_levels()?;
+    let changed: Vec<String> = projects
+        .iter()
+        .map(|p| trust_key(&p.path))
+        .filter(|key| Path::new(key).is_dir() && levels.get(key) != Some(&TrustLevel::Trusted))
+        .collect();
+    if !changed.is_empty() {
+        let changes: Vec<_> =
+            changed.iter().map(|k| (k.clone(), Some(TrustLevel::Trusted))).collect();
+        CodexAdapter.set_trust_levels(&changes)?;
+    }
+    Ok(changed)
+}
+
+#[tauri::command]
+pub fn get_claude_plugins(project_path: Option<String>) -> ClaudePlugins {
+    ClaudeAdapter.read_plugins(project_dir(&project_path))
+}
+
+#[tauri::command]
+pub fn set_claude_plugin_enabled(
+    id: String,
+    enabled: bool,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    ClaudeAdapter.set_plugin_enabled(id.trim(), enabled, project_dir(&project_path))
+}
+
+#[tauri::command]
+pub fn get_gemini_extensions() -> Result<Vec<GeminiExtension>, String> {
+    GeminiAdapter::gemini().read_extensions()
+}
+
+#[tauri::command]
+pub fn set_gemini_extension_enabled(name: String, enabled: bool) -> Result<(), String> {
+    GeminiAdapter::gemini().set_extension_enabled(&name, enabled)
+}
+
+/// Installs the extension in a local folder by copying it.
+#[tauri::command]
+pub fn install_gemini_extension(source: String) -> Result<GeminiExtension, String> {
+    let source = PathBuf::from(source.trim());
+    if !source.is_dir() {
+        return Err(format!("Not a directory: {}", source.display()));
+    }
+    GeminiAdapter::gemini().install_extension(&source)
+}
+
+#[tauri::command]
+pub fn uninstall_gemini_extension(name: String) -> Result<(), String> {
+    GeminiAdapter::gemini().uninstall_extension(&name)
+}
+
+#[tauri::command]
+pub fn get_model_settings(
+    tool: ToolType,
+    project_path: Option<String>,
+) -> Result<ModelSettings, String> {
+    adapter_for(&tool)?.read_model_settings(&scope_from(project_path))
+}
+
+#[tauri::command]
+pub fn save_model_settings(
+    tool: ToolType,
+    settings: ModelSettings,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings;
+    for value in [&mut settings.model, &mut settings.provider] {
+        *value = value.take().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    }
+    adapter_for(&tool)?.write_model_settings(&settings, &scope_from(project_path))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelSummary {
+    pub tool: ToolType,
+    pub model: Option<String>,
+    pub provider: Option<String>,
+    /// The project sets no model, so the global one applies.
+    pub inherited: bool,
+    pub error: Option<String>,
+}
+
+/// The model each tool detected in the project uses, falling back to the
+/// global setting when the project has none.
+#[tauri::command]
+pub fn get_project_model_summary(path: String) -> Result<Vec<ModelSummary>, String> {
+    let p = Path::new(path.trim());
+    if !p.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let scope = ConfigScope::Project(p.to_path_buf());
+    let mut summaries = Vec::new();
+    for tool in detect_tools(p) {
+        let adapter = adapter_for(&tool)?;
+        let mut summary = ModelSummary {
+            tool,
+            model: None,
+            provider: None,
+            inherited: false,
+            error: None,
+        };
+        let read = adapter.read_model_settings(&scope).and_then(|s| {
+            if s.model.is_some() {
+                return Ok(s);
+            }
+            summary.inherited = true;
+            adapter.read_model_settings(&ConfigScope::Global)
+        });
+        match read {
+            Ok(s) => {
+                summary.inherited &= s.model.is_some();
+                summary.model = s.model;
+                summary.provider = s.provider;
+            }
+            Err(e) => summary.error = Some(e),
+        }
+        summaries.push(summary);
+    }
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub fn list_tools() -> Vec<ToolDescriptor> {
+    tools()
+}
+
+#[tauri::command]
+pub fn get_tool_capabilities(tool: ToolType) -> Result<ToolCapabilities, String> {
+    find_tool(&tool)
+        .map(|t| t.capabilities)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))
+}
+
+#[tauri::command]
+pub fn list_custom_tools() -> Vec<ToolManifest> {
+    manifests()
+}
+
+#[tauri::command]
+pub fn reload_custom_tools(watcher: State<'_, FileWatcher>) -> Result<ManifestLoadReport, String> {
+    let report = reload_manifests();
+    watcher.reload_tools()?;
+    Ok(report)
+}
+
 pub fn register_commands() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
     tauri::generate_handler![
         get_mcp_servers,
         save_mcp_server,
         delete_mcp_server,
+        rename_mcp_server,
         get_skills,
         save_skill,
         delete_skill,
+        rename_skill,
         get_rules,
         save_rules,
+        get_commands,
+        save_command,
+        delete_command,
         list_projects,
         add_project,
         remove_project,
+        list_skill_sources,
+        add_skill_source,
+        remove_skill_source,
+        list_source_skills,
+        install_source_skills,
+        list_installed_skills,
+        check_skill_updates,
+        preview_skill_update,
+        update_installed_skill,
         copy_mcp_to_tool,
+        resolve_effective_mcp,
         copy_skill_to_tool,
+        copy_command_to_tool,
+        get_agents,
+        save_agent,
+        delete_agent,
+        copy_agent,
+        get_hooks,
+        save_hooks,
+        validate_hook_config,
+        test_hook,
+        get_claude_settings,
+        save_claude_setting,
+        gitignore_local_settings,
+        get_permissions,
+        save_permissions,
+        compare_permissions,
+        copy_permissions,
+        link_skill,
+        unlink_skill,
+        get_skill_link_states,
+        list_library_skills,
+        lint_skills,
+        export_skills,
+        preview_skill_archive,
+        import_skill_archive,
         detect_project_tools,
-        get_project_config_summary
+        get_project_config_summary,
+        get_model_settings,
+        save_model_settings,
+        get_project_model_summary,
+        get_codex_profiles,
+        save_codex_profile,
+        delete_codex_profile,
+        clone_codex_profile,
+        set_default_codex_profile,
+        get_codex_effective_config,
+        get_codex_trust,
+        set_codex_trust,
+        trust_all_projects,
+        get_claude_plugins,
+        set_claude_plugin_enabled,
+        get_gemini_extensions,
+        set_gemini_extension_enabled,
+        install_gemini_extension,
+        uninstall_gemini_extension,
+        list_tools,
+        get_tool_capabilities,
+        list_custom_tools,
+        reload_custom_tools
     ]
 }
@@ -1,16 +1,41 @@
-use crate::core::{ConfigScope, McpServer, Skill};
+use crate::core::{Command, ConfigScope, McpServer, ModelSettings, Permissions, Skill, SkillScan};
 use std::path::PathBuf;
 
 pub trait ConfigAdapter {
-    fn tool_name(&self) -> &'static str;
+    fn tool_name(&self) -> &str;
     fn global_config_path(&self) -> PathBuf;
     fn project_config_path(&self, project: &PathBuf) -> PathBuf;
     fn read_mcp_servers(&self, scope: &ConfigScope) -> Result<Vec<McpServer>, String>;
     fn write_mcp_server(&self, server: &McpServer, scope: &ConfigScope) -> Result<(), String>;
     fn delete_mcp_server(&self, name: &str, scope: &ConfigScope) -> Result<(), String>;
-    fn read_skills(&self, scope: &ConfigScope) -> Result<Vec<Skill>, String>;
+    /// Moves the entry `old` to `new`, keeping fields the hub does not model.
+    fn rename_mcp_server(&self, old: &str, new: &str, scope: &ConfigScope) -> Result<(), String>;
+    /// File holding the MCP servers of `scope`.
+    fn mcp_config_path(&self, scope: &ConfigScope) -> Option<PathBuf>;
+    /// Valid skills plus diagnostics for folders that failed to load.
+    fn read_skills(&self, scope: &ConfigScope) -> Result<SkillScan, String>;
     fn write_skill(&self, skill: &Skill, scope: &ConfigScope) -> Result<(), String>;
     fn delete_skill(&self, name: &str, scope: &ConfigScope) -> Result<(), String>;
+    /// Folder a skill named `name` lives in (or would be written to).
+    fn skill_dir(&self, name: &str, scope: &ConfigScope) -> Result<PathBuf, String>;
+    /// Slash commands, sorted by name. Tools without commands in `scope`
+    /// return an empty list.
+    fn read_commands(&self, scope: &ConfigScope) -> Result<Vec<Command>, String>;
+    fn write_command(&self, command: &Command, scope: &ConfigScope) -> Result<(), String>;
+    fn delete_command(&self, name: &str, scope: &ConfigScope) -> Result<(), String>;
+    /// Folder of command files, for tools that keep one file per command.
+    fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf>;
+    /// Permission settings in the normalized shape; empty when unsupported.
+    fn read_permissions(&self, scope: &ConfigScope) -> Result<Permissions, String>;
+    /// Replaces the permission settings, keeping unrelated keys.
+    fn write_permissions(&self, permissions: &Permissions, scope: &ConfigScope)
+        -> Result<(), String>;
+    /// Model and provider settings; empty when unsupported.
+    fn read_model_settings(&self, scope: &ConfigScope) -> Result<ModelSettings, String>;
+    /// Replaces the model settings (including the provider list), keeping
+    /// unrelated keys.
+    fn write_model_settings(&self, settings: &ModelSettings, scope: &ConfigScope)
+        -> Result<(), String>;
     fn read_rules(&self, scope: &ConfigScope) -> Result<String, String>;
     fn write_rules(&self, content: &str, scope: &ConfigScope) -> Result<(), String>;
 }
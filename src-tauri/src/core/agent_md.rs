@@ -0,0 +1,195 @@
+//! Claude Code subagent files: `agents/<name>.md` with YAML frontmatter.
+
+use super::skill_md::{split_frontmatter, take};
+use crate::core::{check_skill_name, Agent, AgentScan, AllowedTools, SkillDiagnostic};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Built-in tools an agent may list. MCP tools (`mcp__server__tool`) are
+/// accepted as well.
+pub const AGENT_TOOLS: &[&str] = &[
+    "Agent",
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "Skill",
+    "SlashCommand",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+pub fn parse_agent_md(content: &str, path: PathBuf) -> Result<Agent, SkillDiagnostic> {
+    let diag = |reason: String, line: Option<usize>| SkillDiagnostic {
+        path: path.clone(),
+        reason,
+        line,
+    };
+    let fm = split_frontmatter(content).map_err(|e| diag(e, None))?;
+    let value: Value = serde_yaml::from_str(&fm.yaml).map_err(|e| {
+        let line = e.location().map(|l| fm.line + l.line());
+        diag(format!("Invalid YAML frontmatter: {}", e), line)
+    })?;
+    let mut map = match value {
+        Value::Mapping(m) => m,
+        Value::Null => Mapping::new(),
+        _ => return Err(diag("Frontmatter must be a mapping".into(), Some(fm.line + 1))),
+    };
+    let name = match map.get("name") {
+        None => return Err(diag("Missing `name` in frontmatter".into(), None)),
+        Some(Value::String(_)) => take(&mut map, "name").unwrap_or_default(),
+        Some(_) => return Err(diag("`name` must be a string".into(), None)),
+    };
+    Ok(Agent {
+        name,
+        description: take(&mut map, "description"),
+        tools: take(&mut map, "tools"),
+        model: take(&mut map, "model"),
+        content: fm.body.to_string(),
+        path,
+        extra: map,
+    })
+}
+
+/// Renders an agent back to Markdown. Known keys come first, then the
+/// remaining keys in their original order.
+pub fn render_agent_md(agent: &Agent) -> Result<String, String> {
+    let mut map = Mapping::new();
+    let mut put = |key: &str, value: Option<Value>| {
+        if let Some(v) = value {
+            map.insert(Value::from(key), v);
+        }
+    };
+    put("name", Some(Value::from(agent.name.as_str())));
+    put("description", agent.description.as_deref().map(Value::from));
+    put(
+        "tools",
+        agent.tools.as_ref().map(|t| match t {
+            AllowedTools::Text(s) => Value::from(s.as_str()),
+            AllowedTools::List(l) => Value::from(l.clone()),
+        }),
+    );
+    put("model", agent.model.as_deref().map(Value::from));
+    for (k, v) in &agent.extra {
+        if !map.contains_key(k) {
+            map.insert(k.clone(), v.clone());
+        }
+    }
+
+    let yaml = serde_yaml::to_string(&map)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    Ok(format!("---\n{}---\n\n{}", yaml.trim_start_matches("---\n"), agent.content))
+}
+
+/// Loads every `<dir>/*.md`. Files that fail to parse are reported as
+/// diagnostics.
+pub fn scan_agents_dir(dir: &Path) -> Result<AgentScan, String> {
+    let mut scan = AgentScan::default();
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(scan),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if hidden || !path.is_file() || path.extension().is_none_or(|e| e != "md") {
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(content) => match parse_agent_md(&content, path) {
+                Ok(agent) => scan.agents.push(agent),
+                Err(d) => scan.diagnostics.push(d),
+            },
+            Err(e) => scan.diagnostics.push(SkillDiagnostic {
+                reason: format!("Failed to read file: {}", e),
+                path,
+                line: None,
+            }),
+        }
+    }
+    scan.agents.sort_by(|a, b| a.name.cmp(&b.name));
+    scan.diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(scan)
+}
+
+fn is_known_tool(tool: &str) -> bool {
+    // `Bash(git:*)` style rules narrow a tool; only the name is checked.
+    let name = tool.split('(').next().unwrap_or(tool).trim();
+    AGENT_TOOLS.contains(&name) || (name.starts_with("mcp__") && name.len() > 5)
+}
+
+/// Fails only on a name that can't be a file name. Everything else comes
+/// back as warnings: Claude Code adds tools faster than [`AGENT_TOOLS`] is
+/// updated, and a file with gaps is still worth saving.
+pub fn validate_agent(agent: &Agent) -> Result<Vec<String>, String> {
+    if let Some(msg) = check_skill_name(&agent.name) {
+        return Err(msg);
+    }
+    let mut warnings = Vec::new();
+    if agent.description.as_deref().is_none_or(|d| d.trim().is_empty()) {
+        warnings.push("No description; Claude Code uses it to decide when to delegate".into());
+    }
+    let unknown: Vec<_> = agent.tool_names().into_iter().filter(|t| !is_known_tool(t)).collect();
+    if !unknown.is_empty() {
+        warnings.push(format!("Unknown tools: {}", unknown.join(", ")));
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = "---\nname: reviewer\ndescription: Reviews code\ntools: Read, Grep, Bash(git diff:*)\nmodel: sonnet\ncolor: blue\n---\n\nYou review code.\n";
+
+    #[test]
+    fn round_trip_is_stable() {
+        let agent = parse_agent_md(FULL, PathBuf::from("/a/reviewer.md")).unwrap();
+        assert_eq!(agent.tool_names(), vec!["Read", "Grep", "Bash(git diff:*)"]);
+        assert_eq!(agent.model.as_deref(), Some("sonnet"));
+        assert_eq!(agent.extra.len(), 1);
+        assert_eq!(render_agent_md(&agent).unwrap(), FULL);
+    }
+
+    #[test]
+    fn validates_tools_and_name() {
+        let mut agent = parse_agent_md(FULL, PathBuf::new()).unwrap();
+        assert!(validate_agent(&agent).unwrap().is_empty());
+
+        agent.tools = Some(AllowedTools::Text(
+            "Read, mcp__github__search, mcp__github__*, mcp__linear".into(),
+        ));
+        assert!(validate_agent(&agent).unwrap().is_empty());
+
+        agent.tools = Some(AllowedTools::Text("Read, Teleport, mcp__".into()));
+        agent.description = None;
+        let warnings = validate_agent(&agent).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1], "Unknown tools: Teleport, mcp__");
+
+        agent.tools = None;
+        agent.name = "Bad Name".into();
+        assert!(validate_agent(&agent).is_err());
+    }
+
+    #[test]
+    fn missing_name_is_a_diagnostic() {
+        let err = parse_agent_md("---\ndescription: x\n---\nbody", PathBuf::from("/a/x.md"))
+            .unwrap_err();
+        assert_eq!(err.path, PathBuf::from("/a/x.md"));
+        assert!(err.reason.contains("name"));
+    }
+}
@@ -0,0 +1,104 @@
+//! What each tool's config can express. The UI uses this to grey out fields
+//! and the converter to warn before dropping data.
+
+use crate::core::{ApprovalPolicy, ConfigScope, SandboxMode};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    Stdio,
+    Http,
+    Sse,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct McpCapabilities {
+    pub transports: Vec<Transport>,
+    /// Servers can be disabled without removing them.
+    pub enabled: bool,
+    /// `enabled` only applies to project servers.
+    pub enabled_project_only: bool,
+    /// Custom HTTP headers for remote servers.
+    pub headers: bool,
+    /// Per-server startup or call timeouts.
+    pub timeouts: bool,
+    pub global: bool,
+    pub project: bool,
+}
+
+impl McpCapabilities {
+    pub fn local(&self) -> bool {
+        self.transports.contains(&Transport::Stdio)
+    }
+
+    pub fn remote(&self) -> bool {
+        self.transports.iter().any(|t| matches!(t, Transport::Http | Transport::Sse))
+    }
+
+    pub fn can_disable(&self, scope: &ConfigScope) -> bool {
+        self.enabled && !(self.enabled_project_only && *scope == ConfigScope::Global)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SkillCapabilities {
+    pub global: bool,
+    pub project: bool,
+    /// Files next to `SKILL.md` (scripts, references, assets) are used.
+    pub resources: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct RulesCapabilities {
+    pub global: bool,
+    pub project: bool,
+    /// `@path` imports inside the rules file are expanded.
+    pub imports: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CommandCapabilities {
+    pub global: bool,
+    pub project: bool,
+    /// Placeholder replaced by the user's arguments, e.g. `$ARGUMENTS`.
+    pub arguments: &'static str,
+    /// `$1`, `$2`, ... refer to single arguments.
+    pub positional: bool,
+    pub argument_hint: bool,
+    pub model: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ModelCapabilities {
+    pub global: bool,
+    pub project: bool,
+    /// The model can name the provider serving it.
+    pub provider: bool,
+    /// Custom providers can be defined.
+    pub providers: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PermissionCapabilities {
+    pub global: bool,
+    pub project: bool,
+    /// `allow` and `deny` rules.
+    pub rules: bool,
+    /// `ask` rules.
+    pub ask: bool,
+    /// Tools rules can name, in rule syntax; empty means any tool.
+    pub rule_tools: &'static [&'static str],
+    pub approvals: &'static [ApprovalPolicy],
+    pub sandbox_modes: &'static [SandboxMode],
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ToolCapabilities {
+    pub mcp: McpCapabilities,
+    pub skills: SkillCapabilities,
+    pub rules: RulesCapabilities,
+    pub commands: CommandCapabilities,
+    pub permissions: PermissionCapabilities,
+    pub models: ModelCapabilities,
+}
@@ -0,0 +1,321 @@
+//! Claude Code plugins under `~/.claude/plugins`: `installed_plugins.json`
+//! points at each plugin's folder, whose `.claude-plugin/plugin.json` and
+//! default component folders say what it contributes.
+
+use crate::core::{scan_skills_dir, ClaudeMarketplace, ClaudePlugin, McpServer};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PLUGIN_MANIFEST: &str = ".claude-plugin/plugin.json";
+
+fn read_json(path: &Path) -> Result<Option<Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(c) => serde_json::from_str(&c)
+            .map(Some)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn string_at(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Marketplaces in `known_marketplaces.json`, sorted by name.
+pub fn read_marketplaces(plugins_dir: &Path) -> Result<Vec<ClaudeMarketplace>, String> {
+    let Some(Value::Object(entries)) = read_json(&plugins_dir.join("known_marketplaces.json"))?
+    else {
+        return Ok(vec![]);
+    };
+    let mut marketplaces: Vec<ClaudeMarketplace> = entries
+        .into_iter()
+        .map(|(name, entry)| {
+            let source = entry.get("source");
+            ClaudeMarketplace {
+                name,
+                source: source.and_then(|s| {
+                    ["repo", "url", "path"].iter().find_map(|k| string_at(s, k))
+                }),
+                path: string_at(&entry, "installLocation").map(PathBuf::from),
+            }
+        })
+        .collect();
+    marketplaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(marketplaces)
+}
+
+fn md_names(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "md"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// An inline object, or a path relative to the plugin root; `default` is
+/// used when the manifest names neither.
+fn component(root: &Path, manifest: &Value, key: &str, default: &str) -> Result<Value, String> {
+    let path = match manifest.get(key) {
+        Some(Value::Object(inline)) => return Ok(Value::Object(inline.clone())),
+        Some(Value::String(rel)) => root.join(rel),
+        _ => root.join(default),
+    };
+    Ok(read_json(&path)?.unwrap_or(Value::Null))
+}
+
+/// Servers under `mcpServers` in `config`, or in `config` itself.
+pub(super) fn mcp_servers(config: &Value) -> Vec<McpServer> {
+    let servers = config.get("mcpServers").unwrap_or(config);
+    let Some(servers) = servers.as_object() else {
+        return vec![];
+    };
+    servers
+        .iter()
+        .filter(|(_, entry)| entry.is_object())
+        .map(|(name, entry)| McpServer {
+            name: name.clone(),
+            command: string_at(entry, "command").unwrap_or_default(),
+            args: serde_json::from_value(entry.get("args").cloned().unwrap_or_default())
+                .unwrap_or_default(),
+            env: serde_json::from_value(entry.get("env").cloned().unwrap_or_default())
+                .unwrap_or_default(),
+            url: string_at(entry, "url"),
+            enabled: true,
+        })
+        .collect()
+}
+
+/// Replaces `${CLAUDE_PLUGIN_ROOT}`, which plugin configs use to point at
+/// their own files.
+fn expand_root(server: &mut McpServer, root: &Path) {
+    let root = root.to_string_lossy();
+    let expand = |s: &mut String| *s = s.replace("${CLAUDE_PLUGIN_ROOT}", &root);
+    expand(&mut server.command);
+    server.args.iter_mut().for_each(expand);
+    server.env.values_mut().for_each(expand);
+    if let Some(url) = server.url.as_mut() {
+        expand(url);
+    }
+}
+
+fn read_plugin(plugin: &mut ClaudePlugin) -> Result<(), String> {
+    let root = plugin.path.clone();
+    if !root.is_dir() {
+        return Err(format!("Plugin folder not found: {}", root.display()));
+    }
+    let manifest = read_json(&root.join(PLUGIN_MANIFEST))?.unwrap_or(Value::Null);
+    if let Some(name) = string_at(&manifest, "name") {
+        plugin.name = name;
+    }
+    plugin.version = string_at(&manifest, "version").or(plugin.version.take());
+    plugin.description = string_at(&manifest, "description");
+
+    plugin.mcp_servers = mcp_servers(&component(&root, &manifest, "mcpServers", ".mcp.json")?);
+    plugin.mcp_servers.iter_mut().for_each(|s| expand_root(s, &root));
+    let hooks = component(&root, &manifest, "hooks", "hooks/hooks.json")?;
+    plugin.hooks = hooks
+        .get("hooks")
+        .and_then(|h| h.as_object())
+        .map(|events| events.keys().cloned().collect())
+        .unwrap_or_default();
+    plugin.skills = scan_skills_dir(&root.join("skills"))?.skills;
+    plugin.commands = md_names(&root.join("commands"));
+    plugin.agents = md_names(&root.join("agents"));
+    Ok(())
+}
+
+/// The install that applies: one made for `project` if there is one,
+/// otherwise the user-wide install.
+fn pick_install(entry: &Value, project: Option<&Path>) -> Option<Value> {
+    let Value::Array(installs) = entry else {
+        return Some(entry.clone());
+    };
+    let for_project = |i: &&Value| {
+        project.is_some_and(|p| string_at(i, "projectPath").is_some_and(|pp| Path::new(&pp) == p))
+    };
+    let user_wide = |i: &&Value| string_at(i, "scope").is_none_or(|s| s == "user");
+    installs.iter().find(for_project).or_else(|| installs.iter().find(user_wide)).cloned()
+}
+
+/// Plugins in `installed_plugins.json` available for the user or, given
+/// `project`, in it, sorted by id. `enabled` is the merged `enabledPlugins`
+/// setting; plugins missing from it are off. A plugin whose files cannot be
+/// read is reported on its entry.
+pub fn read_plugins(
+    plugins_dir: &Path,
+    enabled: &Map<String, Value>,
+    project: Option<&Path>,
+) -> Result<Vec<ClaudePlugin>, String> {
+    let installed = read_json(&plugins_dir.join("installed_plugins.json"))?;
+    let entries = installed.as_ref().and_then(|i| i.get("plugins"));
+    let Some(entries) = entries.and_then(|p| p.as_object()) else {
+        return Ok(vec![]);
+    };
+    let mut plugins = Vec::new();
+    for (id, entry) in entries {
+        // Newer files keep a list of installs per plugin, one per scope.
+        let Some(entry) = pick_install(entry, project) else {
+            continue;
+        };
+        let (name, marketplace) = match id.split_once('@') {
+            Some((name, marketplace)) => (name.to_string(), Some(marketplace.to_string())),
+            None => (id.clone(), None),
+        };
+        let mut plugin = ClaudePlugin {
+            id: id.clone(),
+            name,
+            marketplace,
+            version: string_at(&entry, "version"),
+            description: None,
+            path: string_at(&entry, "installPath").map(PathBuf::from).unwrap_or_default(),
+            enabled: enabled.get(id).and_then(|v| v.as_bool()) == Some(true),
+            mcp_servers: vec![],
+            skills: vec![],
+            commands: vec![],
+            agents: vec![],
+            hooks: vec![],
+            error: None,
+        };
+        if let Err(e) = read_plugin(&mut plugin) {
+            plugin.error = Some(e);
+        }
+        plugins.push(plugin);
+    }
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn reads_plugins_and_their_contributions() {
+        let tmp = TempDir::new("read");
+        let dir = tmp.path();
+        let plugin = dir.join("marketplaces/acme/plugins/review");
+        write(
+            &plugin.join(PLUGIN_MANIFEST),
+            r#"{"name":"review","version":"2.0.0","description":"Code review",
+                "mcpServers":{"github":{"command":"gh-mcp","args":["--stdio"]}}}"#,
+        );
+        write(&plugin.join("commands/review.md"), "Review the diff");
+        write(&plugin.join("agents/critic.md"), "---\nname: critic\n---\nBe harsh");
+        write(
+            &plugin.join("skills/triage/SKILL.md"),
+            "---\nname: triage\ndescription: Triage issues\n---\nSteps",
+        );
+        write(
+            &plugin.join("hooks/hooks.json"),
+            r#"{"hooks":{"PostToolUse":[],"Stop":[]}}"#,
+        );
+        write(
+            &dir.join("installed_plugins.json"),
+            &format!(
+                r#"{{"version":2,"plugins":{{
+                    "review@acme":[{{"scope":"user","installPath":{:?},"version":"1.0.0"}}],
+                    "gone@acme":{{"installPath":"/nonexistent/plugin"}}}}}}"#,
+                plugin.to_string_lossy()
+            ),
+        );
+        write(
+            &dir.join("known_marketplaces.json"),
+            r#"{"acme":{"source":{"source":"github","repo":"acme/plugins"},
+                "installLocation":"/tmp/acme"}}"#,
+        );
+
+        let enabled: Map<String, Value> =
+            serde_json::from_str(r#"{"review@acme":true,"gone@acme":false}"#).unwrap();
+        let plugins = read_plugins(dir, &enabled, None).unwrap();
+        assert_eq!(plugins.len(), 2);
+        assert!(plugins[0].error.is_some() && !plugins[0].enabled);
+
+        let review = &plugins[1];
+        assert!(review.enabled && review.error.is_none());
+        assert_eq!(review.marketplace.as_deref(), Some("acme"));
+        assert_eq!(review.version.as_deref(), Some("2.0.0"));
+        assert_eq!(review.mcp_servers[0].command, "gh-mcp");
+        assert_eq!(review.skills[0].name, "triage");
+        assert_eq!(review.commands, vec!["review"]);
+        assert_eq!(review.agents, vec!["critic"]);
+        assert_eq!(review.hooks, vec!["PostToolUse", "Stop"]);
+
+        let marketplaces = read_marketplaces(dir).unwrap();
+        assert_eq!(marketplaces[0].source.as_deref(), Some("acme/plugins"));
+        assert_eq!(marketplaces[0].path, Some(PathBuf::from("/tmp/acme")));
+    }
+
+    #[test]
+    fn falls_back_to_default_files() {
+        let tmp = TempDir::new("defaults");
+        let dir = tmp.path();
+        let plugin = dir.join("local");
+        write(&plugin.join(".mcp.json"), r#"{"mcpServers":{"db":{"command":"db-mcp"}}}"#);
+        write(
+            &dir.join("installed_plugins.json"),
+            &format!(
+                r#"{{"version":1,"plugins":{{"local@dev":{{"installPath":{:?}}}}}}}"#,
+                plugin.to_string_lossy()
+            ),
+        );
+
+        let plugins = read_plugins(dir, &Map::new(), None).unwrap();
+        assert_eq!(plugins[0].name, "local");
+        assert!(!plugins[0].enabled);
+        assert_eq!(plugins[0].mcp_servers[0].name, "db");
+        assert!(read_plugins(&dir.join("missing"), &Map::new(), None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn picks_the_install_for_the_project() {
+        let tmp = TempDir::new("installs");
+        let dir = tmp.path();
+        let (user, pinned) = (dir.join("cache/user"), dir.join("cache/pinned"));
+        for root in [&user, &pinned] {
+            write(
+                &root.join(".mcp.json"),
+                r#"{"srv":{"command":"${CLAUDE_PLUGIN_ROOT}/bin/srv"}}"#,
+            );
+        }
+        write(
+            &dir.join("installed_plugins.json"),
+            &format!(
+                r#"{{"version":2,"plugins":{{
+                    "tool@acme":[
+                        {{"scope":"project","projectPath":"/work/app","installPath":{:?}}},
+                        {{"scope":"user","installPath":{:?}}}],
+                    "other@acme":[{{"scope":"local","projectPath":"/elsewhere","installPath":"/x"}}]
+                }}}}"#,
+                pinned.to_string_lossy(),
+                user.to_string_lossy()
+            ),
+        );
+
+        let here = read_plugins(dir, &Map::new(), Some(Path::new("/work/app"))).unwrap();
+        assert_eq!(here.len(), 1);
+        assert_eq!(here[0].path, pinned);
+        let command = format!("{}/bin/srv", pinned.display());
+        assert_eq!(here[0].mcp_servers[0].command, command);
+
+        let global = read_plugins(dir, &Map::new(), None).unwrap();
+        assert_eq!(global.len(), 1);
+        assert_eq!(global[0].path, user);
+
+        fs::write(dir.join("installed_plugins.json"), "{").unwrap();
+        assert!(read_plugins(dir, &Map::new(), None).is_err());
+    }
+}
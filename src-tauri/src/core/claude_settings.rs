@@ -0,0 +1,225 @@
+//! Claude Code's layered `settings.json`: user, project, local and managed
+//! files merged in that order. Also the MCP servers `claude mcp add` keeps in
+//! `~/.claude.json`.
+
+use super::claude_plugins::mcp_servers;
+use crate::core::{EffectiveSetting, McpServer, SettingsLayer};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `.gitignore` entry for the personal settings file.
+pub const LOCAL_SETTINGS_ENTRY: &str = ".claude/settings.local.json";
+
+/// Where enterprise policy puts `managed-settings.json`.
+pub fn managed_settings_path() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json")
+    } else if cfg!(windows) {
+        PathBuf::from(r"C:\ProgramData\ClaudeCode\managed-settings.json")
+    } else {
+        PathBuf::from("/etc/claude-code/managed-settings.json")
+    }
+}
+
+/// Claude Code's own state file, which holds user- and local-scope servers.
+pub fn claude_json_path() -> PathBuf {
+    dirs::home_dir().unwrap_or_default().join(".claude.json")
+}
+
+/// Servers in `~/.claude.json` at `path`: the user scope at the top level or,
+/// given `project`, the local scope under `projects.<project path>`.
+pub fn claude_json_mcp_servers(
+    path: &Path,
+    project: Option<&Path>,
+) -> Result<Vec<McpServer>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let config: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+    let scope = match project {
+        None => Some(&config),
+        Some(project) => config
+            .get("projects")
+            .and_then(|p| p.get(project.to_string_lossy().trim_end_matches(['/', '\\']))),
+    };
+    let Some(servers) = scope.and_then(|s| s.get("mcpServers")) else {
+        return Ok(vec![]);
+    };
+    let mut servers = mcp_servers(servers);
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(servers)
+}
+
+/// Overlays `top` on `base`: objects merge key by key, arrays are combined
+/// without duplicates (as with permission rules) and anything else is
+/// replaced.
+fn merge_value(base: &mut Value, top: &Value) {
+    match (base, top) {
+        (Value::Object(b), Value::Object(t)) => {
+            for (key, value) in t {
+                match b.get_mut(key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        b.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (Value::Array(b), Value::Array(t)) => {
+            for item in t {
+                if !b.contains(item) {
+                    b.push(item.clone());
+                }
+            }
+        }
+        (base, top) => *base = top.clone(),
+    }
+}
+
+/// Merges the layers, which must be ordered lowest precedence first, and
+/// records where each top-level key comes from.
+pub fn merge_settings(layers: &[(SettingsLayer, &Map<String, Value>)]) -> Vec<EffectiveSetting> {
+    let mut merged: Vec<EffectiveSetting> = Vec::new();
+    for (layer, settings) in layers {
+        for (key, value) in settings.iter() {
+            match merged.iter_mut().find(|s| &s.key == key) {
+                Some(setting) => {
+                    merge_value(&mut setting.value, value);
+                    setting.source = *layer;
+                    setting.layers.push(*layer);
+                }
+                None => merged.push(EffectiveSetting {
+                    key: key.clone(),
+                    value: value.clone(),
+                    source: *layer,
+                    layers: vec![*layer],
+                }),
+            }
+        }
+    }
+    merged.sort_by(|a, b| a.key.cmp(&b.key));
+    merged
+}
+
+fn ignores(line: &str, entry: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix('/').unwrap_or(line);
+    let file = entry.rsplit('/').next().unwrap_or(entry);
+    let dir = entry.split('/').next().unwrap_or(entry);
+    line == entry
+        || line == file
+        || line == format!("**/{}", file)
+        || line.trim_end_matches('/') == dir
+        || line == "*.local.json"
+}
+
+/// Whether the project's top-level `.gitignore` has a line covering `entry`.
+/// Only plain patterns are recognised; negations and nested ignore files
+/// are not considered.
+pub fn is_gitignored(project: &Path, entry: &str) -> bool {
+    fs::read_to_string(project.join(".gitignore"))
+        .map(|content| content.lines().any(|l| ignores(l, entry)))
+        .unwrap_or(false)
+}
+
+/// Appends `entry` to the project's `.gitignore` unless it is already
+/// covered. Returns whether the file changed.
+pub fn add_to_gitignore(project: &Path, entry: &str) -> Result<bool, String> {
+    if is_gitignored(project, entry) {
+        return Ok(false);
+    }
+    let path = project.join(".gitignore");
+    let mut content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(entry);
+    content.push('\n');
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use serde_json::json;
+
+    fn map(v: Value) -> Map<String, Value> {
+        v.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn merges_with_provenance() {
+        let user = map(json!({
+            "model": "sonnet",
+            "permissions": {"allow": ["Read"]},
+            "env": {"A": "1"},
+        }));
+        let project = map(json!({
+            "permissions": {"allow": ["Bash(npm test:*)"], "deny": ["WebFetch"]},
+        }));
+        let local = map(json!({"model": "opus", "env": {"B": "2"}}));
+        let merged = merge_settings(&[
+            (SettingsLayer::User, &user),
+            (SettingsLayer::Project, &project),
+            (SettingsLayer::Local, &local),
+        ]);
+
+        let keys: Vec<_> = merged.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, vec!["env", "model", "permissions"]);
+        assert_eq!(merged[0].value, json!({"A": "1", "B": "2"}));
+        assert_eq!(merged[1].value, "opus");
+        assert_eq!(merged[1].source, SettingsLayer::Local);
+        assert_eq!(merged[1].layers, vec![SettingsLayer::User, SettingsLayer::Local]);
+        assert_eq!(
+            merged[2].value,
+            json!({"allow": ["Read", "Bash(npm test:*)"], "deny": ["WebFetch"]})
+        );
+        assert_eq!(merged[2].source, SettingsLayer::Project);
+    }
+
+    #[test]
+    fn gitignore_entry_added_once() {
+        let tmp = TempDir::new("claude-settings");
+        let dir = tmp.path();
+        fs::write(dir.join(".gitignore"), "node_modules").unwrap();
+
+        assert!(!is_gitignored(dir, LOCAL_SETTINGS_ENTRY));
+        assert!(add_to_gitignore(dir, LOCAL_SETTINGS_ENTRY).unwrap());
+        assert!(!add_to_gitignore(dir, LOCAL_SETTINGS_ENTRY).unwrap());
+        let content = fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert_eq!(content, "node_modules\n.claude/settings.local.json\n");
+
+        fs::write(dir.join(".gitignore"), "/.claude/\n").unwrap();
+        assert!(is_gitignored(dir, LOCAL_SETTINGS_ENTRY));
+    }
+
+    #[test]
+    fn reads_user_and_local_servers_from_claude_json() {
+        let tmp = TempDir::new("claude-json");
+        let path = tmp.path().join(".claude.json");
+        fs::write(
+            &path,
+            r#"{"mcpServers":{"fs":{"type":"stdio","command":"fs-mcp","args":["/"]}},
+                "projects":{"/work/app":{"mcpServers":{
+                    "api":{"type":"http","url":"http://localhost:3000/mcp"}}}}}"#,
+        )
+        .unwrap();
+
+        let user = claude_json_mcp_servers(&path, None).unwrap();
+        assert_eq!((user[0].name.as_str(), user[0].args.clone()), ("fs", vec!["/".to_string()]));
+        let local = claude_json_mcp_servers(&path, Some(Path::new("/work/app/"))).unwrap();
+        assert_eq!(local[0].url.as_deref(), Some("http://localhost:3000/mcp"));
+        assert!(claude_json_mcp_servers(&path, Some(Path::new("/other"))).unwrap().is_empty());
+        assert!(claude_json_mcp_servers(&tmp.path().join("none"), None).unwrap().is_empty());
+    }
+}
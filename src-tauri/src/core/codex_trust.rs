@@ -0,0 +1,150 @@
+//! Codex project trust: `[projects."<abs path>"] trust_level = "trusted"` in
+//! `config.toml`. Edits go through `toml_edit` so comments and layout survive.
+
+use crate::core::TrustLevel;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table};
+
+fn level_str(level: TrustLevel) -> &'static str {
+    match level {
+        TrustLevel::Trusted => "trusted",
+        TrustLevel::Untrusted => "untrusted",
+    }
+}
+
+fn load_document(path: &Path) -> Result<DocumentMut, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))
+}
+
+/// Trust levels by project path. Entries with an unknown level are skipped.
+pub fn read_trust_levels(path: &Path) -> Result<BTreeMap<String, TrustLevel>, String> {
+    let doc = load_document(path)?;
+    let Some(projects) = doc.get("projects").and_then(|p| p.as_table_like()) else {
+        return Ok(BTreeMap::new());
+    };
+    Ok(projects
+        .iter()
+        .filter_map(|(project, entry)| {
+            let level = match entry.get("trust_level")?.as_str()? {
+                "trusted" => TrustLevel::Trusted,
+                "untrusted" => TrustLevel::Untrusted,
+                _ => return None,
+            };
+            Some((project.to_string(), level))
+        })
+        .collect())
+}
+
+/// Sets (or with `None`, clears) the trust level of each project in one
+/// write. Other keys of a project entry are kept.
+pub fn write_trust_levels(
+    path: &Path,
+    changes: &[(String, Option<TrustLevel>)],
+) -> Result<(), String> {
+    let mut doc = load_document(path)?;
+    if !doc.contains_key("projects") {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        doc.insert("projects", Item::Table(table));
+    }
+    let projects = doc["projects"]
+        .as_table_like_mut()
+        .ok_or("`projects` in config.toml is not a table")?;
+    for (project, level) in changes {
+        match level {
+            Some(level) => {
+                if projects.get(project).is_none() {
+                    projects.insert(project, Item::Table(Table::new()));
+                }
+                let entry = projects
+                    .get_mut(project)
+                    .and_then(|e| e.as_table_like_mut())
+                    .ok_or_else(|| format!("Entry for {} is not a table", project))?;
+                entry.insert("trust_level", toml_edit::value(level_str(*level)));
+            }
+            None => {
+                let emptied = match projects.get_mut(project).and_then(|e| e.as_table_like_mut()) {
+                    Some(entry) => {
+                        entry.remove("trust_level");
+                        entry.is_empty()
+                    }
+                    None => false,
+                };
+                if emptied {
+                    projects.remove(project);
+                }
+            }
+        }
+    }
+    if projects.is_empty() {
+        doc.remove("projects");
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, doc.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::path::PathBuf;
+
+    fn temp_config(tmp: &TempDir, content: &str) -> PathBuf {
+        let path = tmp.path().join("config.toml");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn edits_keep_comments() {
+        let tmp = TempDir::new("codex-trust");
+        let path = temp_config(
+            &tmp,
+            concat!(
+                "# my settings\nmodel = \"o3\" # fast enough\n\n",
+                "[projects.\"/a\"]\ntrust_level = \"trusted\"\n",
+            ),
+        );
+        write_trust_levels(
+            &path,
+            &[("/b c".into(), Some(TrustLevel::Trusted)), ("/a".into(), None)],
+        )
+        .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# my settings\nmodel = \"o3\" # fast enough\n"));
+        assert!(content.contains("[projects.\"/b c\"]\ntrust_level = \"trusted\""));
+        assert!(!content.contains("\"/a\""));
+        assert!(!content.contains("[projects]\n"));
+
+        let levels = read_trust_levels(&path).unwrap();
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels["/b c"], TrustLevel::Trusted);
+    }
+
+    #[test]
+    fn clearing_keeps_other_project_keys() {
+        let tmp = TempDir::new("codex-trust");
+        let path = temp_config(
+            &tmp,
+            "[projects.\"/a\"]\ntrust_level = \"untrusted\"\nnote = \"x\"\n",
+        );
+        assert_eq!(read_trust_levels(&path).unwrap()["/a"], TrustLevel::Untrusted);
+        write_trust_levels(&path, &[("/a".into(), None)]).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("note = \"x\"") && !content.contains("trust_level"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Markdown prompt files with optional YAML frontmatter, as used by Claude
+//! Code commands and Codex prompts.
+
+use super::skill_md::{split_frontmatter, take};
+use crate::core::Command;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn has_frontmatter(content: &str) -> bool {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    content.trim_start().starts_with("---")
+}
+
+/// Frontmatter keys and body of a prompt file. A file without frontmatter is
+/// all body.
+fn split(content: &str) -> Result<(Mapping, String), String> {
+    if !has_frontmatter(content) {
+        return Ok((Mapping::new(), content.to_string()));
+    }
+    let fm = split_frontmatter(content)?;
+    let map = match serde_yaml::from_str(&fm.yaml)
+        .map_err(|e| format!("Invalid YAML frontmatter: {}", e))?
+    {
+        Value::Mapping(m) => m,
+        Value::Null => Mapping::new(),
+        _ => return Err("Frontmatter must be a mapping".into()),
+    };
+    Ok((map, fm.body.to_string()))
+}
+
+pub fn parse_command_md(name: &str, content: &str, path: PathBuf) -> Result<Command, String> {
+    let (mut map, body) = split(content)?;
+    Ok(Command {
+        name: name.to_string(),
+        description: take(&mut map, "description"),
+        argument_hint: take(&mut map, "argument-hint"),
+        model: take(&mut map, "model"),
+        content: body,
+        path,
+    })
+}
+
+/// Renders `cmd`, keeping frontmatter keys from `existing` that the hub does
+/// not model (e.g. `allowed-tools`).
+pub fn render_command_md(cmd: &Command, existing: Option<&str>) -> Result<String, String> {
+    let mut extra = existing
+        .and_then(|c| split(c).ok())
+        .map(|(map, _)| map)
+        .unwrap_or_default();
+    for key in ["description", "argument-hint", "model"] {
+        extra.shift_remove(key);
+    }
+
+    let mut map = Mapping::new();
+    let known = [
+        ("description", &cmd.description),
+        ("argument-hint", &cmd.argument_hint),
+        ("model", &cmd.model),
+    ];
+    for (key, value) in known {
+        if let Some(v) = value {
+            map.insert(Value::from(key), Value::from(v.as_str()));
+        }
+    }
+    map.extend(extra);
+
+    if map.is_empty() {
+        return Ok(cmd.content.clone());
+    }
+    let yaml = serde_yaml::to_string(&map)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    Ok(format!("---\n{}---\n\n{}", yaml.trim_start_matches("---\n"), cmd.content))
+}
+
+/// Loads every `<dir>/<name>.md`, sorted by name. Files that fail to parse
+/// are skipped.
+pub fn read_md_commands(dir: &Path) -> Result<Vec<Command>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut commands = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        if !path.is_file()
+            || name.starts_with('.')
+            || path.extension().is_none_or(|e| e != "md")
+        {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if let Ok(cmd) = parse_command_md(&name, &content, path) {
+            commands.push(cmd);
+        }
+    }
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+/// Writes `<dir>/<name>.md`. The caller validates the name.
+pub fn write_md_command(dir: &Path, cmd: &Command) -> Result<(), String> {
+    let path = dir.join(format!("{}.md", cmd.name));
+    let existing = fs::read_to_string(&path).ok();
+    let content = render_command_md(cmd, existing.as_deref())?;
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create dir {}: {}", dir.display(), e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn delete_md_command(dir: &Path, name: &str) -> Result<(), String> {
+    let path = dir.join(format!("{}.md", name));
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to delete {}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_frontmatter_and_plain_files() {
+        let src = "---\ndescription: Review code\nargument-hint: <file>\nallowed-tools: Read\n---\n\nReview $ARGUMENTS\n";
+        let cmd = parse_command_md("review", src, PathBuf::new()).unwrap();
+        assert_eq!(cmd.description.as_deref(), Some("Review code"));
+        assert_eq!(cmd.argument_hint.as_deref(), Some("<file>"));
+        assert_eq!(cmd.content, "Review $ARGUMENTS\n");
+
+        let plain = parse_command_md("plain", "Just do it", PathBuf::new()).unwrap();
+        assert!(plain.description.is_none());
+        assert_eq!(plain.content, "Just do it");
+    }
+
+    #[test]
+    fn render_keeps_unknown_keys() {
+        let existing = "---\ndescription: old\nallowed-tools: Read\n---\nold body";
+        let cmd = Command {
+            name: "c".into(),
+            description: Some("new".into()),
+            content: "new body".into(),
+            ..Default::default()
+        };
+        let out = render_command_md(&cmd, Some(existing)).unwrap();
+        assert_eq!(out, "---\ndescription: new\nallowed-tools: Read\n---\n\nnew body");
+    }
+
+    #[test]
+    fn render_without_metadata_is_plain() {
+        let cmd = Command { name: "c".into(), content: "body".into(), ..Default::default() };
+        assert_eq!(render_command_md(&cmd, None).unwrap(), "body");
+    }
+}
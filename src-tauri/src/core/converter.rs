@@ -1,6 +1,10 @@
-use crate::core::{McpServer, ToolType};
+use crate::core::{
+    find_tool, split_rule, Command, ConfigScope, McpServer, Permissions, ToolCapabilities,
+    ToolType,
+};
+use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum ConfigFormat {
     Json,
     Toml,
@@ -12,18 +16,32 @@ pub struct ConversionResult {
     pub warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct CommandConversion {
+    pub command: Command,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PermissionConversion {
+    pub permissions: Permissions,
+    pub warnings: Vec<String>,
+}
+
 pub fn config_format(tool: ToolType) -> ConfigFormat {
-    match tool {
-        ToolType::Codex => ConfigFormat::Toml,
-        _ => ConfigFormat::Json,
-    }
+    find_tool(&tool).map_or(ConfigFormat::Json, |t| t.format)
 }
 
 pub fn requires_format_conversion(from: ToolType, to: ToolType) -> bool {
     config_format(from) != config_format(to)
 }
 
-pub fn convert_mcp_server(server: &McpServer, from: ToolType, to: ToolType) -> ConversionResult {
+pub fn convert_mcp_server(
+    server: &McpServer,
+    from: ToolType,
+    to: ToolType,
+    scope: &ConfigScope,
+) -> ConversionResult {
     let mut out = server.clone();
     let mut warnings = Vec::new();
 
@@ -36,28 +54,32 @@ pub fn convert_mcp_server(server: &McpServer, from: ToolType, to: ToolType) -> C
         out.url = None;
     }
 
-    // Handle enabled field (only Codex supports it, only warn when from Codex)
-    if from == ToolType::Codex && to != ToolType::Codex && !out.enabled {
+    let (from_caps, to_caps) = (capabilities(&from).mcp, capabilities(&to).mcp);
+
+    // Handle enabled field (only warn when the source can actually disable servers)
+    if from_caps.can_disable(scope) && !to_caps.can_disable(scope) && !out.enabled {
+        let for_scope = if to_caps.enabled { " for global servers" } else { "" };
         add_warning(&mut warnings, format!(
-            "`enabled=false` not supported by {}, will be treated as enabled",
-            tool_display_name(to)
+            "`enabled=false` not supported by {}{}, will be treated as enabled",
+            tool_display_name(&to),
+            for_scope
         ));
         out.enabled = true;
     }
 
     // Handle url field
     let mut is_remote = out.url.as_ref().is_some_and(|u| !u.trim().is_empty());
-    if is_remote && !supports_url(to) {
+    if is_remote && !to_caps.remote() {
         add_warning(&mut warnings, format!(
             "`url` not supported by {}, dropped",
-            tool_display_name(to)
+            tool_display_name(&to)
         ));
         out.url = None;
         is_remote = false;
     }
 
     // Handle OpenCode command array semantics for remote servers
-    if to == ToolType::OpenCode && is_remote {
+    if to == ToolType::OPENCODE && is_remote {
         if !out.command.is_empty() || !out.args.is_empty() {
             add_warning(&mut warnings,
                 "OpenCode remote servers ignore `command`/`args`, dropped".into()
@@ -71,21 +93,22 @@ pub fn convert_mcp_server(server: &McpServer, from: ToolType, to: ToolType) -> C
     if !is_remote && out.command.trim().is_empty() {
         add_warning(&mut warnings, "Empty `command` for local server".into());
     }
+    if !is_remote && !to_caps.local() {
+        add_warning(&mut warnings, format!(
+            "Local servers not supported by {}",
+            tool_display_name(&to)
+        ));
+    }
 
     ConversionResult { server: out, warnings }
 }
 
-fn tool_display_name(tool: ToolType) -> &'static str {
-    match tool {
-        ToolType::ClaudeCode => "Claude Code",
-        ToolType::Codex => "Codex",
-        ToolType::Gemini => "Gemini",
-        ToolType::OpenCode => "OpenCode",
-    }
+fn tool_display_name(tool: &ToolType) -> String {
+    find_tool(tool).map_or_else(|| tool.to_string(), |t| t.name)
 }
 
-fn supports_url(tool: ToolType) -> bool {
-    matches!(tool, ToolType::Codex | ToolType::OpenCode)
+fn capabilities(tool: &ToolType) -> ToolCapabilities {
+    find_tool(tool).map(|t| t.capabilities).unwrap_or_default()
 }
 
 fn add_warning(warnings: &mut Vec<String>, msg: String) {
@@ -94,19 +117,149 @@ fn add_warning(warnings: &mut Vec<String>, msg: String) {
     }
 }
 
+/// Rewrites the argument placeholder and drops fields the target cannot
+/// store.
+pub fn convert_command(command: &Command, from: ToolType, to: ToolType) -> CommandConversion {
+    let mut out = command.clone();
+    let mut warnings = Vec::new();
+    if from == to {
+        return CommandConversion { command: out, warnings };
+    }
+    out.path = Default::default();
+
+    let (from_caps, to_caps) = (capabilities(&from).commands, capabilities(&to).commands);
+    let name = tool_display_name(&to);
+    if !to_caps.global && !to_caps.project {
+        add_warning(&mut warnings, format!("Commands not supported by {}", name));
+        return CommandConversion { command: out, warnings };
+    }
+
+    if !from_caps.arguments.is_empty() && from_caps.arguments != to_caps.arguments {
+        out.content = out.content.replace(from_caps.arguments, to_caps.arguments);
+    }
+    if from_caps.positional && !to_caps.positional && has_positional_args(&out.content) {
+        add_warning(&mut warnings, format!(
+            "Positional arguments (`$1`, `$2`, ...) not supported by {}, left as is",
+            name
+        ));
+    }
+    if out.argument_hint.is_some() && !to_caps.argument_hint {
+        add_warning(&mut warnings, format!("`argument-hint` not supported by {}, dropped", name));
+        out.argument_hint = None;
+    }
+    if out.model.is_some() && !to_caps.model {
+        add_warning(&mut warnings, format!("`model` not supported by {}, dropped", name));
+        out.model = None;
+    }
+
+    CommandConversion { command: out, warnings }
+}
+
+fn has_positional_args(content: &str) -> bool {
+    content
+        .as_bytes()
+        .windows(2)
+        .any(|w| w[0] == b'$' && w[1].is_ascii_digit())
+}
+
+/// Keeps the rules and settings the target can express; everything else is
+/// dropped with a warning.
+pub fn convert_permissions(
+    permissions: &Permissions,
+    from: ToolType,
+    to: ToolType,
+) -> PermissionConversion {
+    let mut out = permissions.clone();
+    let mut warnings = Vec::new();
+    if from == to {
+        return PermissionConversion { permissions: out, warnings };
+    }
+
+    let caps = capabilities(&to).permissions;
+    let name = tool_display_name(&to);
+    if !caps.global && !caps.project {
+        add_warning(&mut warnings, format!("Permissions not supported by {}", name));
+        return PermissionConversion { permissions: Permissions::default(), warnings };
+    }
+
+    if !caps.rules {
+        if !out.allow.is_empty() || !out.ask.is_empty() || !out.deny.is_empty() {
+            add_warning(&mut warnings, format!(
+                "Allow/deny rules not supported by {}, dropped",
+                name
+            ));
+        }
+        out.allow.clear();
+        out.ask.clear();
+        out.deny.clear();
+    }
+    if !caps.ask && !out.ask.is_empty() {
+        add_warning(&mut warnings, format!("`ask` rules not supported by {}, dropped", name));
+        out.ask.clear();
+    }
+    if !caps.rule_tools.is_empty() {
+        for rules in [&mut out.allow, &mut out.ask, &mut out.deny] {
+            rules.retain(|rule| {
+                let keep = caps.rule_tools.contains(&split_rule(rule).0);
+                if !keep {
+                    add_warning(&mut warnings, format!(
+                        "`{}` has no equivalent in {}, dropped",
+                        rule, name
+                    ));
+                }
+                keep
+            });
+        }
+    }
+
+    if let Some(approval) = out.approval.filter(|a| !caps.approvals.contains(a)) {
+        add_warning(&mut warnings, format!(
+            "Approval policy `{}` not supported by {}, dropped",
+            enum_name(&approval),
+            name
+        ));
+        out.approval = None;
+    }
+    if let Some(sandbox) = out.sandbox.filter(|s| !caps.sandbox_modes.contains(s)) {
+        add_warning(&mut warnings, format!(
+            "Sandbox mode `{}` not supported by {}, dropped",
+            enum_name(&sandbox),
+            name
+        ));
+        out.sandbox = None;
+    }
+
+    PermissionConversion { permissions: out, warnings }
+}
+
+/// The serialized (kebab-case) name of a unit enum variant.
+fn enum_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
 pub fn convert_mcp_servers(
     servers: &[McpServer],
     from: ToolType,
     to: ToolType,
+    scope: &ConfigScope,
 ) -> Vec<ConversionResult> {
-    servers.iter().map(|s| convert_mcp_server(s, from, to)).collect()
+    servers
+        .iter()
+        .map(|s| convert_mcp_server(s, from.clone(), to.clone(), scope))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{ApprovalPolicy, SandboxMode};
     use std::collections::HashMap;
 
+    const GLOBAL: ConfigScope = ConfigScope::Global;
+
     fn make_server(name: &str, cmd: &str, enabled: bool) -> McpServer {
         McpServer {
             name: name.into(),
@@ -121,33 +274,119 @@ mod tests {
     #[test]
     fn same_tool_no_warnings() {
         let s = make_server("test", "cmd", true);
-        let r = convert_mcp_server(&s, ToolType::ClaudeCode, ToolType::ClaudeCode);
+        let r =
+            convert_mcp_server(&s, ToolType::CLAUDE_CODE, ToolType::CLAUDE_CODE, &GLOBAL);
         assert!(r.warnings.is_empty());
     }
 
     #[test]
-    fn enabled_false_warns_for_non_codex() {
+    fn enabled_false_warns_when_target_cannot_disable() {
         let s = make_server("test", "cmd", false);
-        let r = convert_mcp_server(&s, ToolType::Codex, ToolType::ClaudeCode);
+        let r = convert_mcp_server(&s, ToolType::CODEX, ToolType::GEMINI, &GLOBAL);
         assert!(r.warnings.iter().any(|w| w.contains("enabled")));
         assert!(r.server.enabled);
+
+        let project = ConfigScope::Project("/tmp/project".into());
+        let r = convert_mcp_server(&s, ToolType::CODEX, ToolType::CLAUDE_CODE, &project);
+        assert!(r.warnings.is_empty());
+        assert!(!r.server.enabled);
+    }
+
+    #[test]
+    fn enabled_false_to_claude_global_is_coerced() {
+        let s = make_server("test", "cmd", false);
+        let r = convert_mcp_server(&s, ToolType::CODEX, ToolType::CLAUDE_CODE, &GLOBAL);
+        assert!(r.warnings.iter().any(|w| w.contains("for global servers")));
+        assert!(r.server.enabled);
     }
 
     #[test]
     fn format_detection() {
-        assert_eq!(config_format(ToolType::Codex), ConfigFormat::Toml);
-        assert_eq!(config_format(ToolType::ClaudeCode), ConfigFormat::Json);
-        assert!(requires_format_conversion(ToolType::ClaudeCode, ToolType::Codex));
-        assert!(!requires_format_conversion(ToolType::ClaudeCode, ToolType::Gemini));
+        assert_eq!(config_format(ToolType::CODEX), ConfigFormat::Toml);
+        assert_eq!(config_format(ToolType::CLAUDE_CODE), ConfigFormat::Json);
+        assert!(requires_format_conversion(ToolType::CLAUDE_CODE, ToolType::CODEX));
+        assert!(!requires_format_conversion(ToolType::CLAUDE_CODE, ToolType::GEMINI));
     }
 
     #[test]
     fn remote_to_unsupported_warns_empty_command() {
         let mut s = make_server("test", "", true);
         s.url = Some("http://example.com".into());
-        let r = convert_mcp_server(&s, ToolType::Codex, ToolType::ClaudeCode);
+        let r = convert_mcp_server(&s, ToolType::CODEX, ToolType::CLAUDE_CODE, &GLOBAL);
         assert!(r.warnings.iter().any(|w| w.contains("url")));
         assert!(r.warnings.iter().any(|w| w.contains("Empty")));
         assert!(r.server.url.is_none());
     }
+
+    #[test]
+    fn remote_kept_when_target_supports_it() {
+        let mut s = make_server("test", "", true);
+        s.url = Some("http://example.com".into());
+        let r = convert_mcp_server(&s, ToolType::CODEX, ToolType::ZED, &GLOBAL);
+        assert!(r.warnings.is_empty());
+        assert_eq!(r.server.url.as_deref(), Some("http://example.com"));
+    }
+
+    fn make_command(content: &str) -> Command {
+        Command {
+            name: "review".into(),
+            description: Some("Review".into()),
+            argument_hint: Some("<file>".into()),
+            model: Some("sonnet".into()),
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn command_placeholders_are_rewritten() {
+        let c = make_command("Review $ARGUMENTS carefully");
+        let r = convert_command(&c, ToolType::CLAUDE_CODE, ToolType::GEMINI);
+        assert_eq!(r.command.content, "Review {{args}} carefully");
+        assert!(r.command.argument_hint.is_none() && r.command.model.is_none());
+        assert_eq!(r.warnings.len(), 2);
+
+        let back = convert_command(&r.command, ToolType::GEMINI, ToolType::CODEX);
+        assert_eq!(back.command.content, "Review $ARGUMENTS carefully");
+        assert!(back.warnings.is_empty());
+    }
+
+    #[test]
+    fn permissions_drop_what_target_cannot_express() {
+        let p = Permissions {
+            allow: vec!["Bash(git:*)".into(), "Read".into()],
+            ask: vec!["Edit".into()],
+            deny: vec!["mcp__github".into()],
+            approval: Some(ApprovalPolicy::OnFailure),
+            sandbox: Some(SandboxMode::WorkspaceWrite),
+        };
+        let r = convert_permissions(&p, ToolType::CODEX, ToolType::OPENCODE);
+        assert_eq!(r.permissions.allow, vec!["Bash(git:*)"]);
+        assert_eq!(r.permissions.ask, vec!["Edit"]);
+        assert!(r.permissions.deny.is_empty());
+        assert!(r.permissions.approval.is_none() && r.permissions.sandbox.is_none());
+        assert!(r.warnings.iter().any(|w| w.contains("`on-failure`")));
+        assert_eq!(r.warnings.len(), 4);
+
+        let r = convert_permissions(&p, ToolType::CLAUDE_CODE, ToolType::CODEX);
+        assert!(r.permissions.allow.is_empty() && r.permissions.ask.is_empty());
+        assert_eq!(r.permissions.sandbox, Some(SandboxMode::WorkspaceWrite));
+        assert_eq!(r.warnings.len(), 1);
+
+        let r = convert_permissions(&p, ToolType::CLAUDE_CODE, ToolType::ZED);
+        assert_eq!(r.permissions, Permissions::default());
+    }
+
+    #[test]
+    fn command_positional_args_warn() {
+        let c = make_command("Compare $1 with $2");
+        let r = convert_command(&c, ToolType::CLAUDE_CODE, ToolType::QWEN);
+        assert!(r.warnings.iter().any(|w| w.contains("Positional")));
+        assert!(convert_command(&c, ToolType::CLAUDE_CODE, ToolType::OPENCODE)
+            .warnings
+            .iter()
+            .all(|w| !w.contains("Positional")));
+        assert!(convert_command(&c, ToolType::CLAUDE_CODE, ToolType::ZED).warnings[0]
+            .contains("not supported"));
+    }
 }
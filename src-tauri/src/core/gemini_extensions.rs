@@ -0,0 +1,302 @@
+//! Gemini CLI extensions: folders under `~/.gemini/extensions` with a
+//! `gemini-extension.json` manifest. Disabled ones are listed by name under
+//! `extensions.disabled` in the user `settings.json`.
+
+use crate::core::{copy_skill_dir, GeminiExtension, McpServer};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+pub const EXTENSION_MANIFEST: &str = "gemini-extension.json";
+
+#[derive(Debug, Deserialize)]
+struct ExtensionManifest {
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default, rename = "mcpServers")]
+    mcp_servers: BTreeMap<String, ExtensionServer>,
+    #[serde(default, rename = "contextFileName")]
+    context_file_name: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionServer {
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    #[serde(default, rename = "httpUrl")]
+    http_url: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+fn read_manifest(dir: &Path) -> Result<ExtensionManifest, String> {
+    let path = dir.join(EXTENSION_MANIFEST);
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let manifest: ExtensionManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+    if manifest.name.trim().is_empty() || manifest.name.contains(['/', '\\']) {
+        return Err(format!("Invalid extension name in {}: {:?}", path.display(), manifest.name));
+    }
+    Ok(manifest)
+}
+
+fn file_names(dir: &Path, extension: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == extension))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+fn read_extension(dir: &Path, disabled: &[String]) -> GeminiExtension {
+    let folder = dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let mut extension = GeminiExtension {
+        enabled: !disabled.contains(&folder),
+        name: folder,
+        version: None,
+        description: None,
+        path: dir.to_path_buf(),
+        mcp_servers: vec![],
+        context_files: vec![],
+        commands: vec![],
+        error: None,
+    };
+    let manifest = match read_manifest(dir) {
+        Ok(m) => m,
+        Err(e) => {
+            extension.error = Some(e);
+            return extension;
+        }
+    };
+
+    let context_files = match manifest.context_file_name {
+        Some(serde_json::Value::String(name)) => vec![name],
+        Some(serde_json::Value::Array(names)) => {
+            names.iter().filter_map(|n| n.as_str().map(String::from)).collect()
+        }
+        _ => vec!["GEMINI.md".to_string()],
+    };
+    extension.context_files = context_files.into_iter().filter(|f| dir.join(f).is_file()).collect();
+    extension.commands = file_names(&dir.join("commands"), "toml");
+    extension.mcp_servers = manifest
+        .mcp_servers
+        .into_iter()
+        .map(|(name, entry)| McpServer {
+            name,
+            command: entry.command.unwrap_or_default(),
+            args: entry.args,
+            env: entry.env,
+            url: entry.http_url.or(entry.url),
+            enabled: true,
+        })
+        .collect();
+    extension.enabled = !disabled.contains(&manifest.name);
+    extension.name = manifest.name;
+    extension.version = manifest.version;
+    extension.description = manifest.description;
+    extension
+}
+
+/// Every folder in `dir` with a manifest, sorted by name. A manifest that
+/// fails to parse is reported on its entry.
+pub fn read_extensions(dir: &Path, disabled: &[String]) -> Result<Vec<GeminiExtension>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut extensions: Vec<GeminiExtension> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(EXTENSION_MANIFEST).is_file())
+        .map(|p| read_extension(&p, disabled))
+        .collect();
+    extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(extensions)
+}
+
+fn find_extension(dir: &Path, name: &str) -> Result<GeminiExtension, String> {
+    read_extensions(dir, &[])?
+        .into_iter()
+        .find(|e| e.name == name)
+        .ok_or_else(|| format!("Extension not found: {}", name))
+}
+
+/// Copies an extension folder into `dir`, named after its manifest. Returns
+/// the installed extension's name.
+pub fn install_extension(dir: &Path, source: &Path) -> Result<String, String> {
+    let manifest = read_manifest(source)?;
+    let dest = dir.join(&manifest.name);
+    if dest.exists() || find_extension(dir, &manifest.name).is_ok() {
+        return Err(format!("Extension already installed: {}", manifest.name));
+    }
+    if let Err(e) = copy_skill_dir(source, &dest, &[".git"]) {
+        let _ = fs::remove_dir_all(&dest);
+        return Err(e);
+    }
+    Ok(manifest.name)
+}
+
+pub fn uninstall_extension(dir: &Path, name: &str) -> Result<(), String> {
+    let path = find_extension(dir, name)?.path;
+    fs::remove_dir_all(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+fn load_settings(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    match serde_json::from_str(&content) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} must contain a JSON object", path.display())),
+        Err(e) => Err(format!("Invalid JSON in {}: {}", path.display(), e)),
+    }
+}
+
+/// Names under `extensions.disabled` in `settings.json`.
+pub fn disabled_extensions(settings_path: &Path) -> Result<Vec<String>, String> {
+    let settings = load_settings(settings_path)?;
+    Ok(settings
+        .get("extensions")
+        .and_then(|e| e.get("disabled"))
+        .and_then(|d| d.as_array())
+        .map(|names| names.iter().filter_map(|n| n.as_str().map(String::from)).collect())
+        .unwrap_or_default())
+}
+
+/// Adds `name` to or removes it from `extensions.disabled`, dropping the
+/// keys again once they are empty. Other settings are left as they are.
+pub fn set_extension_enabled(
+    settings_path: &Path,
+    name: &str,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = load_settings(settings_path)?;
+    let extensions = settings
+        .entry("extensions")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or_else(|| format!("`extensions` in {} is not an object", settings_path.display()))?;
+    let disabled = extensions
+        .entry("disabled")
+        .or_insert_with(|| serde_json::Value::Array(vec![]))
+        .as_array_mut()
+        .ok_or_else(|| {
+            format!("`extensions.disabled` in {} is not a list", settings_path.display())
+        })?;
+    disabled.retain(|n| n.as_str() != Some(name));
+    if !enabled {
+        disabled.push(name.into());
+    }
+    if disabled.is_empty() {
+        extensions.shift_remove("disabled");
+    }
+    if extensions.is_empty() {
+        settings.shift_remove("extensions");
+    }
+
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(settings_path, json)
+        .map_err(|e| format!("Failed to write {}: {}", settings_path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn write_extension(dir: &Path, manifest: &str) {
+        fs::create_dir_all(dir.join("commands")).unwrap();
+        fs::write(dir.join(EXTENSION_MANIFEST), manifest).unwrap();
+        fs::write(dir.join("GEMINI.md"), "# Context").unwrap();
+        fs::write(dir.join("commands/deploy.toml"), "prompt = \"Deploy\"").unwrap();
+    }
+
+    #[test]
+    fn lists_extensions_with_their_servers() {
+        let tmp = TempDir::new("list");
+        let dir = tmp.path();
+        write_extension(
+            &dir.join("tools"),
+            r#"{"name":"tools","version":"1.2.0","mcpServers":{
+                "fs":{"command":"node","args":["${extensionPath}/fs.js"]},
+                "remote":{"httpUrl":"https://example.com/mcp"}}}"#,
+        );
+        fs::create_dir_all(dir.join("broken")).unwrap();
+        fs::write(dir.join("broken").join(EXTENSION_MANIFEST), "{").unwrap();
+        fs::create_dir_all(dir.join("not-an-extension")).unwrap();
+
+        let extensions = read_extensions(dir, &["tools".to_string()]).unwrap();
+        assert_eq!(extensions.len(), 2);
+        assert!(extensions[0].error.is_some());
+        let tools = &extensions[1];
+        assert!(!tools.enabled);
+        assert_eq!(tools.version.as_deref(), Some("1.2.0"));
+        assert_eq!(tools.context_files, vec!["GEMINI.md"]);
+        assert_eq!(tools.commands, vec!["deploy"]);
+        assert_eq!(tools.mcp_servers[0].args, vec!["${extensionPath}/fs.js"]);
+        assert_eq!(tools.mcp_servers[1].url.as_deref(), Some("https://example.com/mcp"));
+
+        assert!(read_extensions(&dir.join("missing"), &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn installs_and_uninstalls() {
+        let tmp = TempDir::new("install");
+        let dir = tmp.path();
+        let source = dir.join("src/checkout");
+        write_extension(&source, r#"{"name":"linter"}"#);
+        fs::create_dir_all(source.join(".git")).unwrap();
+        let installed = dir.join("extensions");
+
+        assert_eq!(install_extension(&installed, &source).unwrap(), "linter");
+        assert!(installed.join("linter/commands/deploy.toml").is_file());
+        assert!(!installed.join("linter/.git").exists());
+        assert!(install_extension(&installed, &source).is_err());
+        assert!(install_extension(&installed, dir).is_err());
+
+        uninstall_extension(&installed, "linter").unwrap();
+        assert!(!installed.join("linter").exists());
+        assert!(uninstall_extension(&installed, "linter").is_err());
+    }
+
+    #[test]
+    fn toggles_the_disabled_list() {
+        let tmp = TempDir::new("toggle");
+        let dir = tmp.path();
+        let settings = dir.join("settings.json");
+        fs::write(&settings, r#"{"theme":"dark","extensions":{"z":1},"a":true}"#).unwrap();
+
+        set_extension_enabled(&settings, "tools", false).unwrap();
+        set_extension_enabled(&settings, "tools", false).unwrap();
+        assert_eq!(disabled_extensions(&settings).unwrap(), vec!["tools"]);
+
+        set_extension_enabled(&settings, "tools", true).unwrap();
+        assert!(disabled_extensions(&settings).unwrap().is_empty());
+        let content = fs::read_to_string(&settings).unwrap();
+        let content: String = content.split_whitespace().collect();
+        assert_eq!(content, r#"{"theme":"dark","extensions":{"z":1},"a":true}"#);
+    }
+}
@@ -0,0 +1,323 @@
+//! Reads skills from local git repositories through the `git` CLI. Skills
+//! are always taken from a commit, never from the working tree.
+
+use crate::core::{parse_skill_md, SourceSkill, SKILL_FILE};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn git(repo: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+fn git_text(repo: &Path, args: &[&str]) -> Result<String, String> {
+    git(repo, args).map(|out| String::from_utf8_lossy(&out).trim_end().to_string())
+}
+
+/// Resolves a source URL (a local path or `file://` remote) to the root of
+/// its repository.
+pub fn resolve_repo(url: &str) -> Result<PathBuf, String> {
+    let url = url.trim();
+    let path = PathBuf::from(url.strip_prefix("file://").unwrap_or(url));
+    if !path.is_dir() {
+        return Err(format!("Repository not found: {}", url));
+    }
+    // Bare repositories have no top level; the path itself is the repo.
+    if git_text(&path, &["rev-parse", "--is-bare-repository"])? == "true" {
+        return Ok(path);
+    }
+    git_text(&path, &["rev-parse", "--show-toplevel"]).map(PathBuf::from)
+}
+
+pub fn head_commit(repo: &Path) -> Result<String, String> {
+    git_text(repo, &["rev-parse", "HEAD"])
+}
+
+struct TreeEntry {
+    mode: String,
+    sha: String,
+    path: String,
+}
+
+/// Blobs under `dir` (repo-relative, empty for the root) at `commit`.
+fn ls_tree(repo: &Path, commit: &str, dir: &str) -> Result<Vec<TreeEntry>, String> {
+    let mut args = vec!["ls-tree", "-r", "-z", "--full-tree", commit];
+    if !dir.is_empty() {
+        args.extend(["--", dir]);
+    }
+    let out = git(repo, &args)?;
+    let mut entries = Vec::new();
+    for record in out.split(|b| *b == 0).filter(|r| !r.is_empty()) {
+        let record = String::from_utf8_lossy(record);
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut meta = meta.split(' ');
+        let (Some(mode), Some(kind), Some(sha)) = (meta.next(), meta.next(), meta.next()) else {
+            continue;
+        };
+        if kind == "blob" {
+            entries.push(TreeEntry { mode: mode.into(), sha: sha.into(), path: path.into() });
+        }
+    }
+    Ok(entries)
+}
+
+/// Skills in the repository at `commit`, sorted by path.
+pub fn list_source_skills(repo: &Path, commit: &str) -> Result<Vec<SourceSkill>, String> {
+    let mut skills = Vec::new();
+    for entry in ls_tree(repo, commit, "")? {
+        let Some(dir) = entry.path.strip_suffix(SKILL_FILE) else {
+            continue;
+        };
+        if !(dir.is_empty() || dir.ends_with('/')) {
+            continue;
+        }
+        let dir = dir.trim_end_matches('/');
+        let content = String::from_utf8_lossy(&git(repo, &["cat-file", "blob", &entry.sha])?)
+            .into_owned();
+        // Malformed skills are not offered for install.
+        if let Ok(skill) = parse_skill_md(&content, PathBuf::from(dir)) {
+            skills.push(SourceSkill {
+                name: skill.name,
+                description: skill.description,
+                path: dir.to_string(),
+            });
+        }
+    }
+    skills.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(skills)
+}
+
+/// Writes the skill folder `dir` as of `commit` into `dest`, replacing what
+/// was there. Returns the files written, relative to `dest`.
+pub fn checkout_skill(
+    repo: &Path,
+    commit: &str,
+    dir: &str,
+    dest: &Path,
+) -> Result<Vec<String>, String> {
+    let prefix = if dir.is_empty() { String::new() } else { format!("{}/", dir) };
+    let entries = ls_tree(repo, commit, dir)?;
+    if !entries.iter().any(|e| e.path == format!("{}{}", prefix, SKILL_FILE)) {
+        return Err(format!("No {} in {} at {}", SKILL_FILE, dir, commit));
+    }
+
+    if dest.exists() {
+        fs::remove_dir_all(dest)
+            .map_err(|e| format!("Failed to delete {}: {}", dest.display(), e))?;
+    }
+    let mut written = Vec::new();
+    for entry in entries {
+        let Some(rel) = entry.path.strip_prefix(&prefix) else {
+            continue;
+        };
+        // Symlinks (120000) and submodules are not part of a skill.
+        if !entry.mode.starts_with("100") || rel.split('/').any(|c| c == "..") {
+            continue;
+        }
+        let to = dest.join(rel);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+        }
+        let data = git(repo, &["cat-file", "blob", &entry.sha])?;
+        fs::write(&to, data).map_err(|e| format!("Failed to write {}: {}", to.display(), e))?;
+        #[cfg(unix)]
+        if entry.mode == "100755" {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&to, fs::Permissions::from_mode(0o755))
+                .map_err(|e| format!("Failed to set mode on {}: {}", to.display(), e))?;
+        }
+        written.push(rel.to_string());
+    }
+    written.sort();
+    Ok(written)
+}
+
+fn local_files(root: &Path, rel: &str, out: &mut Vec<String>) -> Result<(), String> {
+    let dir = root.join(rel);
+    let entries =
+        fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel = if rel.is_empty() { name } else { format!("{}/{}", rel, name) };
+        let file_type = entry.file_type().map_err(|e| format!("Failed to read {}: {}", rel, e))?;
+        if file_type.is_dir() {
+            local_files(root, &rel, out)?;
+        } else {
+            out.push(rel);
+        }
+    }
+    Ok(())
+}
+
+/// Files in `dest` that were edited, added or removed since the skill folder
+/// `dir` was checked out from `commit`, sorted. Empty when `dest` is gone.
+pub fn local_changes(
+    repo: &Path,
+    commit: &str,
+    dir: &str,
+    dest: &Path,
+) -> Result<Vec<String>, String> {
+    if !dest.is_dir() {
+        return Ok(vec![]);
+    }
+    let prefix = if dir.is_empty() { String::new() } else { format!("{}/", dir) };
+    let committed: Vec<(String, String)> = ls_tree(repo, commit, dir)?
+        .into_iter()
+        .filter(|e| e.mode.starts_with("100"))
+        .filter_map(|e| Some((e.path.strip_prefix(&prefix)?.to_string(), e.sha)))
+        .collect();
+    let mut local = Vec::new();
+    local_files(dest, "", &mut local)?;
+
+    let mut changed = Vec::new();
+    let mut kept = Vec::new();
+    for rel in &local {
+        match committed.iter().find(|(c, _)| c == rel) {
+            Some((_, sha)) => kept.push((rel, sha)),
+            None => changed.push(rel.clone()),
+        }
+    }
+    changed.extend(committed.iter().filter(|(c, _)| !local.contains(c)).map(|(c, _)| c.clone()));
+    if !kept.is_empty() {
+        // Hash what is on disk the way git would and compare blob ids.
+        let paths: Vec<String> =
+            kept.iter().map(|(rel, _)| dest.join(rel).to_string_lossy().into_owned()).collect();
+        let mut args = vec!["hash-object", "--no-filters", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        let hashes = git_text(repo, &args)?;
+        for ((rel, sha), hash) in kept.iter().zip(hashes.lines()) {
+            if hash != sha.as_str() {
+                changed.push((*rel).clone());
+            }
+        }
+    }
+    changed.sort();
+    Ok(changed)
+}
+
+/// Whether the skill folder `dir` differs between two commits.
+pub fn skill_changed(repo: &Path, from: &str, to: &str, dir: &str) -> Result<bool, String> {
+    let path = if dir.is_empty() { "." } else { dir };
+    git_text(repo, &["diff", "--name-only", from, to, "--", path]).map(|out| !out.is_empty())
+}
+
+/// Unified diff of the skill folder `dir` between two commits.
+pub fn skill_diff(repo: &Path, from: &str, to: &str, dir: &str) -> Result<String, String> {
+    let path = if dir.is_empty() { "." } else { dir };
+    git_text(repo, &["diff", "--no-color", from, to, "--", path])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    struct TempRepo(TempDir);
+
+    impl TempRepo {
+        fn new(name: &str) -> Self {
+            let repo = Self(TempDir::new(name));
+            repo.git(&["init", "-q"]);
+            repo
+        }
+
+        fn path(&self) -> &PathBuf {
+            self.0.path()
+        }
+
+        fn git(&self, args: &[&str]) -> String {
+            let mut full = vec!["-c", "user.name=t", "-c", "user.email=t@t"];
+            full.extend(["-c", "commit.gpgsign=false"]);
+            full.extend(args);
+            git_text(self.path(), &full).unwrap()
+        }
+
+        fn write(&self, rel: &str, content: &str) {
+            let path = self.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        fn commit(&self, msg: &str) -> String {
+            self.git(&["add", "-A"]);
+            self.git(&["commit", "-q", "-m", msg]);
+            head_commit(self.path()).unwrap()
+        }
+    }
+
+    fn skill_md(name: &str, body: &str) -> String {
+        format!("---\nname: {}\ndescription: {} skill\n---\n{}\n", name, name, body)
+    }
+
+    #[test]
+    fn lists_committed_skills_only() {
+        let repo = TempRepo::new("list");
+        repo.write("skills/pdf/SKILL.md", &skill_md("pdf", "v1"));
+        repo.write("skills/broken/SKILL.md", "no frontmatter");
+        repo.write("other/NOT_SKILL.md", "x");
+        let head = repo.commit("init");
+        repo.write("skills/draft/SKILL.md", &skill_md("draft", "wip"));
+
+        let root = resolve_repo(&format!("file://{}", repo.path().display())).unwrap();
+        let skills = list_source_skills(&root, &head).unwrap();
+        let names: Vec<_> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["pdf"]);
+        assert_eq!(skills[0].path, "skills/pdf");
+    }
+
+    #[test]
+    fn checkout_diff_and_update() {
+        let repo = TempRepo::new("update");
+        repo.write("pdf/SKILL.md", &skill_md("pdf", "v1"));
+        repo.write("pdf/scripts/run.sh", "echo 1");
+        repo.write("docs/README.md", "readme");
+        let first = repo.commit("v1");
+
+        let dest = repo.path().join("out/pdf");
+        let files = checkout_skill(repo.path(), &first, "pdf", &dest).unwrap();
+        assert_eq!(files, vec!["SKILL.md", "scripts/run.sh"]);
+
+        repo.write("docs/README.md", "changed");
+        let docs_only = repo.commit("docs");
+        assert!(!skill_changed(repo.path(), &first, &docs_only, "pdf").unwrap());
+
+        repo.write("pdf/SKILL.md", &skill_md("pdf", "v2"));
+        let second = repo.commit("v2");
+        assert!(skill_changed(repo.path(), &first, &second, "pdf").unwrap());
+        let diff = skill_diff(repo.path(), &first, &second, "pdf").unwrap();
+        assert!(diff.contains("-v1") && diff.contains("+v2"), "{}", diff);
+
+        assert!(local_changes(repo.path(), &first, "pdf", &dest).unwrap().is_empty());
+        fs::write(dest.join("stale.txt"), "x").unwrap();
+        fs::write(dest.join("scripts/run.sh"), "echo 2").unwrap();
+        fs::remove_file(dest.join("SKILL.md")).unwrap();
+        assert_eq!(
+            local_changes(repo.path(), &first, "pdf", &dest).unwrap(),
+            vec!["SKILL.md", "scripts/run.sh", "stale.txt"]
+        );
+        checkout_skill(repo.path(), &second, "pdf", &dest).unwrap();
+        assert!(fs::read_to_string(dest.join("SKILL.md")).unwrap().contains("v2"));
+        assert!(!dest.join("stale.txt").exists());
+    }
+
+    #[test]
+    fn missing_repo_is_rejected() {
+        assert!(resolve_repo("file:///definitely/not/here").is_err());
+    }
+}
@@ -0,0 +1,390 @@
+//! Claude Code hooks: validation and local test runs.
+
+use crate::core::{HookConfig, LintSeverity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+pub const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Events whose matcher is used; the others ignore it.
+const MATCHER_EVENTS: &[&str] = &["PreToolUse", "PostToolUse", "PreCompact", "SessionStart"];
+
+/// Claude Code's own default.
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+
+const SHELL_BUILTINS: &[&str] =
+    &["echo", "printf", "exit", "true", "false", "test", "[", "cd", "exec", "source", "."];
+
+const INTERPRETERS: &[&str] =
+    &["sh", "bash", "zsh", "python", "python3", "node", "bun", "deno", "ruby", "perl"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookFinding {
+    pub event: String,
+    /// Index of the matcher group under the event, if the finding is about one.
+    pub group: Option<usize>,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookRun {
+    pub stdout: String,
+    pub stderr: String,
+    /// `None` when the process was killed or ended by a signal.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+}
+
+/// Checks event names, matchers and handlers. Commands are resolved against
+/// `project_dir`, which also stands in for `$CLAUDE_PROJECT_DIR`. Events and
+/// hook types this list doesn't know are only warned about, since newer
+/// Claude Code releases keep adding them.
+pub fn validate_hooks(hooks: &HookConfig, project_dir: Option<&Path>) -> Vec<HookFinding> {
+    use LintSeverity::{Error, Warning};
+    let mut out = Vec::new();
+    let mut push = |event: &str, group: Option<usize>, severity, message: String| {
+        out.push(HookFinding { event: event.to_string(), group, severity, message });
+    };
+
+    for (event, groups) in hooks {
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            push(event, None, Warning, format!("Unknown hook event `{}`", event));
+            continue;
+        }
+        for (i, group) in groups.iter().enumerate() {
+            let matcher = group.matcher.as_deref().unwrap_or("");
+            if !matcher.is_empty() && matcher != "*" {
+                if !MATCHER_EVENTS.contains(&event.as_str()) {
+                    push(event, Some(i), Warning, format!("{} ignores matchers", event));
+                } else if let Err(e) = Regex::new(matcher) {
+                    push(event, Some(i), Error, format!("Invalid matcher `{}`: {}", matcher, e));
+                }
+            }
+            if group.hooks.is_empty() {
+                push(event, Some(i), Warning, "Matcher has no hooks".into());
+            }
+            for hook in &group.hooks {
+                if hook.timeout == Some(0) {
+                    push(event, Some(i), Error, "Timeout must be at least 1 second".into());
+                }
+                match hook.kind.as_str() {
+                    "command" => match hook.command.as_deref().map(str::trim) {
+                        None | Some("") => {
+                            push(event, Some(i), Error, "Hook command is empty".into())
+                        }
+                        Some(cmd) => {
+                            if let Some(missing) = missing_program(cmd, project_dir) {
+                                push(event, Some(i), Warning, format!("Not found: {}", missing));
+                            }
+                        }
+                    },
+                    "prompt" => {
+                        if hook.prompt.as_deref().is_none_or(|p| p.trim().is_empty()) {
+                            push(event, Some(i), Error, "Hook prompt is empty".into());
+                        }
+                    }
+                    other => {
+                        push(event, Some(i), Warning, format!("Unknown hook type `{}`", other))
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn unquote(token: &str) -> String {
+    token.replace(['"', '\''], "")
+}
+
+fn expand(token: &str, project_dir: Option<&Path>) -> Option<PathBuf> {
+    let mut s = unquote(token);
+    if let Some(dir) = project_dir {
+        let dir = dir.to_string_lossy();
+        s = s.replace("${CLAUDE_PROJECT_DIR}", &dir).replace("$CLAUDE_PROJECT_DIR", &dir);
+    }
+    // Other variables are only known when Claude Code runs the hook.
+    if s.contains('$') {
+        return None;
+    }
+    let path = match s.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => PathBuf::from(s),
+    };
+    match (path.is_relative(), project_dir) {
+        (true, Some(dir)) => Some(dir.join(path)),
+        _ => Some(path),
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| {
+        let candidate = dir.join(program);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+/// The program (or interpreted script) a command starts with, if it cannot
+/// be found.
+fn missing_program(command: &str, project_dir: Option<&Path>) -> Option<String> {
+    let mut tokens = command
+        .split_whitespace()
+        .skip_while(|t| t.contains('=') && !t.starts_with(['"', '\'', '$', '/', '.']));
+    let first = tokens.next()?;
+    let program = unquote(first);
+    if SHELL_BUILTINS.contains(&program.as_str()) {
+        return None;
+    }
+    if program.contains('/') || first.contains('$') || program.starts_with('~') {
+        let path = expand(first, project_dir)?;
+        return (!path.exists()).then(|| path.display().to_string());
+    }
+    if !on_path(&program) {
+        return Some(program);
+    }
+    if INTERPRETERS.contains(&program.as_str()) {
+        let script = tokens.next().filter(|t| !t.starts_with('-'))?;
+        let path = expand(script, project_dir)?;
+        let looks_like_file = path.extension().is_some() || script.contains('/');
+        if looks_like_file && !path.exists() {
+            return Some(path.display().to_string());
+        }
+    }
+    None
+}
+
+/// The JSON Claude Code sends on stdin for `event`, filled with sample
+/// values.
+pub fn sample_hook_payload(event: &str, cwd: &Path) -> Value {
+    let mut payload = json!({
+        "session_id": "test-session",
+        "transcript_path": cwd.join(".claude/test-transcript.jsonl"),
+        "cwd": cwd,
+        "hook_event_name": event,
+    });
+    let extra = match event {
+        "PreToolUse" => json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "echo hello", "description": "Print hello" },
+        }),
+        "PostToolUse" => json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "echo hello", "description": "Print hello" },
+            "tool_response": { "stdout": "hello\n", "stderr": "", "interrupted": false },
+        }),
+        "Notification" => json!({ "message": "Claude needs your permission to use Bash" }),
+        "UserPromptSubmit" => json!({ "prompt": "Write a function that adds two numbers" }),
+        "Stop" | "SubagentStop" => json!({ "stop_hook_active": false }),
+        "PreCompact" => json!({ "trigger": "manual", "custom_instructions": "" }),
+        "SessionStart" => json!({ "source": "startup" }),
+        "SessionEnd" => json!({ "reason": "other" }),
+        _ => json!({}),
+    };
+    if let (Some(p), Value::Object(e)) = (payload.as_object_mut(), extra) {
+        p.extend(e);
+    }
+    payload
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", command]);
+        c
+    }
+}
+
+/// Runs a hook command the way Claude Code does: through the shell, in
+/// `cwd`, with `payload` on stdin. The process is killed after `timeout`.
+pub fn run_hook(
+    command: &str,
+    payload: &str,
+    cwd: &Path,
+    timeout: Duration,
+) -> Result<HookRun, String> {
+    let mut child = shell(command)
+        .current_dir(cwd)
+        .env("CLAUDE_PROJECT_DIR", cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start hook: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that never reads stdin closes the pipe early; that is fine.
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut p) = pipe {
+                let _ = p.read_to_end(&mut buf);
+            }
+            let _ = tx.send(String::from_utf8_lossy(&buf).into_owned());
+        });
+        rx
+    };
+    let stdout = read(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = read(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait().map_err(|e| format!("Failed to wait for hook: {}", e))? {
+            Some(status) => break Some(status),
+            None if started.elapsed() >= timeout => {
+                timed_out = true;
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            None => std::thread::sleep(Duration::from_millis(20)),
+        }
+    };
+
+    // Processes the hook started in the background can keep the pipes open
+    // after a kill, so output is only awaited briefly then.
+    let grace = if timed_out { Duration::from_millis(200) } else { Duration::from_secs(5) };
+    Ok(HookRun {
+        stdout: stdout.recv_timeout(grace).unwrap_or_default(),
+        stderr: stderr.recv_timeout(grace).unwrap_or_default(),
+        exit_code: status.and_then(|s| s.code()),
+        timed_out,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{HookGroup, HookHandler};
+    use crate::test_util::TempDir;
+
+    fn command_hook(cmd: &str) -> HookHandler {
+        HookHandler { kind: "command".into(), command: Some(cmd.into()), ..Default::default() }
+    }
+
+    fn config(event: &str, matcher: Option<&str>, hooks: Vec<HookHandler>) -> HookConfig {
+        let group = HookGroup { matcher: matcher.map(String::from), hooks, ..Default::default() };
+        HookConfig::from([(event.to_string(), vec![group])])
+    }
+
+    fn messages(findings: &[HookFinding]) -> Vec<&str> {
+        findings.iter().map(|f| f.message.as_str()).collect()
+    }
+
+    #[test]
+    fn valid_hooks_have_no_findings() {
+        let hooks = config("PreToolUse", Some("Edit|Write"), vec![command_hook("echo ok")]);
+        assert!(validate_hooks(&hooks, None).is_empty());
+        let hooks = config("Stop", None, vec![command_hook("true")]);
+        assert!(validate_hooks(&hooks, None).is_empty());
+    }
+
+    #[test]
+    fn reports_events_matchers_and_types() {
+        let bad_event = config("BeforeTool", None, vec![command_hook("echo")]);
+        let findings = validate_hooks(&bad_event, None);
+        assert_eq!(messages(&findings), vec!["Unknown hook event `BeforeTool`"]);
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+
+        let bad_regex = config("PostToolUse", Some("Edit("), vec![command_hook("echo")]);
+        let findings = validate_hooks(&bad_regex, None);
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+        assert!(findings[0].message.starts_with("Invalid matcher"));
+
+        let ignored = config("UserPromptSubmit", Some("Bash"), vec![command_hook("echo")]);
+        assert_eq!(validate_hooks(&ignored, None)[0].severity, LintSeverity::Warning);
+
+        let http = HookHandler { kind: "http".into(), ..Default::default() };
+        let bad_type = config("Stop", None, vec![http]);
+        let findings = validate_hooks(&bad_type, None);
+        assert_eq!(messages(&findings), vec!["Unknown hook type `http`"]);
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn missing_commands_are_warned() {
+        let tmp = TempDir::new("hooks");
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join(".claude/hooks")).unwrap();
+        std::fs::write(dir.join(".claude/hooks/ok.sh"), "").unwrap();
+
+        let ok = config(
+            "PreToolUse",
+            None,
+            vec![
+                command_hook("\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/ok.sh"),
+                command_hook("sh .claude/hooks/ok.sh --flag"),
+            ],
+        );
+        assert!(validate_hooks(&ok, Some(dir)).is_empty());
+
+        let missing = config(
+            "PreToolUse",
+            None,
+            vec![
+                command_hook("$CLAUDE_PROJECT_DIR/.claude/hooks/gone.sh"),
+                command_hook("surely-not-a-real-program-xyz --check"),
+                command_hook("sh .claude/hooks/gone.sh"),
+            ],
+        );
+        let findings = validate_hooks(&missing, Some(dir));
+        assert_eq!(findings.len(), 3, "{:?}", messages(&findings));
+        assert!(findings.iter().all(|f| f.severity == LintSeverity::Warning));
+    }
+
+    #[test]
+    fn sample_payload_matches_event() {
+        let payload = sample_hook_payload("PreToolUse", Path::new("/p"));
+        assert_eq!(payload["hook_event_name"], "PreToolUse");
+        assert_eq!(payload["tool_name"], "Bash");
+        assert_eq!(payload["cwd"], "/p");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_hook_captures_output_and_exit_code() {
+        let cwd = std::env::temp_dir();
+        let run = run_hook(
+            "read input; echo \"got $input\"; echo oops >&2; exit 2",
+            "{\"a\":1}\n",
+            &cwd,
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(run.stdout, "got {\"a\":1}\n");
+        assert_eq!(run.stderr, "oops\n");
+        assert_eq!(run.exit_code, Some(2));
+        assert!(!run.timed_out);
+
+        let slow = run_hook("sleep 5", "", &cwd, Duration::from_millis(100)).unwrap();
+        assert!(slow.timed_out);
+        assert_eq!(slow.exit_code, None);
+    }
+}
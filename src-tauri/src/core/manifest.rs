@@ -0,0 +1,336 @@
+//! User-defined tools described by manifest files (`*.toml` / `*.json`) in
+//! the app data dir's `tools/` folder.
+
+use crate::core::{is_builtin, ConfigScope};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+static MANIFEST_DIR: OnceLock<PathBuf> = OnceLock::new();
+static MANIFESTS: RwLock<Vec<ToolManifest>> = RwLock::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestFormat {
+    Json,
+    Jsonc,
+    Toml,
+}
+
+/// A path per scope. Global paths may start with `~/`; project paths are
+/// relative to the project root.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScopedPaths {
+    #[serde(default)]
+    pub global: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+/// Keys used inside each server entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMap {
+    #[serde(default = "default_command")]
+    pub command: String,
+    #[serde(default = "default_args")]
+    pub args: String,
+    #[serde(default = "default_env")]
+    pub env: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<String>,
+    /// Command and args are stored together as one array under `command`.
+    #[serde(default)]
+    pub command_array: bool,
+}
+
+fn default_command() -> String {
+    "command".into()
+}
+
+fn default_args() -> String {
+    "args".into()
+}
+
+fn default_env() -> String {
+    "env".into()
+}
+
+impl Default for FieldMap {
+    fn default() -> Self {
+        Self {
+            command: default_command(),
+            args: default_args(),
+            env: default_env(),
+            url: None,
+            enabled: None,
+            command_array: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolManifest {
+    pub id: String,
+    pub name: String,
+    pub format: ManifestFormat,
+    pub config: ScopedPaths,
+    /// JSON pointer (`/mcpServers`) for JSON formats, dotted path
+    /// (`mcp.servers`) for TOML.
+    pub servers_path: String,
+    #[serde(default)]
+    pub fields: FieldMap,
+    #[serde(default)]
+    pub skills: ScopedPaths,
+    #[serde(default)]
+    pub rules: ScopedPaths,
+    /// Folders of Markdown prompt files using `$ARGUMENTS`.
+    #[serde(default)]
+    pub commands: ScopedPaths,
+    /// Project directory whose presence marks the tool as used. Defaults to
+    /// the first component of `config.project`.
+    #[serde(default)]
+    pub detect: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestLoadReport {
+    pub tools: Vec<ToolManifest>,
+    pub errors: Vec<String>,
+}
+
+impl ToolManifest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty()
+            || !self.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid tool id: {:?}", self.id));
+        }
+        if is_builtin(&self.id) {
+            return Err(format!("Tool id {} is reserved for a built-in tool", self.id));
+        }
+        if self.name.trim().is_empty() {
+            return Err(format!("Tool {} has an empty name", self.id));
+        }
+        if self.server_path_segments().is_empty() {
+            return Err(format!("Tool {} has an empty servers_path", self.id));
+        }
+        let project_paths = [
+            &self.config.project,
+            &self.skills.project,
+            &self.rules.project,
+            &self.commands.project,
+            &self.detect,
+        ];
+        for rel in project_paths.into_iter().flatten() {
+            if !is_safe_relative(rel) {
+                return Err(format!("Tool {}: project path must be relative: {}", self.id, rel));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn server_path_segments(&self) -> Vec<String> {
+        match self.format {
+            ManifestFormat::Toml => self
+                .servers_path
+                .split('.')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            ManifestFormat::Json | ManifestFormat::Jsonc => self
+                .servers_path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.replace("~1", "/").replace("~0", "~"))
+                .collect(),
+        }
+    }
+
+    pub fn config_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        resolve(&self.config, scope)
+    }
+
+    pub fn skills_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        resolve(&self.skills, scope)
+    }
+
+    pub fn rules_path(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        resolve(&self.rules, scope)
+    }
+
+    pub fn commands_dir(&self, scope: &ConfigScope) -> Option<PathBuf> {
+        resolve(&self.commands, scope)
+    }
+
+    pub fn detect_dir(&self) -> Option<String> {
+        self.detect.clone().or_else(|| {
+            let project = self.config.project.as_deref()?;
+            match Path::new(project).components().next()? {
+                Component::Normal(c) if Path::new(project).components().count() > 1 => {
+                    Some(c.to_string_lossy().into_owned())
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Directory to watch for global changes: the global config's parent.
+    pub fn global_root(&self) -> Option<PathBuf> {
+        self.config_path(&ConfigScope::Global)?.parent().map(Path::to_path_buf)
+    }
+}
+
+fn is_safe_relative(path: &str) -> bool {
+    let p = Path::new(path);
+    !path.is_empty() && p.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+fn resolve(paths: &ScopedPaths, scope: &ConfigScope) -> Option<PathBuf> {
+    match scope {
+        ConfigScope::Global => paths.global.as_deref().map(expand_home),
+        ConfigScope::Project(p) => paths.project.as_deref().map(|rel| p.join(rel)),
+    }
+}
+
+pub fn parse_manifest(path: &Path) -> Result<ToolManifest, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let manifest: ToolManifest = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str(&content)
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?,
+        _ => serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?,
+    };
+    manifest
+        .validate()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(manifest)
+}
+
+pub fn load_manifests(dir: &Path) -> ManifestLoadReport {
+    let mut report = ManifestLoadReport::default();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return report;
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("toml") | Some("json")))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match parse_manifest(&path) {
+            Ok(m) if report.tools.iter().any(|t| t.id == m.id) => {
+                report.errors.push(format!("{}: duplicate tool id {}", path.display(), m.id));
+            }
+            Ok(m) => report.tools.push(m),
+            Err(e) => report.errors.push(e),
+        }
+    }
+    report
+}
+
+/// Loads manifests from `dir` and remembers it for later reloads.
+pub fn init_manifests(dir: PathBuf) -> ManifestLoadReport {
+    let _ = MANIFEST_DIR.set(dir);
+    reload_manifests()
+}
+
+pub fn reload_manifests() -> ManifestLoadReport {
+    let report = MANIFEST_DIR
+        .get()
+        .map(|dir| load_manifests(dir))
+        .unwrap_or_default();
+    *MANIFESTS.write().unwrap() = report.tools.clone();
+    report
+}
+
+pub fn manifests() -> Vec<ToolManifest> {
+    MANIFESTS.read().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn manifest(toml_src: &str) -> ToolManifest {
+        toml::from_str(toml_src).unwrap()
+    }
+
+    const ACME: &str = r#"
+        id = "acme"
+        name = "Acme Agent"
+        format = "jsonc"
+        servers_path = "/agent/mcp~1servers"
+
+        [config]
+        global = "~/.acme/settings.json"
+        project = ".acme/settings.json"
+    "#;
+
+    #[test]
+    fn parses_with_defaults() {
+        let m = manifest(ACME);
+        assert!(m.validate().is_ok());
+        assert_eq!(m.fields.command, "command");
+        assert_eq!(m.server_path_segments(), vec!["agent", "mcp/servers"]);
+        assert_eq!(m.detect_dir(), Some(".acme".into()));
+        assert!(m.skills_dir(&ConfigScope::Global).is_none());
+    }
+
+    #[test]
+    fn toml_servers_path_is_dotted() {
+        let mut m = manifest(ACME);
+        m.format = ManifestFormat::Toml;
+        m.servers_path = "mcp.servers".into();
+        assert_eq!(m.server_path_segments(), vec!["mcp", "servers"]);
+    }
+
+    #[test]
+    fn rejects_reserved_and_unsafe() {
+        let mut m = manifest(ACME);
+        m.id = "Codex".into();
+        assert!(m.validate().is_err());
+
+        let mut m = manifest(ACME);
+        m.config.project = Some("../outside.json".into());
+        assert!(m.validate().is_err());
+
+        let mut m = manifest(ACME);
+        m.id = "bad id".into();
+        assert!(m.validate().is_err());
+    }
+
+    #[test]
+    fn project_paths_resolve_against_project() {
+        let m = manifest(ACME);
+        let scope = ConfigScope::Project(PathBuf::from("/work/app"));
+        assert_eq!(m.config_path(&scope), Some(PathBuf::from("/work/app/.acme/settings.json")));
+    }
+
+    #[test]
+    fn load_reports_invalid_files() {
+        let tmp = TempDir::new("manifest-dir");
+        fs::write(tmp.path().join("good.toml"), ACME).unwrap();
+        fs::write(tmp.path().join("bad.json"), "{ not json").unwrap();
+        fs::write(tmp.path().join("dup.toml"), ACME).unwrap();
+        fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let report = load_manifests(tmp.path());
+        assert_eq!(report.tools.len(), 1);
+        assert_eq!(report.tools[0].id, "acme");
+        assert_eq!(report.errors.len(), 2);
+    }
+}
@@ -0,0 +1,131 @@
+//! Which MCP servers a tool actually loads in a project, merged across
+//! scopes with the tool's precedence.
+
+use crate::core::{McpServer, ResolvedMcpServer, ScopeKind};
+use std::path::PathBuf;
+
+/// The servers defined in one scope.
+pub struct McpLayer {
+    pub scope: ScopeKind,
+    pub source: Option<PathBuf>,
+    pub servers: Vec<McpServer>,
+    /// Set when the tool skips the whole scope, with the reason.
+    pub ignored: Option<String>,
+}
+
+/// Merges layers ordered lowest precedence first. A server in a later layer
+/// shadows a same-named one from an earlier layer, even when disabled;
+/// entries from ignored layers never shadow anything. Results are sorted by
+/// name, higher-precedence entries first.
+pub fn resolve_mcp(layers: Vec<McpLayer>) -> Vec<ResolvedMcpServer> {
+    // Each entry remembers whether its layer is loaded at all.
+    let mut resolved: Vec<(ResolvedMcpServer, bool)> = Vec::new();
+    for layer in layers {
+        let loaded = layer.ignored.is_none();
+        for server in layer.servers {
+            if loaded {
+                for (earlier, _) in resolved
+                    .iter_mut()
+                    .filter(|(r, counts)| *counts && r.server.name == server.name)
+                {
+                    earlier.active = false;
+                    earlier.shadowed_by = Some(layer.scope);
+                }
+            }
+            let note = match (&layer.ignored, server.enabled) {
+                (Some(reason), _) => Some(reason.clone()),
+                (None, false) => Some("Disabled".into()),
+                (None, true) => None,
+            };
+            let entry = ResolvedMcpServer {
+                active: loaded && server.enabled,
+                server,
+                scope: layer.scope,
+                source: layer.source.clone(),
+                shadowed_by: None,
+                note,
+            };
+            resolved.push((entry, loaded));
+        }
+    }
+    let mut resolved: Vec<_> = resolved.into_iter().rev().map(|(r, _)| r).collect();
+    resolved.sort_by(|a, b| a.server.name.cmp(&b.server.name).then(b.active.cmp(&a.active)));
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(name: &str, command: &str) -> McpServer {
+        McpServer {
+            name: name.into(),
+            command: command.into(),
+            args: vec![],
+            env: Default::default(),
+            url: None,
+            enabled: true,
+        }
+    }
+
+    fn layer(scope: ScopeKind, servers: Vec<McpServer>) -> McpLayer {
+        McpLayer { scope, source: None, servers, ignored: None }
+    }
+
+    #[test]
+    fn project_shadows_global() {
+        let resolved = resolve_mcp(vec![
+            layer(ScopeKind::Global, vec![server("fs", "global-fs"), server("git", "git")]),
+            layer(ScopeKind::Project, vec![server("fs", "project-fs")]),
+        ]);
+        let summary: Vec<_> = resolved
+            .iter()
+            .map(|r| (r.server.command.as_str(), r.active, r.shadowed_by))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("project-fs", true, None),
+                ("global-fs", false, Some(ScopeKind::Project)),
+                ("git", true, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn claude_local_beats_project_beats_user() {
+        let resolved = resolve_mcp(vec![
+            layer(ScopeKind::User, vec![server("db", "user-db")]),
+            layer(ScopeKind::Project, vec![server("db", "project-db")]),
+            layer(ScopeKind::Local, vec![server("db", "local-db")]),
+        ]);
+        let summary: Vec<_> =
+            resolved.iter().map(|r| (r.server.command.as_str(), r.shadowed_by)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("local-db", None),
+                ("project-db", Some(ScopeKind::Local)),
+                ("user-db", Some(ScopeKind::Local)),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignored_layer_does_not_shadow() {
+        let mut project = layer(ScopeKind::Project, vec![server("fs", "project-fs")]);
+        project.ignored = Some("Project is not trusted".into());
+        let mut disabled = server("off", "x");
+        disabled.enabled = false;
+        let resolved = resolve_mcp(vec![
+            layer(ScopeKind::Global, vec![server("fs", "global-fs"), disabled]),
+            project,
+        ]);
+        assert_eq!(resolved[0].server.command, "global-fs");
+        assert!(resolved[0].active);
+        assert!(!resolved[1].active);
+        assert_eq!(resolved[1].note.as_deref(), Some("Project is not trusted"));
+        assert!(!resolved[2].active);
+        assert_eq!(resolved[2].note.as_deref(), Some("Disabled"));
+    }
+}
@@ -1,12 +1,70 @@
 mod adapter;
+mod agent_md;
+mod capabilities;
+mod claude_plugins;
+mod claude_settings;
+mod codex_trust;
+mod command_md;
 mod converter;
+mod gemini_extensions;
+mod git_source;
+mod hooks;
+mod manifest;
+mod mcp_resolve;
 mod models;
+mod permissions;
+mod registry;
+mod skill_archive;
+mod skill_dir;
+mod skill_library;
+mod skill_lint;
+mod skill_md;
 mod watcher;
 
 pub use adapter::ConfigAdapter;
+pub use agent_md::{render_agent_md, scan_agents_dir, validate_agent};
+pub use capabilities::{
+    CommandCapabilities, McpCapabilities, ModelCapabilities, PermissionCapabilities,
+    RulesCapabilities, SkillCapabilities, ToolCapabilities, Transport,
+};
+pub use claude_plugins::{read_marketplaces, read_plugins};
+pub use claude_settings::{
+    add_to_gitignore, claude_json_mcp_servers, claude_json_path, is_gitignored,
+    managed_settings_path, merge_settings, LOCAL_SETTINGS_ENTRY,
+};
+pub use codex_trust::{read_trust_levels, write_trust_levels};
+pub use command_md::{delete_md_command, read_md_commands, write_md_command};
 pub use converter::{
-    config_format, convert_mcp_server, convert_mcp_servers, requires_format_conversion,
-    ConfigFormat, ConversionResult,
+    config_format, convert_command, convert_mcp_server, convert_mcp_servers, convert_permissions,
+    requires_format_conversion, ConfigFormat, ConversionResult,
+};
+pub use gemini_extensions::{
+    disabled_extensions, install_extension, read_extensions, set_extension_enabled,
+    uninstall_extension, EXTENSION_MANIFEST,
+};
+pub use git_source::{
+    checkout_skill, head_commit, list_source_skills, local_changes, resolve_repo, skill_changed,
+    skill_diff,
 };
+pub use hooks::{
+    run_hook, sample_hook_payload, validate_hooks, HookFinding, HookRun, DEFAULT_HOOK_TIMEOUT_SECS,
+};
+pub use manifest::{
+    init_manifests, manifests, reload_manifests, ManifestFormat, ManifestLoadReport, ToolManifest,
+};
+pub use mcp_resolve::{resolve_mcp, McpLayer};
 pub use models::*;
+pub use permissions::{merge_permissions, split_rule};
+pub use registry::{adapter_for, find_tool, is_builtin, tools, ToolDescriptor};
+pub use skill_archive::{read_skill_archive, write_skill_archive, ArchivedSkill, SkillConflict};
+pub use skill_dir::{copy_skill_dir, find_skill_dir, rename_skill_dir, SKILL_FILE};
+pub use skill_library::{
+    add_to_library, init_skill_library, library_dir, link_state, link_to_library,
+    unlink_from_library, SkillCopyMode, SkillLinkState,
+};
+pub use skill_lint::{
+    check_skill_name, has_errors, lint_duplicates, lint_skill, lint_skill_files, LintSeverity,
+    SkillFinding,
+};
+pub use skill_md::{parse_skill_md, render_skill_md, scan_skills_dir};
 pub use watcher::{ConfigChangeEvent, FileWatcher, WriteGuard};
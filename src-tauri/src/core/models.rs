@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,12 +20,51 @@ fn default_enabled() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Skill {
     pub name: String,
     pub description: Option<String>,
     pub content: String,
     pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<AllowedTools>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_yaml::Mapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Other frontmatter keys, in file order.
+    #[serde(default, skip_serializing_if = "serde_yaml::Mapping::is_empty")]
+    pub extra: serde_yaml::Mapping,
+    /// Every frontmatter key in file order, so saving keeps the layout.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_order: Vec<String>,
+}
+
+/// Why a skill folder could not be loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillDiagnostic {
+    pub path: PathBuf,
+    pub reason: String,
+    /// 1-based line in `SKILL.md`, when known.
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillScan {
+    pub skills: Vec<Skill>,
+    pub diagnostics: Vec<SkillDiagnostic>,
+}
+
+/// `allowed-tools` as written: one space-separated string or a list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AllowedTools {
+    Text(String),
+    List(Vec<String>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,15 +77,393 @@ pub struct Project {
     pub updated_at: i64,
 }
 
+/// A Claude Code subagent: `agents/<name>.md` with a system-prompt body.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Agent {
+    pub name: String,
+    pub description: Option<String>,
+    /// Comma-separated string or list; `None` inherits every tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<AllowedTools>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub path: PathBuf,
+    /// Other frontmatter keys, in file order.
+    #[serde(default, skip_serializing_if = "serde_yaml::Mapping::is_empty")]
+    pub extra: serde_yaml::Mapping,
+}
+
+impl Agent {
+    pub fn tool_names(&self) -> Vec<String> {
+        match &self.tools {
+            None => vec![],
+            Some(AllowedTools::Text(s)) => s
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            Some(AllowedTools::List(l)) => l.iter().map(|t| t.trim().to_string()).collect(),
+        }
+    }
+}
+
+/// Agents plus diagnostics for files that failed to load.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentScan {
+    pub agents: Vec<Agent>,
+    pub diagnostics: Vec<SkillDiagnostic>,
+}
+
+/// When the agent stops to ask before acting. Named after Codex's
+/// `approval_policy` values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ToolType {
-    ClaudeCode,
-    Codex,
-    Gemini,
-    OpenCode,
+#[serde(rename_all = "kebab-case")]
+pub enum ApprovalPolicy {
+    /// Ask for anything not known to be safe.
+    Untrusted,
+    /// Ask only after a sandboxed attempt fails.
+    OnFailure,
+    /// The agent decides when to ask; edits are accepted.
+    OnRequest,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxMode {
+    ReadOnly,
+    WorkspaceWrite,
+    DangerFullAccess,
+}
+
+/// A custom endpoint models can be served from: Codex
+/// `[model_providers.<id>]` or an OpenCode `provider.<id>` entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelProvider {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_key: Option<String>,
+    /// Tool-specific keys (`wire_api`, `npm`, `models`, ...), kept as is.
+    #[serde(default)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The model a tool uses in one scope and the providers defined there.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Provider id the model is served by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub providers: Vec<ModelProvider>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopeKind {
+    Global,
+    /// Claude Code's user scope in `~/.claude.json`.
+    User,
+    Project,
+    /// Claude Code's private per-project scope in `~/.claude.json`.
+    Local,
+}
+
+/// An MCP server as a tool would see it in a project, with the file it came
+/// from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedMcpServer {
+    pub server: McpServer,
+    pub scope: ScopeKind,
+    pub source: Option<PathBuf>,
+    /// The tool loads this entry.
+    pub active: bool,
+    /// A same-named server in this higher-precedence scope replaces it.
+    pub shadowed_by: Option<ScopeKind>,
+    /// Why an entry is not loaded, when it is not simply shadowed.
+    pub note: Option<String>,
+}
+
+/// Claude Code settings files, lowest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsLayer {
+    /// `~/.claude/settings.json`
+    User,
+    /// `.claude/settings.json`, checked in.
+    Project,
+    /// `.claude/settings.local.json`, personal.
+    Local,
+    /// The enterprise `managed-settings.json`; read-only.
+    Managed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsLayerFile {
+    pub layer: SettingsLayer,
+    pub path: PathBuf,
+    pub exists: bool,
+    #[serde(default)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
+    pub error: Option<String>,
+}
+
+/// One top-level key after merging every layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveSetting {
+    pub key: String,
+    pub value: serde_json::Value,
+    /// The highest layer that sets the key; its scalars win.
+    pub source: SettingsLayer,
+    /// Every layer that sets the key, lowest first.
+    pub layers: Vec<SettingsLayer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayeredSettings {
+    pub layers: Vec<SettingsLayerFile>,
+    pub effective: Vec<EffectiveSetting>,
+    /// Whether `settings.local.json` is covered by the project's
+    /// `.gitignore`; `None` outside a project.
+    pub local_gitignored: Option<bool>,
+}
+
+/// A Codex `[profiles.<name>]` block. Keys the hub does not model stay in
+/// `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodexProfile {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<ApprovalPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<SandboxMode>,
+    #[serde(default)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodexProfiles {
+    /// The top-level `profile` key.
+    pub default: Option<String>,
+    pub profiles: Vec<CodexProfile>,
+}
+
+/// Codex `trust_level` for a project directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustLevel {
+    Trusted,
+    Untrusted,
+}
+
+/// A Gemini CLI extension: `<extensions>/<name>/gemini-extension.json`.
+/// Its servers are read-only here; they are loaded alongside `settings.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiExtension {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// The extension's folder.
+    pub path: PathBuf,
+    pub enabled: bool,
+    pub mcp_servers: Vec<McpServer>,
+    /// Context files the extension ships, such as `GEMINI.md`.
+    pub context_files: Vec<String>,
+    /// Names of the `commands/*.toml` it adds.
+    pub commands: Vec<String>,
+    /// Set when the manifest cannot be read; the other fields are then empty.
+    pub error: Option<String>,
+}
+
+/// A plugin marketplace from `~/.claude/plugins/known_marketplaces.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeMarketplace {
+    pub name: String,
+    /// GitHub repo, git URL or local path it was added from.
+    pub source: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
+/// An installed Claude Code plugin and what it contributes. The contributed
+/// items are read-only here; they belong to the plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudePlugin {
+    /// `name@marketplace`, the key used in `enabledPlugins`.
+    pub id: String,
+    pub name: String,
+    pub marketplace: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub path: PathBuf,
+    pub enabled: bool,
+    pub mcp_servers: Vec<McpServer>,
+    pub skills: Vec<Skill>,
+    pub commands: Vec<String>,
+    pub agents: Vec<String>,
+    /// Hook events the plugin registers for.
+    pub hooks: Vec<String>,
+    /// Set when the plugin's files cannot be read.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClaudePlugins {
+    pub plugins: Vec<ClaudePlugin>,
+    pub marketplaces: Vec<ClaudeMarketplace>,
+    /// Why `installed_plugins.json` or `known_marketplaces.json` could not
+    /// be read; what could be read is still listed.
+    pub error: Option<String>,
+}
+
+/// A tool's permission settings in one shape. Rules use Claude Code's
+/// syntax (`Read`, `Bash(npm test:*)`, `mcp__github`); adapters translate
+/// tool names to and from their own.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Permissions {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub ask: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxMode>,
+}
+
+/// One entry under a Claude Code hook event: the hooks to run for tools
+/// (or triggers) that match `matcher`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookGroup {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    #[serde(default)]
+    pub hooks: Vec<HookHandler>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookHandler {
+    /// `command` or `prompt`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The `hooks` object of `settings.json`, keyed by event name.
+pub type HookConfig = std::collections::BTreeMap<String, Vec<HookGroup>>;
+
+/// A reusable prompt (slash command). `content` keeps the tool's own
+/// argument placeholder; the converter rewrites it when copying.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Command {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument_hint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub content: String,
+    /// File the command was read from; empty for config-file entries.
+    #[serde(default)]
+    pub path: PathBuf,
+}
+
+/// A git repository skills are installed from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillSource {
+    pub id: i64,
+    pub name: String,
+    /// Local repository path or `file://` remote.
+    pub url: String,
+    pub created_at: i64,
+}
+
+/// A skill found in a source at its current HEAD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSkill {
+    pub name: String,
+    pub description: Option<String>,
+    /// Folder of the skill, relative to the repository root.
+    pub path: String,
+}
+
+/// A skill installed from a source into one tool and scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledSkill {
+    pub id: i64,
+    pub source_id: i64,
+    pub name: String,
+    pub skill_path: String,
+    pub tool: ToolType,
+    /// `None` for the global scope.
+    pub project_path: Option<String>,
+    /// Source commit the installed files were taken from.
+    pub commit: String,
+    pub installed_at: i64,
+    pub updated_at: i64,
+}
+
+/// Id of a tool in the registry (see `core::registry`). Built-in tools have
+/// associated constants; user-defined tools use their manifest id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ToolType(Cow<'static, str>);
+
+impl ToolType {
+    pub const CLAUDE_CODE: ToolType = ToolType::builtin("ClaudeCode");
+    pub const CODEX: ToolType = ToolType::builtin("Codex");
+    pub const GEMINI: ToolType = ToolType::builtin("Gemini");
+    pub const OPENCODE: ToolType = ToolType::builtin("OpenCode");
+    pub const ZED: ToolType = ToolType::builtin("Zed");
+    pub const QWEN: ToolType = ToolType::builtin("Qwen");
+    pub const IFLOW: ToolType = ToolType::builtin("IFlow");
+
+    const fn builtin(id: &'static str) -> Self {
+        ToolType(Cow::Borrowed(id))
+    }
+
+    pub fn new(id: impl Into<String>) -> Self {
+        ToolType(Cow::Owned(id.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ToolType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConfigScope {
     Global,
     Project(PathBuf),
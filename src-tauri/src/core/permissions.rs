@@ -0,0 +1,62 @@
+//! Helpers for the normalized permission model. Rules use Claude Code's
+//! syntax: a tool name with an optional specifier, e.g. `Bash(npm test:*)`.
+
+use crate::core::Permissions;
+
+/// Splits `Bash(git push:*)` into `("Bash", Some("git push:*"))`.
+pub fn split_rule(rule: &str) -> (&str, Option<&str>) {
+    match rule.trim().split_once('(') {
+        Some((tool, rest)) if rest.ends_with(')') => (tool.trim(), Some(&rest[..rest.len() - 1])),
+        _ => (rule.trim(), None),
+    }
+}
+
+/// Adds the rules of `from` to `into`, skipping duplicates. Approval and
+/// sandbox settings from `from` win when set.
+pub fn merge_permissions(into: &mut Permissions, from: &Permissions) {
+    let lists = [
+        (&mut into.allow, &from.allow),
+        (&mut into.ask, &from.ask),
+        (&mut into.deny, &from.deny),
+    ];
+    for (target, source) in lists {
+        for rule in source {
+            if !target.contains(rule) {
+                target.push(rule.clone());
+            }
+        }
+    }
+    into.approval = from.approval.or(into.approval);
+    into.sandbox = from.sandbox.or(into.sandbox);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ApprovalPolicy;
+
+    #[test]
+    fn splits_rules() {
+        assert_eq!(split_rule("Bash(git push:*)"), ("Bash", Some("git push:*")));
+        assert_eq!(split_rule(" Read "), ("Read", None));
+        assert_eq!(split_rule("Odd(rule"), ("Odd(rule", None));
+    }
+
+    #[test]
+    fn merge_unions_rules() {
+        let mut into = Permissions {
+            allow: vec!["Read".into()],
+            approval: Some(ApprovalPolicy::Untrusted),
+            ..Default::default()
+        };
+        let from = Permissions {
+            allow: vec!["Read".into(), "Bash(ls:*)".into()],
+            deny: vec!["WebFetch".into()],
+            ..Default::default()
+        };
+        merge_permissions(&mut into, &from);
+        assert_eq!(into.allow, vec!["Read", "Bash(ls:*)"]);
+        assert_eq!(into.deny, vec!["WebFetch"]);
+        assert_eq!(into.approval, Some(ApprovalPolicy::Untrusted));
+    }
+}
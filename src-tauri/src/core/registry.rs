@@ -0,0 +1,149 @@
+//! Single list of supported tools. Commands, the converter and the watcher
+//! look tools up here instead of matching on ids.
+
+use crate::core::{manifests, ConfigAdapter, ConfigFormat, ToolCapabilities, ToolType};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+static BUILTIN_TOOLS: OnceLock<Vec<ToolDescriptor>> = OnceLock::new();
+
+type AdapterFactory = Arc<dyn Fn() -> Box<dyn ConfigAdapter> + Send + Sync>;
+
+#[derive(Clone, Serialize)]
+pub struct ToolDescriptor {
+    pub id: ToolType,
+    pub name: String,
+    pub format: ConfigFormat,
+    pub capabilities: ToolCapabilities,
+    /// Global config directory, watched for changes.
+    pub global_dir: Option<PathBuf>,
+    /// Directory relative to a project root whose presence marks the tool as used.
+    pub project_dir: Option<PathBuf>,
+    pub builtin: bool,
+    #[serde(skip)]
+    factory: AdapterFactory,
+}
+
+impl ToolDescriptor {
+    /// Paths are taken from the adapter itself so they cannot drift from
+    /// where it actually reads and writes.
+    pub fn new(
+        id: ToolType,
+        name: impl Into<String>,
+        format: ConfigFormat,
+        capabilities: ToolCapabilities,
+        factory: impl Fn() -> Box<dyn ConfigAdapter> + Send + Sync + 'static,
+    ) -> Self {
+        let adapter = factory();
+        let non_empty = |p: PathBuf| Some(p).filter(|p| !p.as_os_str().is_empty());
+        Self {
+            id,
+            name: name.into(),
+            format,
+            capabilities,
+            global_dir: non_empty(adapter.global_config_path()),
+            project_dir: non_empty(adapter.project_config_path(&PathBuf::new())),
+            builtin: true,
+            factory: Arc::new(factory),
+        }
+    }
+
+    pub fn custom(mut self) -> Self {
+        self.builtin = false;
+        self
+    }
+
+    pub fn adapter(&self) -> Box<dyn ConfigAdapter> {
+        (self.factory)()
+    }
+
+    pub fn is_used_in(&self, project: &Path) -> bool {
+        self.project_dir
+            .as_ref()
+            .is_some_and(|dir| project.join(dir).is_dir())
+    }
+}
+
+fn builtin_tools() -> &'static [ToolDescriptor] {
+    BUILTIN_TOOLS.get_or_init(crate::adapters::builtin_tools)
+}
+
+pub fn is_builtin(id: &str) -> bool {
+    builtin_tools().iter().any(|t| t.id.as_str() == id)
+}
+
+/// Built-in tools followed by the currently loaded manifests.
+pub fn tools() -> Vec<ToolDescriptor> {
+    builtin_tools()
+        .iter()
+        .cloned()
+        .chain(manifests().into_iter().map(crate::adapters::manifest_tool))
+        .collect()
+}
+
+pub fn find_tool(id: &ToolType) -> Option<ToolDescriptor> {
+    if let Some(t) = builtin_tools().iter().find(|t| t.id == *id) {
+        return Some(t.clone());
+    }
+    manifests()
+        .into_iter()
+        .find(|m| m.id == id.as_str())
+        .map(crate::adapters::manifest_tool)
+}
+
+pub fn adapter_for(id: &ToolType) -> Result<Box<dyn ConfigAdapter>, String> {
+    find_tool(id)
+        .map(|t| t.adapter())
+        .ok_or_else(|| format!("Unknown tool: {}", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn builtin_ids_are_unique() {
+        let ids: HashSet<_> = builtin_tools().iter().map(|t| t.id.clone()).collect();
+        assert_eq!(ids.len(), builtin_tools().len());
+    }
+
+    #[test]
+    fn builtin_paths_come_from_adapters() {
+        let opencode = find_tool(&ToolType::OPENCODE).unwrap();
+        assert!(opencode.global_dir.unwrap().ends_with(".config/opencode"));
+        assert_eq!(opencode.project_dir, Some(PathBuf::from(".opencode")));
+        assert_eq!(
+            find_tool(&ToolType::CODEX).unwrap().format,
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    fn capabilities_match_adapters() {
+        let codex = find_tool(&ToolType::CODEX).unwrap().capabilities;
+        assert!(codex.mcp.enabled && codex.mcp.remote());
+        let zed = find_tool(&ToolType::ZED).unwrap().capabilities;
+        assert!(!zed.skills.project && !zed.rules.global);
+        let claude = find_tool(&ToolType::CLAUDE_CODE).unwrap().capabilities;
+        assert!(claude.mcp.local() && !claude.mcp.remote() && claude.rules.imports);
+    }
+
+    #[test]
+    fn unknown_tool_has_no_adapter() {
+        assert!(adapter_for(&ToolType::new("nope")).is_err());
+        assert!(!is_builtin("nope"));
+        assert!(is_builtin("ClaudeCode"));
+    }
+
+    #[test]
+    fn tool_type_serializes_as_id() {
+        assert_eq!(
+            serde_json::to_string(&ToolType::CLAUDE_CODE).unwrap(),
+            "\"ClaudeCode\""
+        );
+        let t: ToolType = serde_json::from_str("\"Codex\"").unwrap();
+        assert_eq!(t, ToolType::CODEX);
+    }
+}
@@ -0,0 +1,340 @@
+//! Portable `.zip` / `.skill` archives of skill folders.
+//!
+//! An archive holds one folder per skill (`<name>/SKILL.md`, `<name>/scripts/..`).
+//! A single skill may also sit at the archive root.
+
+use crate::core::{parse_skill_md, Skill, SKILL_FILE};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Upper bound on the unpacked size of an archive.
+const MAX_UNPACKED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// What to do when an imported skill's name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillConflict {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+/// A skill read from an archive, not yet written anywhere.
+#[derive(Debug, Clone)]
+pub struct ArchivedSkill {
+    pub skill: Skill,
+    files: Vec<ArchivedFile>,
+}
+
+#[derive(Debug, Clone)]
+struct ArchivedFile {
+    /// Relative to the skill folder, `/`-separated.
+    path: String,
+    data: Vec<u8>,
+    mode: Option<u32>,
+}
+
+impl ArchivedSkill {
+    /// Files in the skill folder, sorted.
+    pub fn files(&self) -> Vec<String> {
+        self.files.iter().map(|f| f.path.clone()).collect()
+    }
+
+    /// Writes every file except `SKILL.md` into `dest`, which the caller
+    /// renders from [`ArchivedSkill::skill`].
+    pub fn extract_resources(&self, dest: &Path) -> Result<Vec<String>, String> {
+        let mut written = Vec::new();
+        for file in self.files.iter().filter(|f| f.path != SKILL_FILE) {
+            let to = dest.join(&file.path);
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+            }
+            fs::write(&to, &file.data)
+                .map_err(|e| format!("Failed to write {}: {}", to.display(), e))?;
+            set_mode(&to, file.mode)?;
+            written.push(file.path.clone());
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: Option<u32>) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    match mode {
+        Some(mode) => fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777))
+            .map_err(|e| format!("Failed to set mode on {}: {}", path.display(), e)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: Option<u32>) -> Result<(), String> {
+    Ok(())
+}
+
+/// Joins path components with `/`, rejecting anything that could leave the
+/// extraction folder.
+fn safe_entry_path(name: &str) -> Result<Vec<String>, String> {
+    let path = Path::new(name);
+    let mut parts = Vec::new();
+    for c in path.components() {
+        match c {
+            Component::Normal(p) => parts.push(p.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => return Err(format!("Archive entry escapes the skill folder: {}", name)),
+        }
+    }
+    if name.contains('\\') {
+        return Err(format!("Archive entry escapes the skill folder: {}", name));
+    }
+    Ok(parts)
+}
+
+/// Reads and validates every skill in the archive at `path`.
+pub fn read_skill_archive(path: &Path) -> Result<Vec<ArchivedSkill>, String> {
+    let file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read archive {}: {}", path.display(), e))?;
+
+    let mut entries = Vec::new();
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let parts = safe_entry_path(entry.name())?;
+        let hidden = parts.first().is_some_and(|p| p.starts_with('.') || p == "__MACOSX");
+        if entry.is_dir() || parts.is_empty() || hidden {
+            continue;
+        }
+        // Count the bytes actually inflated; the size in the header is
+        // whatever the archive claims.
+        let name = entry.name().to_string();
+        let mut data = Vec::new();
+        entry
+            .by_ref()
+            .take(MAX_UNPACKED_BYTES - total + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        total += data.len() as u64;
+        if total > MAX_UNPACKED_BYTES {
+            return Err(format!("Archive is larger than {} MB unpacked", MAX_UNPACKED_BYTES >> 20));
+        }
+        entries.push((parts, data, entry.unix_mode()));
+    }
+
+    // A root SKILL.md means the whole archive is one skill.
+    let single = entries.iter().any(|(p, _, _)| p.len() == 1 && p[0] == SKILL_FILE);
+    let mut groups: BTreeMap<String, Vec<ArchivedFile>> = BTreeMap::new();
+    for (mut parts, data, mode) in entries {
+        let folder = if single {
+            String::new()
+        } else if parts.len() > 1 {
+            parts.remove(0)
+        } else {
+            return Err(format!("Unexpected file at archive root: {}", parts[0]));
+        };
+        groups
+            .entry(folder)
+            .or_default()
+            .push(ArchivedFile { path: parts.join("/"), data, mode });
+    }
+    if groups.is_empty() {
+        return Err("Archive contains no skills".into());
+    }
+
+    groups
+        .into_iter()
+        .map(|(folder, mut files)| {
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            let label = if folder.is_empty() { "archive root" } else { folder.as_str() };
+            let md = files
+                .iter()
+                .find(|f| f.path == SKILL_FILE)
+                .ok_or_else(|| format!("Missing SKILL.md in {}", label))?;
+            let content = String::from_utf8(md.data.clone())
+                .map_err(|_| format!("SKILL.md in {} is not valid UTF-8", label))?;
+            let skill = parse_skill_md(&content, PathBuf::new())
+                .map_err(|d| format!("Invalid SKILL.md in {}: {}", label, d.reason))?;
+            Ok(ArchivedSkill { skill, files })
+        })
+        .collect()
+}
+
+fn collect_files(dir: &Path, rel: &str, out: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel_path = if rel.is_empty() { name } else { format!("{}/{}", rel, name) };
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_files(&path, &rel_path, out)?;
+        } else if file_type.is_file() || (file_type.is_symlink() && path.is_file()) {
+            out.push((rel_path, path));
+        }
+    }
+    Ok(())
+}
+
+/// Writes the skill folders into a new archive at `dest`, one top-level
+/// folder per skill named after it. Returns the archive entries, sorted.
+pub fn write_skill_archive(skills: &[Skill], dest: &Path) -> Result<Vec<String>, String> {
+    if skills.is_empty() {
+        return Err("No skills to export".into());
+    }
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let mut written = Vec::new();
+
+    for skill in skills {
+        if !skill.path.join(SKILL_FILE).is_file() {
+            return Err(format!("Skill directory not found: {}", skill.path.display()));
+        }
+        let mut files = Vec::new();
+        collect_files(&skill.path, "", &mut files)?;
+        files.sort();
+
+        for (rel, path) in files {
+            let name = format!("{}/{}", skill.name, rel);
+            let meta = fs::metadata(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .unix_permissions(file_mode(&meta));
+            let data =
+                fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            zip.start_file(name.as_str(), options)
+                .and_then(|_| zip.write_all(&data).map_err(Into::into))
+                .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+            written.push(name);
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    written.sort();
+    Ok(written)
+}
+
+#[cfg(unix)]
+fn file_mode(meta: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o777
+}
+
+#[cfg(not(unix))]
+fn file_mode(_meta: &fs::Metadata) -> u32 {
+    0o644
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sample_skill(root: &Path, name: &str) -> Skill {
+        let dir = root.join(name);
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        let md = format!("---\nname: {}\ndescription: Sample\n---\n\nbody\n", name);
+        fs::write(dir.join(SKILL_FILE), &md).unwrap();
+        fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        parse_skill_md(&md, dir).unwrap()
+    }
+
+    fn raw_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, body) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn export_then_import_round_trip() {
+        let tmp = TempDir::new("round");
+        let root = tmp.path();
+        let skills = vec![sample_skill(root, "alpha"), sample_skill(root, "beta")];
+        let archive = root.join("out.zip");
+
+        let entries = write_skill_archive(&skills, &archive).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert!(entries.contains(&"alpha/scripts/run.sh".to_string()));
+
+        let read = read_skill_archive(&archive).unwrap();
+        let names: Vec<_> = read.iter().map(|s| s.skill.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+        assert_eq!(read[0].files(), vec!["SKILL.md", "scripts/run.sh"]);
+
+        let dest = root.join("dest");
+        let written = read[0].extract_resources(&dest).unwrap();
+        assert_eq!(written, vec!["scripts/run.sh"]);
+        assert!(!dest.join(SKILL_FILE).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = TempDir::new("mode");
+        let root = tmp.path();
+        let skill = sample_skill(root, "alpha");
+        let script = skill.path.join("scripts/run.sh");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let archive = root.join("alpha.skill");
+        write_skill_archive(&[skill], &archive).unwrap();
+
+        let dest = root.join("dest");
+        read_skill_archive(&archive).unwrap()[0].extract_resources(&dest).unwrap();
+        let mode = fs::metadata(dest.join("scripts/run.sh")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn single_skill_at_root() {
+        let tmp = TempDir::new("single");
+        let root = tmp.path();
+        let archive = root.join("one.skill");
+        raw_zip(
+            &archive,
+            &[("SKILL.md", "---\nname: one\ndescription: d\n---\nbody"), ("notes.md", "n")],
+        );
+        let read = read_skill_archive(&archive).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].skill.name, "one");
+        assert_eq!(read[0].files(), vec!["SKILL.md", "notes.md"]);
+    }
+
+    #[test]
+    fn rejects_traversal_and_bad_frontmatter() {
+        let tmp = TempDir::new("bad");
+        let root = tmp.path();
+        let archive = root.join("bad.zip");
+
+        raw_zip(&archive, &[("s/SKILL.md", "---\nname: s\n---\n"), ("s/../../evil", "x")]);
+        let err = read_skill_archive(&archive).unwrap_err();
+        assert!(err.contains("escapes"), "{}", err);
+
+        raw_zip(&archive, &[("/abs/SKILL.md", "---\nname: s\n---\n")]);
+        assert!(read_skill_archive(&archive).unwrap_err().contains("escapes"));
+
+        raw_zip(&archive, &[("s/SKILL.md", "no frontmatter")]);
+        assert!(read_skill_archive(&archive).unwrap_err().contains("Invalid SKILL.md in s"));
+
+        raw_zip(&archive, &[("s/notes.md", "x")]);
+        assert!(read_skill_archive(&archive).unwrap_err().contains("Missing SKILL.md"));
+    }
+}
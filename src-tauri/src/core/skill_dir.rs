@@ -0,0 +1,193 @@
+//! Copies a skill's whole folder (scripts, references, assets, templates),
+//! not just `SKILL.md`.
+
+use crate::core::{check_skill_name, parse_skill_md, render_skill_md, ConfigAdapter, ConfigScope};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SKILL_FILE: &str = "SKILL.md";
+
+/// Recursively copies `src` into `dest`, keeping file modes so scripts stay
+/// executable. Top-level entries named in `skip` are left out. Symlinked
+/// files are copied as regular files; symlinked directories are skipped to
+/// avoid cycles. Returns the copied files relative to `dest`, sorted, with
+/// `/` separators.
+pub fn copy_skill_dir(src: &Path, dest: &Path, skip: &[&str]) -> Result<Vec<String>, String> {
+    if !src.is_dir() {
+        return Err(format!("Skill directory not found: {}", src.display()));
+    }
+    let (src_real, dest_real) = (resolve(src), resolve(dest));
+    if dest_real.starts_with(&src_real) || src_real.starts_with(&dest_real) {
+        return Err(format!("Cannot copy {} into {}", src.display(), dest.display()));
+    }
+
+    let mut copied = Vec::new();
+    copy_recursive(src, dest, Path::new(""), skip, &mut copied)?;
+    copied.sort();
+    Ok(copied)
+}
+
+/// The folder skill `name` was scanned from, which need not be
+/// `skill_dir(name)`: the folder name can differ, or the tool may have found
+/// it in a fallback location.
+pub fn find_skill_dir(
+    adapter: &dyn ConfigAdapter,
+    name: &str,
+    scope: &ConfigScope,
+) -> Result<PathBuf, String> {
+    adapter
+        .read_skills(scope)?
+        .skills
+        .into_iter()
+        .find(|s| s.name == name)
+        .map(|s| s.path)
+        .ok_or_else(|| format!("Skill not found: {}", name))
+}
+
+/// Renames the skill folder `from` to `new` in the same parent folder and
+/// rewrites the frontmatter `name`, keeping every other file.
+pub fn rename_skill_dir(from: &Path, new: &str) -> Result<(), String> {
+    if let Some(msg) = check_skill_name(new) {
+        return Err(msg);
+    }
+    let to = from.with_file_name(new);
+    let skill_file = from.join(SKILL_FILE);
+    let content = fs::read_to_string(&skill_file)
+        .map_err(|e| format!("Failed to read {}: {}", skill_file.display(), e))?;
+    if fs::symlink_metadata(&to).is_ok() {
+        return Err(format!("Skill already exists: {}", new));
+    }
+    let mut skill = parse_skill_md(&content, to.clone()).map_err(|d| d.reason)?;
+    skill.name = new.to_string();
+    let content = render_skill_md(&skill)?;
+
+    fs::rename(from, &to).map_err(|e| {
+        format!("Failed to rename {} to {}: {}", from.display(), to.display(), e)
+    })?;
+    let path = to.join(SKILL_FILE);
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Canonicalizes the longest existing prefix of `path`, so paths that do not
+/// exist yet can still be compared.
+fn resolve(path: &Path) -> PathBuf {
+    let mut rest = Vec::new();
+    let mut cur = path;
+    loop {
+        if let Ok(real) = fs::canonicalize(cur) {
+            return rest.iter().rev().fold(real, |acc, c| acc.join(c));
+        }
+        match (cur.parent(), cur.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                cur = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+fn copy_recursive(
+    src: &Path,
+    dest: &Path,
+    rel: &Path,
+    skip: &[&str],
+    copied: &mut Vec<String>,
+) -> Result<(), String> {
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("Failed to create dir {}: {}", dest.display(), e))?;
+    let entries =
+        fs::read_dir(src).map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if rel.as_os_str().is_empty() && skip.iter().any(|s| name == *s) {
+            continue;
+        }
+        let from = entry.path();
+        let to = dest.join(&name);
+        let rel_path: PathBuf = rel.join(&name);
+
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            copy_recursive(&from, &to, &rel_path, skip, copied)?;
+        } else if file_type.is_file() || (file_type.is_symlink() && from.is_file()) {
+            // fs::copy also copies the permission bits.
+            fs::copy(&from, &to)
+                .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+            copied.push(rel_path.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sample_skill(root: &Path) -> PathBuf {
+        let src = root.join("src-skill");
+        fs::create_dir_all(src.join("scripts")).unwrap();
+        fs::create_dir_all(src.join("references/deep")).unwrap();
+        fs::write(src.join(SKILL_FILE), "---\nname: s\n---\nbody").unwrap();
+        fs::write(src.join("scripts/run.sh"), "#!/bin/sh\necho hi\n").unwrap();
+        fs::write(src.join("references/deep/notes.md"), "notes").unwrap();
+        src
+    }
+
+    #[test]
+    fn copies_full_tree() {
+        let tmp = TempDir::new("full");
+        let root = tmp.path();
+        let src = sample_skill(root);
+        let dest = root.join("dest");
+
+        let files = copy_skill_dir(&src, &dest, &[]).unwrap();
+        assert_eq!(
+            files,
+            vec!["SKILL.md", "references/deep/notes.md", "scripts/run.sh"]
+        );
+        assert_eq!(fs::read_to_string(dest.join("references/deep/notes.md")).unwrap(), "notes");
+    }
+
+    #[test]
+    fn skips_top_level_entries() {
+        let tmp = TempDir::new("skip");
+        let root = tmp.path();
+        let src = sample_skill(root);
+        let dest = root.join("dest");
+
+        let files = copy_skill_dir(&src, &dest, &[SKILL_FILE]).unwrap();
+        assert!(!files.contains(&SKILL_FILE.to_string()));
+        assert!(!dest.join(SKILL_FILE).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = TempDir::new("mode");
+        let root = tmp.path();
+        let src = sample_skill(root);
+        let script = src.join("scripts/run.sh");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let dest = root.join("dest");
+
+        copy_skill_dir(&src, &dest, &[]).unwrap();
+        let mode = fs::metadata(dest.join("scripts/run.sh")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn rejects_copy_into_itself() {
+        let tmp = TempDir::new("nested");
+        let root = tmp.path();
+        let src = sample_skill(root);
+        assert!(copy_skill_dir(&src, &src.join("inner"), &[]).is_err());
+        assert!(copy_skill_dir(&src, &src, &[]).is_err());
+        assert!(!src.join("inner").exists());
+    }
+}
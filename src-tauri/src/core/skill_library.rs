@@ -0,0 +1,225 @@
+//! Central skill library owned by the hub. Tools get symlinks into it instead
+//! of their own copies, so a skill edited once is updated everywhere.
+
+use crate::core::copy_skill_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static LIBRARY_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillCopyMode {
+    #[default]
+    Copy,
+    Link,
+}
+
+/// How a tool's skill folder relates to the library entry of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillLinkState {
+    /// Symlink into the library.
+    Linked,
+    /// Own copy, identical to the library entry.
+    Synced,
+    /// Own copy that differs from the library entry.
+    Diverged,
+    /// Not in the library.
+    Unmanaged,
+}
+
+pub fn init_skill_library(dir: PathBuf) {
+    let _ = LIBRARY_DIR.set(dir);
+}
+
+pub fn library_dir() -> Result<PathBuf, String> {
+    LIBRARY_DIR
+        .get()
+        .cloned()
+        .ok_or_else(|| "Skill library is not initialized".to_string())
+}
+
+/// Puts the skill folder `src` into `library` as `name`, unless an identical
+/// entry is already there. Returns the library entry.
+pub fn add_to_library(library: &Path, src: &Path, name: &str) -> Result<PathBuf, String> {
+    let entry = library.join(name);
+    if entry.exists() {
+        if is_linked_to(src, &entry) || dir_fingerprint(src)? == dir_fingerprint(&entry)? {
+            return Ok(entry);
+        }
+        return Err(format!(
+            "The library already has a different `{}`; resolve the divergence first",
+            name
+        ));
+    }
+    copy_skill_dir(src, &entry, &[])?;
+    Ok(entry)
+}
+
+/// Replaces `dest` with a symlink to `entry`. An existing `dest` must already
+/// match the entry so nothing is lost.
+pub fn link_to_library(entry: &Path, dest: &Path) -> Result<(), String> {
+    if is_linked_to(dest, entry) {
+        return Ok(());
+    }
+    if fs::symlink_metadata(dest).is_ok() {
+        if dir_fingerprint(dest)? != dir_fingerprint(entry)? {
+            return Err(format!("{} differs from the library copy", dest.display()));
+        }
+        remove_skill_path(dest)?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create dir {}: {}", parent.display(), e))?;
+    }
+    symlink_dir(entry, dest)
+        .map_err(|e| format!("Failed to link {} to {}: {}", dest.display(), entry.display(), e))
+}
+
+/// Replaces the symlink `dest` with its own copy of `entry`, the reverse of
+/// `link_to_library`. The library entry is left in place.
+pub fn unlink_from_library(entry: &Path, dest: &Path) -> Result<(), String> {
+    if !is_linked_to(dest, entry) {
+        return Err(format!("{} is not linked to the library", dest.display()));
+    }
+    remove_skill_path(dest)?;
+    if let Err(e) = copy_skill_dir(entry, dest, &[]) {
+        let _ = fs::remove_dir_all(dest);
+        let _ = symlink_dir(entry, dest);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn remove_skill_path(path: &Path) -> Result<(), String> {
+    let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    let result = if is_link {
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    } else {
+        fs::remove_dir_all(path)
+    };
+    result.map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
+
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+fn is_linked_to(path: &Path, entry: &Path) -> bool {
+    let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    is_link
+        && match (path.canonicalize(), entry.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+}
+
+pub fn link_state(library: &Path, skill_dir: &Path, name: &str) -> SkillLinkState {
+    let entry = library.join(name);
+    if !entry.is_dir() {
+        return SkillLinkState::Unmanaged;
+    }
+    if is_linked_to(skill_dir, &entry) {
+        return SkillLinkState::Linked;
+    }
+    match (dir_fingerprint(skill_dir), dir_fingerprint(&entry)) {
+        (Ok(a), Ok(b)) if a == b => SkillLinkState::Synced,
+        _ => SkillLinkState::Diverged,
+    }
+}
+
+/// Relative path and content hash of every file under `dir`.
+fn dir_fingerprint(dir: &Path) -> Result<BTreeMap<PathBuf, u64>, String> {
+    fn walk(dir: &Path, root: &Path, out: &mut BTreeMap<PathBuf, u64>) -> Result<(), String> {
+        let entries =
+            fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, root, out)?;
+            } else if path.is_file() {
+                let data = fs::read(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                let mut hasher = DefaultHasher::new();
+                data.hash(&mut hasher);
+                let rel = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                out.insert(rel, hasher.finish());
+            }
+        }
+        Ok(())
+    }
+    let mut out = BTreeMap::new();
+    walk(dir, dir, &mut out)?;
+    Ok(out)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn write_skill(dir: &Path, body: &str) {
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\nname: s\n---\n{}", body)).unwrap();
+        fs::write(dir.join("scripts/run.sh"), "echo").unwrap();
+    }
+
+    #[test]
+    fn link_replaces_identical_copies() {
+        let tmp = TempDir::new("link");
+        let root = tmp.path();
+        let library = root.join("library");
+        let claude = root.join("claude/s");
+        let codex = root.join("codex/s");
+        write_skill(&claude, "body");
+
+        let entry = add_to_library(&library, &claude, "s").unwrap();
+        assert_eq!(link_state(&library, &claude, "s"), SkillLinkState::Synced);
+
+        link_to_library(&entry, &claude).unwrap();
+        link_to_library(&entry, &codex).unwrap();
+        assert_eq!(link_state(&library, &claude, "s"), SkillLinkState::Linked);
+        assert_eq!(link_state(&library, &codex, "s"), SkillLinkState::Linked);
+        assert!(codex.join("scripts/run.sh").is_file());
+
+        // Linking again, or adding a linked copy, is a no-op.
+        link_to_library(&entry, &codex).unwrap();
+        assert_eq!(add_to_library(&library, &codex, "s").unwrap(), entry);
+
+        unlink_from_library(&entry, &codex).unwrap();
+        assert!(!fs::symlink_metadata(&codex).unwrap().file_type().is_symlink());
+        assert_eq!(link_state(&library, &codex, "s"), SkillLinkState::Synced);
+        assert!(entry.join("scripts/run.sh").is_file());
+        assert!(unlink_from_library(&entry, &codex).is_err());
+    }
+
+    #[test]
+    fn diverged_copies_are_not_replaced() {
+        let tmp = TempDir::new("diverged");
+        let root = tmp.path();
+        let library = root.join("library");
+        let a = root.join("a/s");
+        let b = root.join("b/s");
+        write_skill(&a, "one");
+        write_skill(&b, "two");
+
+        let entry = add_to_library(&library, &a, "s").unwrap();
+        assert_eq!(link_state(&library, &b, "s"), SkillLinkState::Diverged);
+        assert!(add_to_library(&library, &b, "s").is_err());
+        assert!(link_to_library(&entry, &b).is_err());
+        assert!(b.join("SKILL.md").is_file());
+        assert_eq!(link_state(&library, &b, "other"), SkillLinkState::Unmanaged);
+    }
+}
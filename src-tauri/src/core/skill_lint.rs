@@ -0,0 +1,271 @@
+//! Checks skills against the Agent Skills spec.
+
+use crate::core::{Skill, SkillDiagnostic};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const MAX_NAME_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+pub const MAX_BODY_LINES: usize = 500;
+/// Rough budget for the body once loaded, at ~4 characters per token.
+pub const MAX_BODY_TOKENS: usize = 5000;
+
+const RESOURCE_DIRS: &[&str] = &["scripts/", "references/", "assets/", "templates/"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillFinding {
+    pub skill: String,
+    pub rule: String,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+impl SkillFinding {
+    fn new(skill: &str, rule: &str, severity: LintSeverity, message: String) -> Self {
+        Self { skill: skill.to_string(), rule: rule.to_string(), severity, message }
+    }
+}
+
+impl From<&SkillDiagnostic> for SkillFinding {
+    fn from(d: &SkillDiagnostic) -> Self {
+        let skill = d
+            .path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let message = match d.line {
+            Some(line) => format!("{} (line {})", d.reason, line),
+            None => d.reason.clone(),
+        };
+        Self::new(&skill, "parse", LintSeverity::Error, message)
+    }
+}
+
+pub fn has_errors(findings: &[SkillFinding]) -> bool {
+    findings.iter().any(|f| f.severity == LintSeverity::Error)
+}
+
+pub fn check_skill_name(name: &str) -> Option<String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Some(format!("Name must be 1-{} characters", MAX_NAME_LEN));
+    }
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_chars || name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+        return Some(
+            "Name must use lowercase letters, digits and single hyphens, \
+             without leading or trailing hyphens"
+                .into(),
+        );
+    }
+    None
+}
+
+/// Relative file references in the body: markdown link targets and inline
+/// code spans that point into a resource folder.
+pub fn skill_references(body: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+
+    let mut rest = body;
+    while let Some(i) = rest.find("](") {
+        rest = &rest[i + 2..];
+        let Some(end) = rest.find(')') else { break };
+        let target = rest[..end].split_whitespace().next().unwrap_or("");
+        let target = target.trim_matches(|c| c == '<' || c == '>');
+        let target = target.split('#').next().unwrap_or("");
+        let external = target.contains("://") || target.starts_with("mailto:");
+        if !target.is_empty() && !external && !target.starts_with('/') {
+            refs.push(target.to_string());
+        }
+        rest = &rest[end..];
+    }
+
+    for (i, span) in body.split('`').enumerate() {
+        let span = span.trim();
+        if i % 2 == 1
+            && !span.contains(char::is_whitespace)
+            && RESOURCE_DIRS.iter().any(|d| span.starts_with(d))
+        {
+            refs.push(span.to_string());
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+/// Lints one skill whose folder is `dir`. References are only checked when
+/// the folder exists.
+pub fn lint_skill(skill: &Skill, dir: &Path) -> Vec<SkillFinding> {
+    let dir_name = dir.file_name().map(|n| n.to_string_lossy());
+    let exists = |target: &str| !dir.is_dir() || dir.join(target).exists();
+    lint_skill_files(skill, dir_name.as_deref(), exists)
+}
+
+/// Lints a skill without touching the filesystem: `dir_name` is its folder's
+/// name and `exists` says whether a referenced path is in the folder.
+pub fn lint_skill_files(
+    skill: &Skill,
+    dir_name: Option<&str>,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<SkillFinding> {
+    use LintSeverity::{Error, Warning};
+    let name = skill.name.as_str();
+    let mut out = Vec::new();
+
+    if let Some(msg) = check_skill_name(name) {
+        out.push(SkillFinding::new(name, "name-format", Error, msg));
+    }
+    if let Some(dir_name) = dir_name {
+        if dir_name != name {
+            out.push(SkillFinding::new(
+                name,
+                "name-matches-dir",
+                Warning,
+                format!("Folder `{}` does not match skill name `{}`", dir_name, name),
+            ));
+        }
+    }
+
+    match skill.description.as_deref().map(str::trim) {
+        None | Some("") => out.push(SkillFinding::new(
+            name,
+            "description-missing",
+            Error,
+            "Description is required".into(),
+        )),
+        Some(d) if d.chars().count() > MAX_DESCRIPTION_LEN => out.push(SkillFinding::new(
+            name,
+            "description-length",
+            Error,
+            format!(
+                "Description is {} characters, limit is {}",
+                d.chars().count(),
+                MAX_DESCRIPTION_LEN
+            ),
+        )),
+        Some(_) => {}
+    }
+
+    for target in skill_references(&skill.content) {
+        if !exists(&target) {
+            out.push(SkillFinding::new(
+                name,
+                "missing-reference",
+                Warning,
+                format!("Referenced file not found in skill folder: {}", target),
+            ));
+        }
+    }
+
+    let lines = skill.content.lines().count();
+    let tokens = skill.content.chars().count() / 4;
+    if lines > MAX_BODY_LINES || tokens > MAX_BODY_TOKENS {
+        out.push(SkillFinding::new(
+            name,
+            "body-size",
+            Warning,
+            format!(
+                "Body is {} lines (~{} tokens); keep it under {} lines and move details \
+                 into references/",
+                lines, tokens, MAX_BODY_LINES
+            ),
+        ));
+    }
+
+    out
+}
+
+/// Flags project skills that shadow a global skill of the same name.
+pub fn lint_duplicates(global: &[Skill], project: &[Skill]) -> Vec<SkillFinding> {
+    project
+        .iter()
+        .filter(|p| global.iter().any(|g| g.name == p.name))
+        .map(|p| {
+            SkillFinding::new(
+                &p.name,
+                "duplicate-name",
+                LintSeverity::Warning,
+                format!("`{}` also exists in the global scope", p.name),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn skill(name: &str, content: &str) -> Skill {
+        Skill {
+            name: name.into(),
+            description: Some("Does things".into()),
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    fn rules(findings: &[SkillFinding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn name_format() {
+        assert!(check_skill_name("pdf-tools-2").is_none());
+        for bad in ["", "Upper", "a--b", "-a", "a-", "a_b", &"x".repeat(65)] {
+            assert!(check_skill_name(bad).is_some(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn description_and_dir() {
+        let mut s = skill("good", "body");
+        s.description = Some("x".repeat(MAX_DESCRIPTION_LEN + 1));
+        let findings = lint_skill(&s, Path::new("/skills/other"));
+        assert_eq!(rules(&findings), vec!["name-matches-dir", "description-length"]);
+        assert!(has_errors(&findings));
+
+        s.description = None;
+        assert!(rules(&lint_skill(&s, Path::new("/skills/good"))).contains(&"description-missing"));
+    }
+
+    #[test]
+    fn missing_references() {
+        let tmp = TempDir::new("lint");
+        let dir = tmp.path().join("refs");
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("scripts/ok.sh"), "").unwrap();
+
+        let body = "Run `scripts/ok.sh`, see [guide](references/guide.md#top) \
+                    and [site](https://example.com). Also `scripts/gone.py`.";
+        let findings = lint_skill(&skill("refs", body), &dir);
+        let messages: Vec<_> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(findings.len(), 2, "{:?}", messages);
+        assert!(messages.iter().any(|m| m.ends_with("references/guide.md")));
+        assert!(messages.iter().any(|m| m.ends_with("scripts/gone.py")));
+    }
+
+    #[test]
+    fn body_size_and_duplicates() {
+        let big = "line\n".repeat(MAX_BODY_LINES + 1);
+        let findings = lint_skill(&skill("big", &big), &PathBuf::from("big"));
+        assert_eq!(rules(&findings), vec!["body-size"]);
+        assert!(!has_errors(&findings));
+
+        let dupes = lint_duplicates(&[skill("a", ""), skill("b", "")], &[skill("b", "")]);
+        assert_eq!(rules(&dupes), vec!["duplicate-name"]);
+    }
+}
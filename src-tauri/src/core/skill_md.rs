@@ -0,0 +1,294 @@
+//! `SKILL.md` frontmatter parsing and rendering shared by all adapters.
+
+use crate::core::{AllowedTools, Skill, SkillDiagnostic, SkillScan, SKILL_FILE};
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(super) struct Frontmatter<'a> {
+    pub yaml: String,
+    pub body: &'a str,
+    /// Line of the opening `---`.
+    pub line: usize,
+}
+
+/// Splits `SKILL.md` into its YAML frontmatter and body. Accepts a UTF-8 BOM
+/// and CRLF line endings.
+pub(super) fn split_frontmatter(content: &str) -> Result<Frontmatter<'_>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut pos = 0;
+    let mut opened: Option<(usize, usize)> = None;
+
+    for (idx, raw) in content.split_inclusive('\n').enumerate() {
+        let text = raw.trim_end_matches(['\n', '\r']).trim_end();
+        let start = pos;
+        pos += raw.len();
+        match opened {
+            None if text.trim().is_empty() => {}
+            None if text == "---" => opened = Some((pos, idx + 1)),
+            None => return Err("Missing `---` frontmatter at the top of SKILL.md".into()),
+            Some((yaml_start, line)) if text == "---" => {
+                return Ok(Frontmatter {
+                    yaml: content[yaml_start..start].replace("\r\n", "\n"),
+                    body: content[pos..].trim_start(),
+                    line,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    Err(match opened {
+        Some(_) => "Frontmatter is not closed with `---`".into(),
+        None => "SKILL.md is empty".into(),
+    })
+}
+
+/// Takes `key` out of `map` if it has the expected type; otherwise leaves it
+/// there so it is written back untouched.
+pub(super) fn take<T: DeserializeOwned>(map: &mut Mapping, key: &str) -> Option<T> {
+    let value = map.get(key)?.clone();
+    let typed = serde_yaml::from_value(value).ok()?;
+    map.shift_remove(key);
+    Some(typed)
+}
+
+/// Parses the `SKILL.md` of the skill folder at `dir`.
+pub fn parse_skill_md(content: &str, dir: PathBuf) -> Result<Skill, SkillDiagnostic> {
+    let diag = |reason: String, line: Option<usize>| SkillDiagnostic {
+        path: dir.join(SKILL_FILE),
+        reason,
+        line,
+    };
+    let fm = split_frontmatter(content).map_err(|e| diag(e, None))?;
+    let value: Value = serde_yaml::from_str(&fm.yaml).map_err(|e| {
+        let line = e.location().map(|l| fm.line + l.line());
+        diag(format!("Invalid YAML frontmatter: {}", e), line)
+    })?;
+    let mut map = match value {
+        Value::Mapping(m) => m,
+        Value::Null => Mapping::new(),
+        _ => return Err(diag("Frontmatter must be a mapping".into(), Some(fm.line + 1))),
+    };
+    if let Some(key) = non_string_key(&map) {
+        return Err(diag(format!("Frontmatter key {} must be a string", key), None));
+    }
+    let key_order = map.keys().filter_map(|k| k.as_str().map(String::from)).collect();
+    let name = match map.get("name") {
+        None => return Err(diag("Missing `name` in frontmatter".into(), None)),
+        Some(Value::String(_)) => take(&mut map, "name").unwrap_or_default(),
+        Some(_) => return Err(diag("`name` must be a string".into(), None)),
+    };
+    Ok(Skill {
+        name,
+        description: take(&mut map, "description"),
+        content: fm.body.to_string(),
+        path: dir,
+        license: take(&mut map, "license"),
+        allowed_tools: take(&mut map, "allowed-tools"),
+        metadata: take(&mut map, "metadata"),
+        version: take(&mut map, "version"),
+        model: take(&mut map, "model"),
+        extra: map,
+        key_order,
+    })
+}
+
+/// The first key anywhere in `map` that is not a string, rendered as YAML.
+/// Such keys have no JSON equivalent, so the skill could not reach the UI.
+fn non_string_key(map: &Mapping) -> Option<String> {
+    fn nested(value: &Value) -> Option<String> {
+        match value {
+            Value::Mapping(m) => non_string_key(m),
+            Value::Sequence(items) => items.iter().find_map(nested),
+            Value::Tagged(t) => nested(&t.value),
+            _ => None,
+        }
+    }
+    map.iter().find_map(|(k, v)| match k {
+        Value::String(_) => nested(v),
+        _ => Some(serde_yaml::to_string(k).unwrap_or_default().trim().to_string()),
+    })
+}
+
+/// Loads every `<dir>/<name>/SKILL.md`. Folders that fail to load are
+/// reported as diagnostics; hidden folders are ignored.
+pub fn scan_skills_dir(dir: &Path) -> Result<SkillScan, String> {
+    let mut scan = SkillScan::default();
+    if !dir.exists() {
+        return Ok(scan);
+    }
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let skill_file = path.join(SKILL_FILE);
+        match fs::read_to_string(&skill_file) {
+            Ok(content) => match parse_skill_md(&content, path) {
+                Ok(skill) => scan.skills.push(skill),
+                Err(d) => scan.diagnostics.push(d),
+            },
+            Err(e) => {
+                let reason = if e.kind() == std::io::ErrorKind::NotFound {
+                    "Missing SKILL.md".to_string()
+                } else {
+                    format!("Failed to read SKILL.md: {}", e)
+                };
+                scan.diagnostics.push(SkillDiagnostic { path: skill_file, reason, line: None });
+            }
+        }
+    }
+
+    scan.skills.sort_by(|a, b| a.name.cmp(&b.name));
+    scan.diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(scan)
+}
+
+/// Renders a skill back to `SKILL.md`. Keys keep the order they were read
+/// in; new keys follow, known ones first.
+pub fn render_skill_md(skill: &Skill) -> Result<String, String> {
+    let mut known = Mapping::new();
+    let mut put = |key: &str, value: Option<Value>| {
+        if let Some(v) = value {
+            known.insert(Value::from(key), v);
+        }
+    };
+    put("name", Some(Value::from(skill.name.as_str())));
+    put("description", skill.description.as_deref().map(Value::from));
+    put("license", skill.license.as_deref().map(Value::from));
+    put(
+        "allowed-tools",
+        skill.allowed_tools.as_ref().map(|t| match t {
+            AllowedTools::Text(s) => Value::from(s.as_str()),
+            AllowedTools::List(l) => Value::from(l.clone()),
+        }),
+    );
+    put("metadata", skill.metadata.clone().map(Value::Mapping));
+    put("version", skill.version.as_deref().map(Value::from));
+    put("model", skill.model.as_deref().map(Value::from));
+    let mut extra = skill.extra.clone();
+    let mut map = Mapping::new();
+    for key in &skill.key_order {
+        let value = known.shift_remove(key.as_str()).or_else(|| extra.shift_remove(key.as_str()));
+        if let Some(v) = value {
+            map.insert(Value::from(key.as_str()), v);
+        }
+    }
+    for (k, v) in known.into_iter().chain(extra) {
+        if !map.contains_key(&k) {
+            map.insert(k, v);
+        }
+    }
+
+    if let Some(key) = non_string_key(&map) {
+        return Err(format!("Frontmatter key {} must be a string", key));
+    }
+
+    let yaml = serde_yaml::to_string(&map)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    let yaml_clean = yaml.trim_start_matches("---\n");
+    Ok(format!("---\n{}---\n\n{}", yaml_clean, skill.content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = "---\nname: pdf-tools\ndescription: Work with PDFs\nlicense: MIT\nallowed-tools: Read Grep\nmetadata:\n  author: me\n  team: docs\nversion: 1.2.0\nmodel: opus\nx-custom:\n- a\n- b\nhooks: true\n---\n\n# Body\n";
+
+    #[test]
+    fn parses_known_and_extra_keys() {
+        let skill = parse_skill_md(FULL, PathBuf::from("/s")).unwrap();
+        assert_eq!(skill.name, "pdf-tools");
+        assert_eq!(skill.license.as_deref(), Some("MIT"));
+        assert_eq!(skill.allowed_tools, Some(AllowedTools::Text("Read Grep".into())));
+        assert_eq!(skill.version.as_deref(), Some("1.2.0"));
+        assert_eq!(skill.model.as_deref(), Some("opus"));
+        assert_eq!(skill.metadata.as_ref().unwrap().len(), 2);
+        let extra: Vec<_> = skill.extra.keys().filter_map(|k| k.as_str()).collect();
+        assert_eq!(extra, vec!["x-custom", "hooks"]);
+        assert_eq!(skill.content, "# Body\n");
+    }
+
+    #[test]
+    fn round_trip_is_stable() {
+        let skill = parse_skill_md(FULL, PathBuf::new()).unwrap();
+        let rendered = render_skill_md(&skill).unwrap();
+        assert_eq!(rendered, FULL);
+    }
+
+    #[test]
+    fn round_trip_keeps_key_order() {
+        let src = "---\nx-custom: 1\ndescription: d\nname: s\nversion: '1'\n---\n\nbody";
+        let mut skill = parse_skill_md(src, PathBuf::new()).unwrap();
+        assert_eq!(render_skill_md(&skill).unwrap(), src);
+
+        skill.license = Some("MIT".into());
+        let rendered = render_skill_md(&skill).unwrap();
+        let keys = "---\nx-custom: 1\ndescription: d\nname: s\nversion: '1'\nlicense: MIT\n";
+        assert!(rendered.starts_with(keys), "{}", rendered);
+    }
+
+    #[test]
+    fn non_string_keys_are_rejected() {
+        let err = parse_skill_md("---\nname: s\n1: one\n---\n", PathBuf::new()).unwrap_err();
+        assert_eq!(err.reason, "Frontmatter key 1 must be a string");
+        let err = parse_skill_md("---\nname: s\nx:\n- true: y\n---\n", PathBuf::new()).unwrap_err();
+        assert_eq!(err.reason, "Frontmatter key true must be a string");
+
+        let mut skill = parse_skill_md("---\nname: s\n---\n", PathBuf::new()).unwrap();
+        skill.extra.insert(Value::from(2), Value::from("two"));
+        assert!(render_skill_md(&skill).is_err());
+    }
+
+    #[test]
+    fn mistyped_known_key_is_kept_as_is() {
+        let src = "---\nname: s\nversion: 2\n---\nbody";
+        let skill = parse_skill_md(src, PathBuf::new()).unwrap();
+        assert!(skill.version.is_none());
+        let rendered = render_skill_md(&skill).unwrap();
+        assert!(rendered.contains("version: 2\n"));
+    }
+
+    #[test]
+    fn allowed_tools_list() {
+        let src = "---\nname: s\nallowed-tools:\n- Read\n- Bash(git:*)\n---\nbody";
+        let skill = parse_skill_md(src, PathBuf::new()).unwrap();
+        assert_eq!(
+            skill.allowed_tools,
+            Some(AllowedTools::List(vec!["Read".into(), "Bash(git:*)".into()]))
+        );
+        assert!(render_skill_md(&skill).unwrap().contains("- Bash(git:*)"));
+    }
+
+    #[test]
+    fn missing_name_is_rejected() {
+        let err = parse_skill_md("---\ndescription: x\n---\nbody", PathBuf::new()).unwrap_err();
+        assert!(err.reason.contains("name"));
+        assert!(parse_skill_md("no frontmatter", PathBuf::new()).is_err());
+        assert!(parse_skill_md("---\nname: x\n", PathBuf::new()).is_err());
+    }
+
+    #[test]
+    fn yaml_error_reports_file_line() {
+        // Line 4 of the file; the opening `---` is on line 2.
+        let src = "\n---\nname: s\ndescription: a: b\nlicense: MIT\n---\nbody";
+        let err = parse_skill_md(src, PathBuf::from("/skills/s")).unwrap_err();
+        assert_eq!(err.path, PathBuf::from("/skills/s/SKILL.md"));
+        assert!(err.reason.starts_with("Invalid YAML"));
+        assert_eq!(err.line, Some(4), "{}", err.reason);
+    }
+
+    #[test]
+    fn accepts_bom_and_crlf() {
+        let src = "\u{feff}---\r\nname: s\r\ndescription: d\r\n---\r\n\r\nbody\r\n";
+        let skill = parse_skill_md(src, PathBuf::new()).unwrap();
+        assert_eq!(skill.name, "s");
+        assert_eq!(skill.description.as_deref(), Some("d"));
+        assert_eq!(skill.content, "body\r\n");
+    }
+}
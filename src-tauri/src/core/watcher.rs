@@ -1,4 +1,4 @@
-use crate::core::ToolType;
+use crate::core::{tools, ConfigScope, ToolType};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -15,6 +15,8 @@ pub struct ConfigChangeEvent {
     pub tool: ToolType,
     pub path: String,
     pub scope: String,
+    /// `config` for a tool's config directory, `commands` for its commands.
+    pub kind: String,
 }
 
 pub struct WriteGuard {
@@ -32,8 +34,11 @@ struct WatchRoot {
     path: PathBuf,
     tool: ToolType,
     scope: String,
+    kind: &'static str,
 }
 
+type RootMatch = (ToolType, String, &'static str);
+
 enum Msg {
     Event(notify::Result<notify::Event>),
     RemoveRoot(PathBuf),
@@ -75,35 +80,50 @@ impl FileWatcher {
     }
 
     pub fn start_global_watch(&self) -> Result<(), String> {
-        let home = dirs::home_dir().ok_or("No home directory")?;
-        let dirs = [
-            (home.join(".claude"), ToolType::ClaudeCode),
-            (home.join(".codex"), ToolType::Codex),
-            (home.join(".gemini"), ToolType::Gemini),
-            (home.join(".config/opencode"), ToolType::OpenCode),
-        ];
-        for (path, tool) in dirs {
-            self.add_root(path, tool, "global".into())?;
+        for (path, tool, kind) in global_roots() {
+            self.add_root(path, tool, "global".into(), kind)?;
         }
         Ok(())
     }
 
     pub fn watch_project(&self, project: &Path) -> Result<(), String> {
         let scope = project.to_string_lossy().into_owned();
-        let dirs = [
-            (project.join(".claude"), ToolType::ClaudeCode),
-            (project.join(".codex"), ToolType::Codex),
-            (project.join(".gemini"), ToolType::Gemini),
-            (project.join(".opencode"), ToolType::OpenCode),
-        ];
         let mut added = Vec::new();
-        for (path, tool) in dirs {
-            if self.add_root(path.clone(), tool, scope.clone())? {
+        for (path, tool, kind) in project_roots(project) {
+            if self.add_root(path.clone(), tool, scope.clone(), kind)? {
                 added.push(path);
             }
         }
-        if !added.is_empty() {
-            self.projects.lock().unwrap().insert(project.to_path_buf(), added);
+        self.projects.lock().unwrap().entry(project.to_path_buf()).or_default().extend(added);
+        Ok(())
+    }
+
+    /// Matches the roots to the current tool list after manifests were
+    /// reloaded: roots no tool uses any more are unwatched, new ones added.
+    pub fn reload_tools(&self) -> Result<(), String> {
+        let projects: Vec<PathBuf> = self.projects.lock().unwrap().keys().cloned().collect();
+        let mut expected: HashSet<PathBuf> = global_roots().into_iter().map(|r| r.0).collect();
+        for project in &projects {
+            expected.extend(project_roots(project).into_iter().map(|r| r.0));
+        }
+        let stale: Vec<PathBuf> = self
+            .roots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| !expected.contains(&r.path))
+            .map(|r| r.path.clone())
+            .collect();
+        for path in &stale {
+            self.remove_root(path);
+        }
+        for paths in self.projects.lock().unwrap().values_mut() {
+            paths.retain(|p| !stale.contains(p));
+        }
+
+        self.start_global_watch()?;
+        for project in &projects {
+            self.watch_project(project)?;
         }
         Ok(())
     }
@@ -127,7 +147,13 @@ impl FileWatcher {
         self.writing.lock().unwrap().remove(path);
     }
 
-    fn add_root(&self, path: PathBuf, tool: ToolType, scope: String) -> Result<bool, String> {
+    fn add_root(
+        &self,
+        path: PathBuf,
+        tool: ToolType,
+        scope: String,
+        kind: &'static str,
+    ) -> Result<bool, String> {
         if !path.is_dir() {
             return Ok(false);
         }
@@ -141,7 +167,7 @@ impl FileWatcher {
             w.watch(&path, RecursiveMode::NonRecursive)
                 .map_err(|e| e.to_string())?;
         }
-        roots.push(WatchRoot { path, tool, scope });
+        roots.push(WatchRoot { path, tool, scope, kind });
         Ok(true)
     }
 
@@ -170,6 +196,35 @@ impl Drop for FileWatcher {
     }
 }
 
+type Root = (PathBuf, ToolType, &'static str);
+
+fn global_roots() -> Vec<Root> {
+    let mut roots = Vec::new();
+    for tool in tools() {
+        if let Some(dir) = tool.adapter().commands_dir(&ConfigScope::Global) {
+            roots.push((dir, tool.id.clone(), "commands"));
+        }
+        if let Some(dir) = tool.global_dir {
+            roots.push((dir, tool.id, "config"));
+        }
+    }
+    roots
+}
+
+fn project_roots(project: &Path) -> Vec<Root> {
+    let scope = ConfigScope::Project(project.to_path_buf());
+    let mut roots = Vec::new();
+    for tool in tools() {
+        if let Some(dir) = tool.adapter().commands_dir(&scope) {
+            roots.push((dir, tool.id.clone(), "commands"));
+        }
+        if let Some(dir) = tool.project_dir {
+            roots.push((project.join(dir), tool.id, "config"));
+        }
+    }
+    roots
+}
+
 fn run_loop(
     app: tauri::AppHandle,
     rx: mpsc::Receiver<Msg>,
@@ -177,7 +232,7 @@ fn run_loop(
     writing: Arc<Mutex<HashSet<PathBuf>>>,
 ) {
     let debounce = Duration::from_millis(DEBOUNCE_MS);
-    let mut pending: HashMap<PathBuf, (ToolType, String, Instant)> = HashMap::new();
+    let mut pending: HashMap<PathBuf, (RootMatch, Instant)> = HashMap::new();
 
     loop {
         match rx.recv_timeout(Duration::from_millis(100)) {
@@ -192,8 +247,8 @@ fn run_loop(
                     continue;
                 }
                 for path in ev.paths {
-                    if let Some((tool, scope)) = find_root(&roots, &path) {
-                        pending.insert(path, (tool, scope, Instant::now()));
+                    if let Some(root) = find_root(&roots, &path) {
+                        pending.insert(path, (root, Instant::now()));
                     }
                 }
             }
@@ -201,7 +256,7 @@ fn run_loop(
         }
 
         let now = Instant::now();
-        pending.retain(|path, (tool, scope, ts)| {
+        pending.retain(|path, ((tool, scope, kind), ts)| {
             if now.duration_since(*ts) < debounce {
                 return true;
             }
@@ -212,9 +267,10 @@ fn run_loop(
             let _ = app.emit(
                 "config-changed",
                 ConfigChangeEvent {
-                    tool: *tool,
+                    tool: tool.clone(),
                     path: path.to_string_lossy().into_owned(),
                     scope: scope.clone(),
+                    kind: kind.to_string(),
                 },
             );
             false
@@ -222,14 +278,14 @@ fn run_loop(
     }
 }
 
-fn find_root(roots: &Arc<Mutex<Vec<WatchRoot>>>, path: &Path) -> Option<(ToolType, String)> {
+fn find_root(roots: &Arc<Mutex<Vec<WatchRoot>>>, path: &Path) -> Option<RootMatch> {
     roots
         .lock()
         .unwrap()
         .iter()
         .filter(|r| path.starts_with(&r.path))
         .max_by_key(|r| r.path.components().count())
-        .map(|r| (r.tool, r.scope.clone()))
+        .map(|r| (r.tool.clone(), r.scope.clone(), r.kind))
 }
 
 #[cfg(test)]
@@ -240,8 +296,9 @@ mod tests {
         Arc::new(Mutex::new(
             entries.iter().map(|(p, t, s)| WatchRoot {
                 path: PathBuf::from(p),
-                tool: *t,
+                tool: t.clone(),
                 scope: s.to_string(),
+                kind: "config",
             }).collect()
         ))
     }
@@ -249,19 +306,19 @@ mod tests {
     #[test]
     fn find_root_matches_exact() {
         let roots = make_roots(&[
-            ("/home/user/.claude", ToolType::ClaudeCode, "global"),
+            ("/home/user/.claude", ToolType::CLAUDE_CODE, "global"),
         ]);
         let result = find_root(&roots, Path::new("/home/user/.claude/.mcp.json"));
         assert!(result.is_some());
-        let (tool, scope) = result.unwrap();
-        assert_eq!(tool, ToolType::ClaudeCode);
+        let (tool, scope, _) = result.unwrap();
+        assert_eq!(tool, ToolType::CLAUDE_CODE);
         assert_eq!(scope, "global");
     }
 
     #[test]
     fn find_root_returns_none_for_unmatched() {
         let roots = make_roots(&[
-            ("/home/user/.claude", ToolType::ClaudeCode, "global"),
+            ("/home/user/.claude", ToolType::CLAUDE_CODE, "global"),
         ]);
         let result = find_root(&roots, Path::new("/home/user/.codex/config.toml"));
         assert!(result.is_none());
@@ -270,36 +327,52 @@ mod tests {
     #[test]
     fn find_root_prefers_longer_match() {
         let roots = make_roots(&[
-            ("/home/user/.claude", ToolType::ClaudeCode, "global"),
-            ("/home/user/project/.claude", ToolType::ClaudeCode, "/home/user/project"),
+            ("/home/user/.claude", ToolType::CLAUDE_CODE, "global"),
+            ("/home/user/project/.claude", ToolType::CLAUDE_CODE, "/home/user/project"),
         ]);
         let result = find_root(&roots, Path::new("/home/user/project/.claude/.mcp.json"));
         assert!(result.is_some());
-        let (_tool, scope) = result.unwrap();
+        let (_tool, scope, _) = result.unwrap();
         assert_eq!(scope, "/home/user/project");
     }
 
     #[test]
     fn find_root_matches_multiple_tools() {
         let roots = make_roots(&[
-            ("/home/user/.claude", ToolType::ClaudeCode, "global"),
-            ("/home/user/.codex", ToolType::Codex, "global"),
-            ("/home/user/.gemini", ToolType::Gemini, "global"),
+            ("/home/user/.claude", ToolType::CLAUDE_CODE, "global"),
+            ("/home/user/.codex", ToolType::CODEX, "global"),
+            ("/home/user/.gemini", ToolType::GEMINI, "global"),
         ]);
 
         let r1 = find_root(&roots, Path::new("/home/user/.codex/config.toml"));
-        assert_eq!(r1.unwrap().0, ToolType::Codex);
+        assert_eq!(r1.unwrap().0, ToolType::CODEX);
 
         let r2 = find_root(&roots, Path::new("/home/user/.gemini/settings.json"));
-        assert_eq!(r2.unwrap().0, ToolType::Gemini);
+        assert_eq!(r2.unwrap().0, ToolType::GEMINI);
+    }
+
+    #[test]
+    fn find_root_reports_commands_kind() {
+        let roots = make_roots(&[("/home/user/.codex", ToolType::CODEX, "global")]);
+        roots.lock().unwrap().push(WatchRoot {
+            path: PathBuf::from("/home/user/.codex/prompts"),
+            tool: ToolType::CODEX,
+            scope: "global".into(),
+            kind: "commands",
+        });
+        let (_, _, kind) = find_root(&roots, Path::new("/home/user/.codex/prompts/a.md")).unwrap();
+        assert_eq!(kind, "commands");
+        let (_, _, kind) = find_root(&roots, Path::new("/home/user/.codex/config.toml")).unwrap();
+        assert_eq!(kind, "config");
     }
 
     #[test]
     fn config_change_event_serialization() {
         let event = ConfigChangeEvent {
-            tool: ToolType::ClaudeCode,
+            tool: ToolType::CLAUDE_CODE,
             path: "/home/user/.claude/.mcp.json".into(),
             scope: "global".into(),
+            kind: "config".into(),
         };
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("ClaudeCode"));
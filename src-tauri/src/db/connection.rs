@@ -15,5 +15,27 @@ pub fn init_db(app_dir: &PathBuf) -> Result<Connection, String> {
         [],
     )
     .map_err(|e| e.to_string())?;
+    conn.execute_batch(
+        "PRAGMA foreign_keys = ON;
+        CREATE TABLE IF NOT EXISTS skill_sources (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL UNIQUE,
+            created_at INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS installed_skills (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_id INTEGER NOT NULL REFERENCES skill_sources(id) ON DELETE CASCADE,
+            name TEXT NOT NULL,
+            skill_path TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            project_path TEXT NOT NULL DEFAULT '',
+            commit_sha TEXT NOT NULL,
+            installed_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            UNIQUE (tool, project_path, name)
+        );",
+    )
+    .map_err(|e| e.to_string())?;
     Ok(conn)
 }
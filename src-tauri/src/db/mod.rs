@@ -1,7 +1,9 @@
 mod connection;
 mod projects;
+mod skill_sources;
 #[cfg(test)]
 mod tests;
 
 pub use connection::init_db;
 pub use projects::ProjectRepo;
+pub use skill_sources::SkillSourceRepo;
@@ -0,0 +1,179 @@
+use crate::core::{InstalledSkill, SkillSource, ToolType};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct SkillSourceRepo<'a> {
+    conn: &'a Connection,
+}
+
+const INSTALL_COLUMNS: &str = "id, source_id, name, skill_path, tool, project_path, commit_sha, \
+                               installed_at, updated_at";
+
+impl<'a> SkillSourceRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    fn now_ms() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0)
+    }
+
+    fn map_source(row: &rusqlite::Row) -> rusqlite::Result<SkillSource> {
+        Ok(SkillSource {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            url: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    }
+
+    fn map_install(row: &rusqlite::Row) -> rusqlite::Result<InstalledSkill> {
+        let project_path: String = row.get(5)?;
+        Ok(InstalledSkill {
+            id: row.get(0)?,
+            source_id: row.get(1)?,
+            name: row.get(2)?,
+            skill_path: row.get(3)?,
+            tool: ToolType::new(row.get::<_, String>(4)?),
+            project_path: Some(project_path).filter(|p| !p.is_empty()),
+            commit: row.get(6)?,
+            installed_at: row.get(7)?,
+            updated_at: row.get(8)?,
+        })
+    }
+
+    pub fn list(&self) -> Result<Vec<SkillSource>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, url, created_at FROM skill_sources ORDER BY name")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], Self::map_source).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }
+
+    pub fn add(&self, url: &str) -> Result<SkillSource, String> {
+        let url = url.trim();
+        if url.is_empty() {
+            return Err("Source URL cannot be empty".into());
+        }
+        let name = Path::new(url.trim_end_matches('/'))
+            .file_name()
+            .map(|s| s.to_string_lossy().trim_end_matches(".git").to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| url.to_string());
+        let now = Self::now_ms();
+        self.conn
+            .execute(
+                "INSERT INTO skill_sources (name, url, created_at) VALUES (?1, ?2, ?3)",
+                params![&name, url, now],
+            )
+            .map_err(|e| {
+                if e.to_string().contains("UNIQUE constraint failed") {
+                    "Skill source already exists".to_string()
+                } else {
+                    e.to_string()
+                }
+            })?;
+        Ok(SkillSource {
+            id: self.conn.last_insert_rowid(),
+            name,
+            url: url.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// Removes the source and forgets its installs; installed files stay.
+    pub fn remove(&self, id: i64) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM installed_skills WHERE source_id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        self.conn
+            .execute("DELETE FROM skill_sources WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<SkillSource>, String> {
+        self.conn
+            .query_row(
+                "SELECT id, name, url, created_at FROM skill_sources WHERE id = ?1",
+                params![id],
+                Self::map_source,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Records an install, replacing any earlier record for the same tool,
+    /// scope and name.
+    pub fn record_install(
+        &self,
+        source_id: i64,
+        name: &str,
+        skill_path: &str,
+        tool: &ToolType,
+        project_path: Option<&str>,
+        commit: &str,
+    ) -> Result<InstalledSkill, String> {
+        let now = Self::now_ms();
+        self.conn
+            .execute(
+                "INSERT INTO installed_skills
+                    (source_id, name, skill_path, tool, project_path, commit_sha, installed_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                 ON CONFLICT (tool, project_path, name) DO UPDATE SET
+                    source_id = excluded.source_id,
+                    skill_path = excluded.skill_path,
+                    commit_sha = excluded.commit_sha,
+                    updated_at = excluded.updated_at",
+                params![
+                    source_id,
+                    name,
+                    skill_path,
+                    tool.as_str(),
+                    project_path.unwrap_or(""),
+                    commit,
+                    now
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM installed_skills
+                     WHERE tool = ?1 AND project_path = ?2 AND name = ?3",
+                    INSTALL_COLUMNS
+                ),
+                params![tool.as_str(), project_path.unwrap_or(""), name],
+                Self::map_install,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn list_installs(&self) -> Result<Vec<InstalledSkill>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM installed_skills ORDER BY name, tool",
+                INSTALL_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], Self::map_install).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }
+
+    pub fn get_install(&self, id: i64) -> Result<Option<InstalledSkill>, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM installed_skills WHERE id = ?1", INSTALL_COLUMNS),
+                params![id],
+                Self::map_install,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+}
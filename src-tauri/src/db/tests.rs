@@ -1,4 +1,5 @@
-use crate::db::{init_db, ProjectRepo};
+use crate::core::ToolType;
+use crate::db::{init_db, ProjectRepo, SkillSourceRepo};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -191,3 +192,47 @@ fn project_timestamps() {
     assert!(p.updated_at > 0);
     assert_eq!(p.created_at, p.updated_at);
 }
+
+#[test]
+fn skill_source_add_and_remove() {
+    let tmp = TempDbDir::new();
+    let conn = init_db(tmp.path()).unwrap();
+    let repo = SkillSourceRepo::new(&conn);
+
+    let src = repo.add("file:///repos/team-skills.git").unwrap();
+    assert_eq!(src.name, "team-skills");
+    assert!(repo.add("file:///repos/team-skills.git").unwrap_err().contains("already exists"));
+    assert_eq!(repo.list().unwrap().len(), 1);
+
+    repo.record_install(src.id, "pdf", "skills/pdf", &ToolType::CODEX, None, "abc").unwrap();
+    repo.remove(src.id).unwrap();
+    assert!(repo.list().unwrap().is_empty());
+    assert!(repo.list_installs().unwrap().is_empty());
+}
+
+#[test]
+fn skill_install_is_upserted() {
+    let tmp = TempDbDir::new();
+    let conn = init_db(tmp.path()).unwrap();
+    let repo = SkillSourceRepo::new(&conn);
+    let src = repo.add("/repos/skills").unwrap();
+
+    let first = repo
+        .record_install(src.id, "pdf", "pdf", &ToolType::CLAUDE_CODE, Some("/p"), "aaa")
+        .unwrap();
+    let second = repo
+        .record_install(src.id, "pdf", "pdf", &ToolType::CLAUDE_CODE, Some("/p"), "bbb")
+        .unwrap();
+    assert_eq!(first.id, second.id);
+    assert_eq!(second.commit, "bbb");
+    assert_eq!(second.project_path.as_deref(), Some("/p"));
+    assert_eq!(second.installed_at, first.installed_at);
+
+    let global = repo
+        .record_install(src.id, "pdf", "pdf", &ToolType::CLAUDE_CODE, None, "aaa")
+        .unwrap();
+    assert_ne!(global.id, first.id);
+    assert!(global.project_path.is_none());
+    assert_eq!(repo.list_installs().unwrap().len(), 2);
+    assert_eq!(repo.get_install(first.id).unwrap().unwrap().commit, "bbb");
+}
@@ -2,9 +2,11 @@ mod adapters;
 mod commands;
 mod core;
 mod db;
+#[cfg(test)]
+mod test_util;
 
 use commands::{register_commands, DbState};
-use core::FileWatcher;
+use core::{init_manifests, init_skill_library, FileWatcher};
 use db::init_db;
 use std::sync::Mutex;
 use tauri::Manager;
@@ -20,6 +22,8 @@ pub fn run() {
             std::fs::create_dir_all(&app_dir).ok();
             let conn = init_db(&app_dir).expect("Failed to init database");
             app.manage(DbState(Mutex::new(conn)));
+            init_manifests(app_dir.join("tools"));
+            init_skill_library(app_dir.join("skills"));
 
             let watcher = FileWatcher::new(app.handle().clone())
                 .expect("Failed to create file watcher");
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { TOOLS, type ToolType, useConfigStore } from '../stores/configStore';
+import { type ToolType, useConfigStore } from '../stores/configStore';
 
 interface CopyMcpDialogProps {
   serverName: string;
@@ -16,7 +16,8 @@ interface ToolResult {
 
 export function CopyMcpDialog({ serverName, sourceTool, onClose }: CopyMcpDialogProps) {
   const copyMcpServer = useConfigStore((s) => s.copyMcpServer);
-  const targets = TOOLS.filter((t) => t !== sourceTool);
+  const tools = useConfigStore((s) => s.tools);
+  const targets = tools.filter((t) => t !== sourceTool);
 
   const [selected, setSelected] = useState<Set<ToolType>>(new Set());
   const [results, setResults] = useState<Partial<Record<ToolType, ToolResult>>>({});
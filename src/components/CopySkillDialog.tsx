@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { TOOLS, type ToolType, useConfigStore } from '../stores/configStore';
+import { type ToolType, useConfigStore } from '../stores/configStore';
 
 interface CopySkillDialogProps {
   skillName: string;
@@ -11,12 +11,14 @@ type CopyStatus = 'idle' | 'pending' | 'success' | 'skipped' | 'error';
 
 export function CopySkillDialog({ skillName, sourceTool, onClose }: CopySkillDialogProps) {
   const copySkill = useConfigStore((s) => s.copySkill);
-  const targets = TOOLS.filter((t) => t !== sourceTool);
+  const tools = useConfigStore((s) => s.tools);
+  const targets = tools.filter((t) => t !== sourceTool);
 
   const [selected, setSelected] = useState<Set<ToolType>>(new Set());
   const [results, setResults] = useState<Partial<Record<ToolType, CopyStatus>>>({});
   const [errors, setErrors] = useState<Partial<Record<ToolType, string>>>({});
   const [copying, setCopying] = useState(false);
+  const [link, setLink] = useState(false);
 
   const toggle = (tool: ToolType) => {
     setSelected((prev) => {
@@ -33,7 +35,7 @@ export function CopySkillDialog({ skillName, sourceTool, onClose }: CopySkillDia
     await Promise.all(
       [...selected].map(async (tool) => {
         try {
-          const res = await copySkill(sourceTool, tool, skillName);
+          const res = await copySkill(sourceTool, tool, skillName, link ? 'link' : 'copy');
           setResults((p) => ({ ...p, [tool]: res.skipped ? 'skipped' : 'success' }));
         } catch (e) {
           setResults((p) => ({ ...p, [tool]: 'error' }));
@@ -73,6 +75,12 @@ export function CopySkillDialog({ skillName, sourceTool, onClose }: CopySkillDia
           })}
         </div>
 
+        <label className="copy-target-label">
+          <input type="checkbox" checked={link}
+            onChange={(e) => setLink(e.target.checked)} disabled={copying || done} />
+          Link through the skill library instead of copying
+        </label>
+
         {Object.values(errors).some(Boolean) && (
           <div className="copy-warnings">
             {Object.entries(errors).map(([tool, msg]) =>
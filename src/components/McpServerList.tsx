@@ -1,5 +1,7 @@
 import { useEffect, useState } from 'react';
-import { useConfigStore, TOOLS, type ToolType, type McpServer } from '../stores/configStore';
+import {
+  useConfigStore, type ToolType, type McpServer, type GeminiExtension,
+} from '../stores/configStore';
 
 interface McpServerListProps {
   selectedTool?: ToolType;
@@ -9,10 +11,29 @@ interface McpServerListProps {
 }
 
 export function McpServerList({ selectedTool, selectedName, onSelect, onAdd }: McpServerListProps) {
-  const { mcpServers, fetchAllMcpServers, loading } = useConfigStore();
+  const {
+    tools, mcpServers, claudePlugins, geminiExtensions, fetchAllMcpServers, fetchClaudePlugins,
+    fetchGeminiExtensions, loading,
+  } = useConfigStore();
   const [collapsed, setCollapsed] = useState<Partial<Record<ToolType, boolean>>>({});
 
-  useEffect(() => { fetchAllMcpServers(); }, [fetchAllMcpServers]);
+  useEffect(() => {
+    fetchAllMcpServers();
+    fetchClaudePlugins();
+    fetchGeminiExtensions();
+  }, [fetchAllMcpServers, fetchClaudePlugins, fetchGeminiExtensions]);
+
+  // Servers Claude Code plugins and Gemini extensions bring along; shown read-only.
+  const pluginServers = (claudePlugins?.plugins ?? []).flatMap((plugin) =>
+    plugin.mcp_servers.map((server) => ({
+      key: `${plugin.id}/${server.name}`, origin: plugin.id, kind: 'plugin',
+      enabled: plugin.enabled, server,
+    })));
+  const extensionServers = geminiExtensions.flatMap((extension) =>
+    extension.mcp_servers.map((server) => ({
+      key: `${extension.name}/${server.name}`, origin: extension.name, kind: 'extension',
+      enabled: extension.enabled, server,
+    })));
 
   const toggle = (tool: ToolType) =>
     setCollapsed((p) => ({ ...p, [tool]: !p[tool] }));
@@ -20,8 +41,10 @@ export function McpServerList({ selectedTool, selectedName, onSelect, onAdd }: M
   return (
     <div className="mcp-list">
       {loading.mcp > 0 && <p className="mcp-loading">Loading...</p>}
-      {TOOLS.map((tool) => {
+      {tools.map((tool) => {
         const servers = mcpServers[tool];
+        const fromPlugins = tool === 'ClaudeCode' ? pluginServers
+          : tool === 'Gemini' ? extensionServers : [];
         const isCollapsed = !!collapsed[tool];
         return (
           <section key={tool} className="mcp-tool-section">
@@ -29,14 +52,14 @@ export function McpServerList({ selectedTool, selectedName, onSelect, onAdd }: M
               <button type="button" className="mcp-tool-toggle" onClick={() => toggle(tool)}>
                 <span className={`mcp-chevron${isCollapsed ? ' is-collapsed' : ''}`}>&#9660;</span>
                 <span className="mcp-tool-name">{tool}</span>
-                <span className="mcp-tool-count">{servers.length}</span>
+                <span className="mcp-tool-count">{servers.length + fromPlugins.length}</span>
               </button>
               <button type="button" className="mcp-add-btn"
                 onClick={() => onAdd(tool)}>+ Add</button>
             </div>
             {!isCollapsed && (
               <div className="mcp-tool-body">
-                {servers.length === 0 ? (
+                {servers.length === 0 && fromPlugins.length === 0 ? (
                   <p className="mcp-empty">No MCP servers configured</p>
                 ) : servers.map((s) => (
                   <div
@@ -58,6 +81,24 @@ export function McpServerList({ selectedTool, selectedName, onSelect, onAdd }: M
                     )}
                   </div>
                 ))}
+                {fromPlugins.map(({ key, origin, kind, enabled, server }) => (
+                  <div key={key} className="mcp-row is-readonly"
+                    title={`Provided by ${kind} ${origin}`}>
+                    <div className="mcp-row-info">
+                      <span className="mcp-row-name">
+                        {server.name}
+                        <span className="mcp-origin">{origin}</span>
+                      </span>
+                      <span className="mcp-row-cmd" title={server.command}>{server.command}</span>
+                    </div>
+                    <span className={`mcp-badge${enabled ? ' is-on' : ''}`}>
+                      {enabled ? 'ON' : 'OFF'}
+                    </span>
+                  </div>
+                ))}
+                {tool === 'Gemini' && geminiExtensions.length > 0 && (
+                  <GeminiExtensionRows extensions={geminiExtensions} />
+                )}
               </div>
             )}
           </section>
@@ -66,3 +107,57 @@ export function McpServerList({ selectedTool, selectedName, onSelect, onAdd }: M
     </div>
   );
 }
+
+/** Installed Gemini CLI extensions, which can be switched off or removed here. */
+function GeminiExtensionRows({ extensions }: { extensions: GeminiExtension[] }) {
+  const { setGeminiExtensionEnabled, uninstallGeminiExtension } = useConfigStore();
+  const [confirming, setConfirming] = useState<string | null>(null);
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const run = async (name: string, action: () => Promise<void>) => {
+    setBusy(name);
+    try {
+      await action();
+    } catch {
+      // The store reports the error.
+    } finally {
+      setBusy(null);
+      setConfirming(null);
+    }
+  };
+
+  return (
+    <>
+      <p className="mcp-subheading">Extensions</p>
+      {extensions.map((ext) => (
+        <div key={ext.name} className="mcp-row is-readonly" title={ext.error ?? ext.path}>
+          <div className="mcp-row-info">
+            <span className="mcp-row-name">
+              {ext.name}
+              {ext.version && <span className="mcp-origin">{ext.version}</span>}
+            </span>
+            <span className="mcp-row-cmd">
+              {ext.error ?? ext.description ?? `${ext.mcp_servers.length} MCP servers`}
+            </span>
+          </div>
+          <button type="button" className={`mcp-badge${ext.enabled ? ' is-on' : ''}`}
+            disabled={busy === ext.name}
+            onClick={() => run(ext.name, () => setGeminiExtensionEnabled(ext.name, !ext.enabled))}>
+            {ext.enabled ? 'ON' : 'OFF'}
+          </button>
+          {confirming === ext.name ? (
+            <button type="button" className="btn-danger mcp-row-action" disabled={busy === ext.name}
+              onClick={() => run(ext.name, () => uninstallGeminiExtension(ext.name))}>
+              Confirm
+            </button>
+          ) : (
+            <button type="button" className="btn-secondary mcp-row-action"
+              onClick={() => setConfirming(ext.name)}>
+              Uninstall
+            </button>
+          )}
+        </div>
+      ))}
+    </>
+  );
+}
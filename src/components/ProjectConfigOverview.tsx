@@ -1,16 +1,20 @@
 import { useEffect, useState } from 'react';
 import {
+  toolLabel,
   useConfigStore,
   type Project,
   type ProjectConfigSummary,
   type ToolType,
 } from '../stores/configStore';
 
-const TOOL_LABELS: Record<ToolType, string> = {
+const TOOL_LABELS: Partial<Record<ToolType, string>> = {
   ClaudeCode: 'Claude Code',
   Codex: 'Codex',
   Gemini: 'Gemini',
   OpenCode: 'OpenCode',
+  Zed: 'Zed',
+  Qwen: 'Qwen Code',
+  IFlow: 'iFlow CLI',
 };
 
 interface Props {
@@ -19,7 +23,7 @@ interface Props {
 }
 
 export function ProjectConfigOverview({ project, onRemove }: Props) {
-  const { getProjectConfigSummary } = useConfigStore();
+  const { toolDescriptors, getProjectConfigSummary } = useConfigStore();
   const [summaries, setSummaries] = useState<ProjectConfigSummary[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
@@ -56,7 +60,7 @@ export function ProjectConfigOverview({ project, onRemove }: Props) {
           {summaries.map((s) => (
             <div key={s.tool} className="project-summary-card">
               <span className="project-summary-tool">
-                {TOOL_LABELS[s.tool]}
+                {TOOL_LABELS[s.tool] ?? toolLabel(toolDescriptors, s.tool)}
               </span>
               <div className="project-summary-stats">
                 <span className="project-stat">
@@ -1,11 +1,14 @@
 import { useEffect } from 'react';
-import { useConfigStore, type Project, type ToolType } from '../stores/configStore';
+import { toolLabel, useConfigStore, type Project, type ToolType } from '../stores/configStore';
 
-const TOOL_LABELS: Record<ToolType, string> = {
+const TOOL_LABELS: Partial<Record<ToolType, string>> = {
   ClaudeCode: 'Claude',
   Codex: 'Codex',
   Gemini: 'Gemini',
   OpenCode: 'OpenCode',
+  Zed: 'Zed',
+  Qwen: 'Qwen',
+  IFlow: 'iFlow',
 };
 
 interface Props {
@@ -15,11 +18,12 @@ interface Props {
 }
 
 export function ProjectList({ selectedId, onSelect, onAdd }: Props) {
-  const { projects, loading, fetchProjects } = useConfigStore();
+  const { projects, toolDescriptors, loading, fetchProjects, fetchTools } = useConfigStore();
 
   useEffect(() => {
     fetchProjects();
-  }, [fetchProjects]);
+    fetchTools();
+  }, [fetchProjects, fetchTools]);
 
   return (
     <div className="project-list">
@@ -49,7 +53,7 @@ export function ProjectList({ selectedId, onSelect, onAdd }: Props) {
           <div className="project-tool-badges">
             {p.tools.map((t) => (
               <span key={t} className="tool-badge">
-                {TOOL_LABELS[t]}
+                {TOOL_LABELS[t] ?? toolLabel(toolDescriptors, t)}
               </span>
             ))}
           </div>
@@ -1,11 +1,14 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
-import { useConfigStore, TOOLS, type ToolType } from '../stores/configStore';
+import { useConfigStore, type ToolType } from '../stores/configStore';
 
-const TOOL_PATHS: Record<ToolType, string> = {
+const TOOL_PATHS: Partial<Record<ToolType, string>> = {
   ClaudeCode: '~/.claude/CLAUDE.md',
   Codex: '~/.codex/AGENTS.md',
   Gemini: '~/.gemini/GEMINI.md',
   OpenCode: '~/.config/opencode/AGENTS.md',
+  Zed: '.rules',
+  Qwen: '~/.qwen/QWEN.md',
+  IFlow: '~/.iflow/IFLOW.md',
 };
 
 interface ConflictState {
@@ -16,13 +19,14 @@ interface ConflictState {
 type Drafts = Partial<Record<ToolType, string>>;
 
 export function RulesEditor() {
-  const { rules, fetchRules, saveRules, loading } = useConfigStore();
+  const { tools, rules, fetchTools, fetchRules, saveRules, loading } = useConfigStore();
 
   const [activeTool, setActiveTool] = useState<ToolType>('ClaudeCode');
   const [drafts, setDrafts] = useState<Drafts>({});
   const [conflict, setConflict] = useState<ConflictState | null>(null);
   const baseRef = useRef<Partial<Record<ToolType, string>>>({});
 
+  useEffect(() => { fetchTools(); }, [fetchTools]);
   useEffect(() => { fetchRules(activeTool); }, [activeTool, fetchRules]);
 
   const serverContent = rules[activeTool] ?? '';
@@ -89,7 +93,7 @@ export function RulesEditor() {
   return (
     <div className="rules-editor">
       <div className="rules-tool-tabs">
-        {TOOLS.map((tool) => (
+        {tools.map((tool) => (
           <button
             key={tool}
             type="button"
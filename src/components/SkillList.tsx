@@ -1,5 +1,5 @@
-import { useEffect, useState } from 'react';
-import { useConfigStore, TOOLS, type ToolType, type Skill } from '../stores/configStore';
+import { useEffect, useState, type MouseEvent } from 'react';
+import { useConfigStore, type ToolType, type Skill } from '../stores/configStore';
 import { validateSkill } from './skillValidation';
 
 interface SkillListProps {
@@ -9,10 +9,27 @@ interface SkillListProps {
 }
 
 export function SkillList({ selectedTool, selectedName, onSelect }: SkillListProps) {
-  const { skills, fetchAllSkills, loading } = useConfigStore();
+  const {
+    tools, skills, skillLinkStates, claudePlugins, fetchAllSkills, fetchSkillLinkStates,
+    fetchClaudePlugins, unlinkSkill, loading,
+  } = useConfigStore();
   const [collapsed, setCollapsed] = useState<Partial<Record<ToolType, boolean>>>({});
 
-  useEffect(() => { fetchAllSkills(); }, [fetchAllSkills]);
+  useEffect(() => {
+    fetchAllSkills();
+    fetchSkillLinkStates();
+    fetchClaudePlugins();
+  }, [fetchAllSkills, fetchSkillLinkStates, fetchClaudePlugins]);
+
+  // Skills Claude Code plugins bring along; shown read-only.
+  const pluginSkills = (claudePlugins?.plugins ?? []).flatMap((plugin) =>
+    plugin.skills.map((skill) => ({ plugin, skill })));
+
+  const unlink = (e: MouseEvent, tool: ToolType, name: string) => {
+    e.stopPropagation();
+    // The store reports the error.
+    unlinkSkill(tool, name).then(() => fetchAllSkills(), () => {});
+  };
 
   const toggle = (tool: ToolType) =>
     setCollapsed((p) => ({ ...p, [tool]: !p[tool] }));
@@ -20,8 +37,9 @@ export function SkillList({ selectedTool, selectedName, onSelect }: SkillListPro
   return (
     <div className="mcp-list">
       {loading.skills > 0 && <p className="mcp-loading">Loading...</p>}
-      {TOOLS.map((tool) => {
+      {tools.map((tool) => {
         const items = skills[tool];
+        const fromPlugins = tool === 'ClaudeCode' ? pluginSkills : [];
         const isCollapsed = !!collapsed[tool];
         return (
           <section key={tool} className="mcp-tool-section">
@@ -29,15 +47,16 @@ export function SkillList({ selectedTool, selectedName, onSelect }: SkillListPro
               <button type="button" className="mcp-tool-toggle" onClick={() => toggle(tool)}>
                 <span className={`mcp-chevron${isCollapsed ? ' is-collapsed' : ''}`}>&#9660;</span>
                 <span className="mcp-tool-name">{tool}</span>
-                <span className="mcp-tool-count">{items.length}</span>
+                <span className="mcp-tool-count">{items.length + fromPlugins.length}</span>
               </button>
             </div>
             {!isCollapsed && (
               <div className="mcp-tool-body">
-                {items.length === 0 ? (
+                {items.length === 0 && fromPlugins.length === 0 ? (
                   <p className="mcp-empty">No skills found</p>
                 ) : items.map((s) => {
                   const v = validateSkill(s);
+                  const link = skillLinkStates[tool][s.name];
                   return (
                     <div
                       key={s.name}
@@ -54,14 +73,48 @@ export function SkillList({ selectedTool, selectedName, onSelect }: SkillListPro
                             title={v.valid ? 'Valid' : v.errors.join('; ')}
                           />
                           {s.name}
+                          {(link === 'linked' || link === 'diverged') && (
+                            <span
+                              className={`skill-link skill-link--${link}`}
+                              title={link === 'linked'
+                                ? 'Linked to the skill library'
+                                : 'Differs from the skill library copy'}
+                            >
+                              {link}
+                            </span>
+                          )}
                         </span>
                         {s.description && (
                           <span className="mcp-row-cmd" title={s.description}>{s.description}</span>
                         )}
                       </div>
+                      {link === 'linked' && (
+                        <button type="button" className="btn-secondary mcp-row-action"
+                          title="Replace the link with this tool's own copy"
+                          onClick={(e) => unlink(e, tool, s.name)}>
+                          Unlink
+                        </button>
+                      )}
                     </div>
                   );
                 })}
+                {fromPlugins.map(({ plugin, skill }) => (
+                  <div key={`${plugin.id}/${skill.name}`} className="mcp-row is-readonly"
+                    title={`Provided by plugin ${plugin.id}`}>
+                    <div className="mcp-row-info">
+                      <span className="mcp-row-name">
+                        {skill.name}
+                        <span className="mcp-origin">{plugin.id}</span>
+                      </span>
+                      {skill.description && (
+                        <span className="mcp-row-cmd" title={skill.description}>
+                          {skill.description}
+                        </span>
+                      )}
+                    </div>
+                    {!plugin.enabled && <span className="mcp-badge">OFF</span>}
+                  </div>
+                ))}
               </div>
             )}
           </section>
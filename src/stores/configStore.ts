@@ -2,8 +2,13 @@ import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 
-export const TOOLS = ['ClaudeCode', 'Codex', 'Gemini', 'OpenCode'] as const;
-export type ToolType = (typeof TOOLS)[number];
+/** Tool id from the backend registry; custom tools add their own. */
+export type ToolType = string;
+
+/** Built-in tools, used until `list_tools` has answered. */
+export const BUILTIN_TOOLS: ToolType[] = [
+  'ClaudeCode', 'Codex', 'Gemini', 'OpenCode', 'Zed', 'Qwen', 'IFlow',
+];
 
 export interface McpServer {
   name: string;
@@ -19,6 +24,209 @@ export interface Skill {
   description?: string;
   content: string;
   path: string;
+  license?: string;
+  allowed_tools?: string | string[];
+  metadata?: Record<string, unknown>;
+  version?: string;
+  model?: string;
+  extra?: Record<string, unknown>;
+  key_order?: string[];
+}
+
+export interface Command {
+  name: string;
+  description?: string;
+  argument_hint?: string;
+  model?: string;
+  content: string;
+  path?: string;
+}
+
+export interface Agent {
+  name: string;
+  description?: string;
+  tools?: string | string[];
+  model?: string;
+  content: string;
+  path?: string;
+  extra?: Record<string, unknown>;
+}
+
+interface AgentScan {
+  agents: Agent[];
+  diagnostics: SkillDiagnostic[];
+}
+
+export interface HookHandler {
+  type: 'command' | 'prompt' | string;
+  command?: string;
+  prompt?: string;
+  timeout?: number;
+  [key: string]: unknown;
+}
+
+export interface HookGroup {
+  matcher?: string;
+  hooks: HookHandler[];
+  [key: string]: unknown;
+}
+
+export type HookConfig = Record<string, HookGroup[]>;
+
+export interface HookFinding {
+  event: string;
+  group?: number | null;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+export interface HookRun {
+  stdout: string;
+  stderr: string;
+  exit_code?: number | null;
+  timed_out: boolean;
+}
+
+export type ApprovalPolicy = 'untrusted' | 'on-failure' | 'on-request' | 'never';
+export type SandboxMode = 'read-only' | 'workspace-write' | 'danger-full-access';
+
+/** Rules use Claude Code syntax, e.g. `Bash(git:*)`. */
+export interface Permissions {
+  allow: string[];
+  ask: string[];
+  deny: string[];
+  approval?: ApprovalPolicy | null;
+  sandbox?: SandboxMode | null;
+}
+
+export interface ToolPermissions {
+  tool: ToolType;
+  permissions?: Permissions | null;
+  error?: string | null;
+}
+
+export interface PermissionCopyResult {
+  permissions: Permissions;
+  warnings: string[];
+}
+
+export interface ModelProvider {
+  id: string;
+  name?: string | null;
+  base_url?: string | null;
+  env_key?: string | null;
+  extra: Record<string, unknown>;
+}
+
+export interface ModelSettings {
+  model?: string | null;
+  provider?: string | null;
+  providers: ModelProvider[];
+}
+
+export interface ModelSummary {
+  tool: ToolType;
+  model?: string | null;
+  provider?: string | null;
+  inherited: boolean;
+  error?: string | null;
+}
+
+export interface CodexProfile {
+  name: string;
+  model?: string | null;
+  model_provider?: string | null;
+  approval_policy?: ApprovalPolicy | null;
+  sandbox_mode?: SandboxMode | null;
+  extra: Record<string, unknown>;
+}
+
+export interface CodexProfiles {
+  default?: string | null;
+  profiles: CodexProfile[];
+}
+
+export type TrustLevel = 'trusted' | 'untrusted';
+
+export interface CodexTrustEntry {
+  path: string;
+  project_id?: number | null;
+  trust_level?: TrustLevel | null;
+  exists: boolean;
+}
+
+export interface GeminiExtension {
+  name: string;
+  version?: string | null;
+  description?: string | null;
+  path: string;
+  enabled: boolean;
+  mcp_servers: McpServer[];
+  context_files: string[];
+  commands: string[];
+  error?: string | null;
+}
+
+export interface ClaudeMarketplace {
+  name: string;
+  source?: string | null;
+  path?: string | null;
+}
+
+export interface ClaudePlugin {
+  id: string;
+  name: string;
+  marketplace?: string | null;
+  version?: string | null;
+  description?: string | null;
+  path: string;
+  enabled: boolean;
+  mcp_servers: McpServer[];
+  skills: Skill[];
+  commands: string[];
+  agents: string[];
+  hooks: string[];
+  error?: string | null;
+}
+
+export interface ClaudePlugins {
+  plugins: ClaudePlugin[];
+  marketplaces: ClaudeMarketplace[];
+  error?: string | null;
+}
+
+export type SettingsLayer = 'user' | 'project' | 'local' | 'managed';
+
+export interface SettingsLayerFile {
+  layer: SettingsLayer;
+  path: string;
+  exists: boolean;
+  settings: Record<string, unknown>;
+  error?: string | null;
+}
+
+export interface EffectiveSetting {
+  key: string;
+  value: unknown;
+  source: SettingsLayer;
+  layers: SettingsLayer[];
+}
+
+export interface LayeredSettings {
+  layers: SettingsLayerFile[];
+  effective: EffectiveSetting[];
+  local_gitignored?: boolean | null;
+}
+
+export type ScopeKind = 'global' | 'user' | 'project' | 'local';
+
+export interface ResolvedMcpServer {
+  server: McpServer;
+  scope: ScopeKind;
+  source?: string | null;
+  active: boolean;
+  shadowed_by?: ScopeKind | null;
+  note?: string | null;
 }
 
 export interface Project {
@@ -36,8 +244,107 @@ interface CopyResult {
   skipped: boolean;
 }
 
+export interface CommandCopyResult {
+  command: Command | null;
+  warnings: string[];
+  skipped: boolean;
+}
+
+export interface SkillDiagnostic {
+  path: string;
+  reason: string;
+  line?: number | null;
+}
+
+export interface SkillFinding {
+  skill: string;
+  rule: string;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+export interface SkillArchivePreview {
+  name: string;
+  description?: string | null;
+  files: string[];
+  exists: boolean;
+  findings: SkillFinding[];
+}
+
+export type SkillConflict = 'skip' | 'overwrite' | 'rename';
+
+export interface SkillImportResult {
+  name: string;
+  imported_as?: string | null;
+  skipped: boolean;
+  files: string[];
+}
+
+interface SkillScan {
+  skills: Skill[];
+  diagnostics: SkillDiagnostic[];
+}
+
 interface SkillCopyResult {
   skipped: boolean;
+  files: string[];
+  linked: boolean;
+}
+
+export type SkillCopyMode = 'copy' | 'link';
+export type SkillLinkState = 'linked' | 'synced' | 'diverged' | 'unmanaged';
+
+interface SkillLinkStatus {
+  tool: ToolType;
+  name: string;
+  state: SkillLinkState;
+}
+
+interface SkillLinkStates {
+  states: SkillLinkStatus[];
+  errors: string[];
+}
+
+export interface RenameTarget {
+  tool: ToolType;
+  project_path?: string | null;
+}
+
+export interface SkillSource {
+  id: number;
+  name: string;
+  url: string;
+  created_at: number;
+}
+
+export interface SourceSkill {
+  name: string;
+  description?: string | null;
+  path: string;
+}
+
+export interface InstalledSkill {
+  id: number;
+  source_id: number;
+  name: string;
+  skill_path: string;
+  tool: ToolType;
+  project_path?: string | null;
+  commit: string;
+  installed_at: number;
+  updated_at: number;
+}
+
+export interface SkillInstallResult {
+  path: string;
+  tool: ToolType;
+  installed?: InstalledSkill | null;
+  error?: string | null;
+}
+
+export interface SkillUpdate {
+  install: InstalledSkill;
+  head: string;
 }
 
 export interface ProjectConfigSummary {
@@ -47,66 +354,263 @@ export interface ProjectConfigSummary {
   has_rules: boolean;
 }
 
+export type Transport = 'stdio' | 'http' | 'sse';
+
+export interface ToolCapabilities {
+  mcp: {
+    transports: Transport[];
+    enabled: boolean;
+    enabled_project_only: boolean;
+    headers: boolean;
+    timeouts: boolean;
+    global: boolean;
+    project: boolean;
+  };
+  skills: { global: boolean; project: boolean; resources: boolean };
+  rules: { global: boolean; project: boolean; imports: boolean };
+  commands: {
+    global: boolean;
+    project: boolean;
+    arguments: string;
+    positional: boolean;
+    argument_hint: boolean;
+    model: boolean;
+  };
+  permissions: {
+    global: boolean;
+    project: boolean;
+    rules: boolean;
+    ask: boolean;
+    rule_tools: string[];
+    approvals: ApprovalPolicy[];
+    sandbox_modes: SandboxMode[];
+  };
+  models: { global: boolean; project: boolean; provider: boolean; providers: boolean };
+}
+
+export interface ToolDescriptor {
+  id: ToolType;
+  name: string;
+  format: 'Json' | 'Toml';
+  capabilities: ToolCapabilities;
+  global_dir: string | null;
+  project_dir: string | null;
+  builtin: boolean;
+}
+
+/** Display name of a tool, falling back to its id. */
+export const toolLabel = (descriptors: ToolDescriptor[], id: ToolType) =>
+  descriptors.find((d) => d.id === id)?.name ?? id;
+
 type ToolRecord<T> = Record<ToolType, T>;
 
 interface ConfigState {
+  tools: ToolType[];
+  toolDescriptors: ToolDescriptor[];
   mcpServers: ToolRecord<McpServer[]>;
   skills: ToolRecord<Skill[]>;
+  skillDiagnostics: ToolRecord<SkillDiagnostic[]>;
+  skillLinkStates: ToolRecord<Record<string, SkillLinkState>>;
   rules: ToolRecord<string>;
+  commands: ToolRecord<Command[]>;
+  agents: Agent[];
+  agentDiagnostics: SkillDiagnostic[];
+  hooks: HookConfig;
+  claudeSettings: LayeredSettings | null;
+  claudePlugins: ClaudePlugins | null;
+  geminiExtensions: GeminiExtension[];
+  permissions: ToolRecord<Permissions>;
+  modelSettings: ToolRecord<ModelSettings>;
+  codexProfiles: CodexProfiles;
   projects: Project[];
+  skillSources: SkillSource[];
   currentProjectPath: string | null;
-  loading: { mcp: number; skills: number; rules: number; projects: number };
+  loading: { mcp: number; skills: number; rules: number; commands: number; projects: number };
   error: string | null;
 }
 
 interface ConfigActions {
   setCurrentProject: (path: string | null) => void;
   clearError: () => void;
+  fetchTools: () => Promise<void>;
   // Projects
   fetchProjects: () => Promise<void>;
   addProject: (path: string) => Promise<Project>;
   removeProject: (id: number) => Promise<void>;
   detectProjectTools: (path: string) => Promise<ToolType[]>;
   getProjectConfigSummary: (path: string) => Promise<ProjectConfigSummary[]>;
+  getToolCapabilities: (tool: ToolType) => Promise<ToolCapabilities>;
   // MCP
   fetchMcpServers: (tool: ToolType) => Promise<void>;
   fetchAllMcpServers: () => Promise<void>;
   saveMcpServer: (tool: ToolType, server: McpServer) => Promise<void>;
   deleteMcpServer: (tool: ToolType, name: string) => Promise<void>;
+  renameMcpServer: (
+    tool: ToolType, oldName: string, newName: string, propagate: boolean,
+  ) => Promise<RenameTarget[]>;
   copyMcpServer: (from: ToolType, to: ToolType, name: string) => Promise<CopyResult>;
+  resolveEffectiveMcp: (tool: ToolType, projectPath: string) => Promise<ResolvedMcpServer[]>;
   // Skills
   fetchSkills: (tool: ToolType) => Promise<void>;
   fetchAllSkills: () => Promise<void>;
-  saveSkill: (tool: ToolType, skill: Skill) => Promise<void>;
+  saveSkill: (tool: ToolType, skill: Skill) => Promise<SkillFinding[]>;
+  lintSkills: (tool: ToolType) => Promise<SkillFinding[]>;
+  exportSkills: (tool: ToolType, names: string[], dest: string) => Promise<string[]>;
+  previewSkillArchive: (tool: ToolType, archive: string) => Promise<SkillArchivePreview[]>;
+  importSkillArchive: (
+    tool: ToolType, archive: string, conflict: SkillConflict,
+  ) => Promise<SkillImportResult[]>;
   deleteSkill: (tool: ToolType, name: string) => Promise<void>;
-  copySkill: (from: ToolType, to: ToolType, name: string) => Promise<SkillCopyResult>;
+  renameSkill: (
+    tool: ToolType, oldName: string, newName: string, propagate: boolean,
+  ) => Promise<RenameTarget[]>;
+  copySkill: (
+    from: ToolType, to: ToolType, name: string, mode?: SkillCopyMode,
+  ) => Promise<SkillCopyResult>;
+  fetchSkillLinkStates: () => Promise<void>;
+  linkSkill: (tool: ToolType, name: string) => Promise<void>;
+  unlinkSkill: (tool: ToolType, name: string) => Promise<void>;
+  // Skill sources
+  fetchSkillSources: () => Promise<void>;
+  addSkillSource: (url: string) => Promise<SkillSource>;
+  removeSkillSource: (id: number) => Promise<void>;
+  listSourceSkills: (sourceId: number) => Promise<SourceSkill[]>;
+  installSourceSkills: (
+    sourceId: number, paths: string[], tools: ToolType[],
+  ) => Promise<SkillInstallResult[]>;
+  checkSkillUpdates: () => Promise<SkillUpdate[]>;
+  previewSkillUpdate: (installId: number) => Promise<string>;
+  updateInstalledSkill: (installId: number, force?: boolean) => Promise<InstalledSkill>;
   // Rules
   fetchRules: (tool: ToolType) => Promise<void>;
   fetchAllRules: () => Promise<void>;
   saveRules: (tool: ToolType, content: string) => Promise<void>;
+  // Commands
+  fetchCommands: (tool: ToolType) => Promise<void>;
+  fetchAllCommands: () => Promise<void>;
+  saveCommand: (tool: ToolType, command: Command) => Promise<void>;
+  deleteCommand: (tool: ToolType, name: string) => Promise<void>;
+  copyCommand: (from: ToolType, to: ToolType, name: string) => Promise<CommandCopyResult>;
+  // Claude Code agents
+  fetchAgents: () => Promise<void>;
+  saveAgent: (agent: Agent) => Promise<string[]>;
+  deleteAgent: (name: string) => Promise<void>;
+  copyAgent: (name: string, fromProject: string | null, toProject: string | null) => Promise<boolean>;
+  // Claude Code hooks
+  fetchHooks: () => Promise<void>;
+  saveHooks: (hooks: HookConfig) => Promise<HookFinding[]>;
+  validateHooks: (hooks: HookConfig) => Promise<HookFinding[]>;
+  testHook: (
+    command: string, event: string, payload?: string, timeout?: number,
+  ) => Promise<HookRun>;
+  // Claude Code settings layers
+  fetchClaudeSettings: () => Promise<void>;
+  saveClaudeSetting: (layer: SettingsLayer, key: string, value: unknown | null) => Promise<void>;
+  gitignoreLocalSettings: () => Promise<boolean>;
+  // Permissions
+  fetchPermissions: (tool: ToolType) => Promise<void>;
+  savePermissions: (tool: ToolType, permissions: Permissions) => Promise<void>;
+  comparePermissions: () => Promise<ToolPermissions[]>;
+  copyPermissions: (from: ToolType, to: ToolType) => Promise<PermissionCopyResult>;
+  // Models
+  fetchModelSettings: (tool: ToolType) => Promise<void>;
+  saveModelSettings: (tool: ToolType, settings: ModelSettings) => Promise<void>;
+  getProjectModelSummary: (path: string) => Promise<ModelSummary[]>;
+  // Codex profiles
+  fetchCodexProfiles: () => Promise<void>;
+  saveCodexProfile: (profile: CodexProfile) => Promise<void>;
+  deleteCodexProfile: (name: string) => Promise<void>;
+  cloneCodexProfile: (name: string, newName: string) => Promise<CodexProfile>;
+  setDefaultCodexProfile: (name: string | null) => Promise<void>;
+  getCodexEffectiveConfig: (profile?: string | null) => Promise<Record<string, unknown>>;
+  // Codex project trust
+  getCodexTrust: () => Promise<CodexTrustEntry[]>;
+  setCodexTrust: (path: string, trustLevel: TrustLevel | null) => Promise<void>;
+  trustAllProjects: () => Promise<string[]>;
+  // Claude Code plugins
+  fetchClaudePlugins: () => Promise<void>;
+  setClaudePluginEnabled: (id: string, enabled: boolean) => Promise<void>;
+  // Gemini extensions
+  fetchGeminiExtensions: () => Promise<void>;
+  setGeminiExtensionEnabled: (name: string, enabled: boolean) => Promise<void>;
+  installGeminiExtension: (source: string) => Promise<GeminiExtension>;
+  uninstallGeminiExtension: (name: string) => Promise<void>;
   // Events
   setupEventListeners: () => Promise<UnlistenFn>;
 }
 
-const emptyToolRecord = <T>(factory: () => T): ToolRecord<T> => ({
-  ClaudeCode: factory(), Codex: factory(), Gemini: factory(), OpenCode: factory(),
-});
+const emptyToolRecord = <T>(factory: () => T, tools = BUILTIN_TOOLS): ToolRecord<T> =>
+  Object.fromEntries(tools.map((t) => [t, factory()]));
+
+/** Adds empty entries for tools a record does not have yet. */
+const extendToolRecord = <T>(record: ToolRecord<T>, tools: ToolType[], factory: () => T) =>
+  ({ ...emptyToolRecord(factory, tools), ...record });
+
+let toolsRequest: Promise<void> | null = null;
+
+/** The registered tools, fetched once before the first per-tool fetch. */
+const knownTools = async (get: () => ConfigState & ConfigActions) => {
+  await (toolsRequest ?? get().fetchTools());
+  return get().tools;
+};
 
 let eventListenerPromise: Promise<UnlistenFn> | null = null;
 let eventListenerRefs = 0;
 
 export const useConfigStore = create<ConfigState & ConfigActions>((set, get) => ({
+  tools: BUILTIN_TOOLS,
+  toolDescriptors: [],
   mcpServers: emptyToolRecord(() => []),
   skills: emptyToolRecord(() => []),
+  skillDiagnostics: emptyToolRecord(() => []),
+  skillLinkStates: emptyToolRecord(() => ({})),
   rules: emptyToolRecord(() => ''),
+  commands: emptyToolRecord(() => []),
+  agents: [],
+  agentDiagnostics: [],
+  hooks: {},
+  claudeSettings: null,
+  claudePlugins: null,
+  geminiExtensions: [],
+  permissions: emptyToolRecord(() => ({ allow: [], ask: [], deny: [] })),
+  modelSettings: emptyToolRecord(() => ({ providers: [] })),
+  codexProfiles: { default: null, profiles: [] },
   projects: [],
+  skillSources: [],
   currentProjectPath: null,
-  loading: { mcp: 0, skills: 0, rules: 0, projects: 0 },
+  loading: { mcp: 0, skills: 0, rules: 0, commands: 0, projects: 0 },
   error: null,
 
   setCurrentProject: (path) => set({ currentProjectPath: path }),
   clearError: () => set({ error: null }),
 
+  fetchTools: async () => {
+    toolsRequest = (async () => {
+      try {
+        const descriptors = await invoke<ToolDescriptor[]>('list_tools');
+        const tools = descriptors.map((d) => d.id);
+        set((s) => ({
+          tools,
+          toolDescriptors: descriptors,
+          mcpServers: extendToolRecord(s.mcpServers, tools, () => []),
+          skills: extendToolRecord(s.skills, tools, () => []),
+          skillDiagnostics: extendToolRecord(s.skillDiagnostics, tools, () => []),
+          skillLinkStates: extendToolRecord(s.skillLinkStates, tools, () => ({})),
+          rules: extendToolRecord(s.rules, tools, () => ''),
+          commands: extendToolRecord(s.commands, tools, () => []),
+          permissions: extendToolRecord(
+            s.permissions, tools, () => ({ allow: [], ask: [], deny: [] }),
+          ),
+          modelSettings: extendToolRecord(s.modelSettings, tools, () => ({ providers: [] })),
+        }));
+      } catch (e) {
+        toolsRequest = null;
+        set({ error: String(e) });
+      }
+    })();
+    await toolsRequest;
+  },
+
   fetchProjects: async () => {
     set((s) => ({ loading: { ...s.loading, projects: s.loading.projects + 1 }, error: null }));
     try {
@@ -152,6 +656,10 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     return invoke<ProjectConfigSummary[]>('get_project_config_summary', { path });
   },
 
+  getToolCapabilities: async (tool) => {
+    return invoke<ToolCapabilities>('get_tool_capabilities', { tool });
+  },
+
   fetchMcpServers: async (tool) => {
     const scopePath = get().currentProjectPath;
     set((s) => ({ loading: { ...s.loading, mcp: s.loading.mcp + 1 }, error: null }));
@@ -170,7 +678,8 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
   },
 
   fetchAllMcpServers: async () => {
-    await Promise.all(TOOLS.map((t) => get().fetchMcpServers(t)));
+    const tools = await knownTools(get);
+    await Promise.all(tools.map((t) => get().fetchMcpServers(t)));
   },
 
   saveMcpServer: async (tool, server) => {
@@ -209,6 +718,23 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     }
   },
 
+  renameMcpServer: async (tool, oldName, newName, propagate) => {
+    try {
+      const targets = await invoke<RenameTarget[]>('rename_mcp_server', {
+        tool, oldName, newName, propagate, projectPath: get().currentProjectPath,
+      });
+      await Promise.all([...new Set(targets.map((t) => t.tool))].map((t) => get().fetchMcpServers(t)));
+      return targets;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  resolveEffectiveMcp: async (tool, projectPath) => {
+    return invoke<ResolvedMcpServer[]>('resolve_effective_mcp', { tool, projectPath });
+  },
+
   copyMcpServer: async (from, to, name) => {
     const scopePath = get().currentProjectPath;
     try {
@@ -233,11 +759,14 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     const scopePath = get().currentProjectPath;
     set((s) => ({ loading: { ...s.loading, skills: s.loading.skills + 1 }, error: null }));
     try {
-      const skills = await invoke<Skill[]>('get_skills', {
+      const scan = await invoke<SkillScan>('get_skills', {
         tool, projectPath: scopePath,
       });
       if (get().currentProjectPath === scopePath) {
-        set((s) => ({ skills: { ...s.skills, [tool]: skills } }));
+        set((s) => ({
+          skills: { ...s.skills, [tool]: scan.skills },
+          skillDiagnostics: { ...s.skillDiagnostics, [tool]: scan.diagnostics },
+        }));
       }
     } catch (e) {
       set({ error: String(e) });
@@ -247,7 +776,8 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
   },
 
   fetchAllSkills: async () => {
-    await Promise.all(TOOLS.map((t) => get().fetchSkills(t)));
+    const tools = await knownTools(get);
+    await Promise.all(tools.map((t) => get().fetchSkills(t)));
   },
 
   saveSkill: async (tool, skill) => {
@@ -258,7 +788,7 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
       : [...prev, skill];
     set((s) => ({ skills: { ...s.skills, [tool]: updated }, error: null }));
     try {
-      await invoke('save_skill', { tool, skill, projectPath: scopePath });
+      return await invoke<SkillFinding[]>('save_skill', { tool, skill, projectPath: scopePath });
     } catch (e) {
       if (get().currentProjectPath === scopePath) {
         await get().fetchSkills(tool);
@@ -268,6 +798,33 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     }
   },
 
+  lintSkills: async (tool) => {
+    return invoke<SkillFinding[]>('lint_skills', { tool, projectPath: get().currentProjectPath });
+  },
+
+  exportSkills: async (tool, names, dest) => {
+    return invoke<string[]>('export_skills', {
+      tool, names, dest, projectPath: get().currentProjectPath,
+    });
+  },
+
+  previewSkillArchive: async (tool, archive) => {
+    return invoke<SkillArchivePreview[]>('preview_skill_archive', {
+      tool, archive, projectPath: get().currentProjectPath,
+    });
+  },
+
+  importSkillArchive: async (tool, archive, conflict) => {
+    const scopePath = get().currentProjectPath;
+    const results = await invoke<SkillImportResult[]>('import_skill_archive', {
+      tool, archive, conflict, projectPath: scopePath,
+    });
+    if (get().currentProjectPath === scopePath) {
+      await get().fetchSkills(tool);
+    }
+    return results;
+  },
+
   deleteSkill: async (tool, name) => {
     const scopePath = get().currentProjectPath;
     const prev = get().skills[tool];
@@ -286,14 +843,28 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     }
   },
 
-  copySkill: async (from, to, name) => {
+  renameSkill: async (tool, oldName, newName, propagate) => {
+    try {
+      const targets = await invoke<RenameTarget[]>('rename_skill', {
+        tool, oldName, newName, propagate, projectPath: get().currentProjectPath,
+      });
+      await Promise.all([...new Set(targets.map((t) => t.tool))].map((t) => get().fetchSkills(t)));
+      return targets;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  copySkill: async (from, to, name, mode = 'copy') => {
     const scopePath = get().currentProjectPath;
     try {
       const result = await invoke<SkillCopyResult>('copy_skill_to_tool', {
-        fromTool: from, toTool: to, skillName: name, projectPath: scopePath,
+        fromTool: from, toTool: to, skillName: name, projectPath: scopePath, mode,
       });
       if (!result.skipped && get().currentProjectPath === scopePath) {
         await get().fetchSkills(to);
+        await get().fetchSkillLinkStates();
       }
       return result;
     } catch (e) {
@@ -302,6 +873,92 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     }
   },
 
+  fetchSkillLinkStates: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const { states, errors } = await invoke<SkillLinkStates>('get_skill_link_states', {
+        projectPath: scopePath,
+      });
+      if (get().currentProjectPath !== scopePath) return;
+      const byTool = emptyToolRecord<Record<string, SkillLinkState>>(() => ({}), get().tools);
+      for (const s of states) {
+        if (s.tool in byTool) byTool[s.tool][s.name] = s.state;
+      }
+      set({ skillLinkStates: byTool });
+      if (errors.length > 0) set({ error: errors.join('; ') });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  linkSkill: async (tool, name) => {
+    try {
+      await invoke('link_skill', { tool, name, projectPath: get().currentProjectPath });
+      await get().fetchSkillLinkStates();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  unlinkSkill: async (tool, name) => {
+    try {
+      await invoke('unlink_skill', { tool, name, projectPath: get().currentProjectPath });
+      await get().fetchSkillLinkStates();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchSkillSources: async () => {
+    try {
+      set({ skillSources: await invoke<SkillSource[]>('list_skill_sources') });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  addSkillSource: async (url) => {
+    const source = await invoke<SkillSource>('add_skill_source', { url });
+    await get().fetchSkillSources();
+    return source;
+  },
+
+  removeSkillSource: async (id) => {
+    await invoke('remove_skill_source', { id });
+    set((s) => ({ skillSources: s.skillSources.filter((x) => x.id !== id) }));
+  },
+
+  listSourceSkills: async (sourceId) => {
+    return invoke<SourceSkill[]>('list_source_skills', { sourceId });
+  },
+
+  installSourceSkills: async (sourceId, paths, tools) => {
+    const scopePath = get().currentProjectPath;
+    const results = await invoke<SkillInstallResult[]>('install_source_skills', {
+      sourceId, paths, tools, projectPath: scopePath,
+    });
+    if (get().currentProjectPath === scopePath) {
+      await Promise.all(tools.map((t) => get().fetchSkills(t)));
+    }
+    return results;
+  },
+
+  checkSkillUpdates: async () => {
+    return invoke<SkillUpdate[]>('check_skill_updates');
+  },
+
+  previewSkillUpdate: async (installId) => {
+    return invoke<string>('preview_skill_update', { installId });
+  },
+
+  updateInstalledSkill: async (installId, force = false) => {
+    const installed = await invoke<InstalledSkill>('update_installed_skill', { installId, force });
+    await get().fetchSkills(installed.tool);
+    return installed;
+  },
+
   fetchRules: async (tool) => {
     const scopePath = get().currentProjectPath;
     set((s) => ({ loading: { ...s.loading, rules: s.loading.rules + 1 }, error: null }));
@@ -320,7 +977,8 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
   },
 
   fetchAllRules: async () => {
-    await Promise.all(TOOLS.map((t) => get().fetchRules(t)));
+    const tools = await knownTools(get);
+    await Promise.all(tools.map((t) => get().fetchRules(t)));
   },
 
   saveRules: async (tool, content) => {
@@ -337,16 +995,438 @@ export const useConfigStore = create<ConfigState & ConfigActions>((set, get) =>
     }
   },
 
+  fetchCommands: async (tool) => {
+    const scopePath = get().currentProjectPath;
+    set((s) => ({ loading: { ...s.loading, commands: s.loading.commands + 1 }, error: null }));
+    try {
+      const commands = await invoke<Command[]>('get_commands', {
+        tool, projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) {
+        set((s) => ({ commands: { ...s.commands, [tool]: commands } }));
+      }
+    } catch (e) {
+      set({ error: String(e) });
+    } finally {
+      set((s) => ({ loading: { ...s.loading, commands: s.loading.commands - 1 } }));
+    }
+  },
+
+  fetchAllCommands: async () => {
+    const tools = await knownTools(get);
+    await Promise.all(tools.map((t) => get().fetchCommands(t)));
+  },
+
+  saveCommand: async (tool, command) => {
+    const scopePath = get().currentProjectPath;
+    const prev = get().commands[tool];
+    const updated = prev.some((c) => c.name === command.name)
+      ? prev.map((c) => (c.name === command.name ? command : c))
+      : [...prev, command];
+    set((s) => ({ commands: { ...s.commands, [tool]: updated }, error: null }));
+    try {
+      await invoke('save_command', { tool, command, projectPath: scopePath });
+    } catch (e) {
+      if (get().currentProjectPath === scopePath) {
+        await get().fetchCommands(tool);
+      }
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  deleteCommand: async (tool, name) => {
+    const scopePath = get().currentProjectPath;
+    const prev = get().commands[tool];
+    set((s) => ({
+      commands: { ...s.commands, [tool]: prev.filter((c) => c.name !== name) },
+      error: null,
+    }));
+    try {
+      await invoke('delete_command', { tool, name, projectPath: scopePath });
+    } catch (e) {
+      if (get().currentProjectPath === scopePath) {
+        await get().fetchCommands(tool);
+      }
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  copyCommand: async (from, to, name) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const result = await invoke<CommandCopyResult>('copy_command_to_tool', {
+        fromTool: from, toTool: to, name, projectPath: scopePath,
+      });
+      if (!result.skipped && get().currentProjectPath === scopePath) {
+        await get().fetchCommands(to);
+      }
+      return result;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchAgents: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const scan = await invoke<AgentScan>('get_agents', { projectPath: scopePath });
+      if (get().currentProjectPath === scopePath) {
+        set({ agents: scan.agents, agentDiagnostics: scan.diagnostics });
+      }
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  saveAgent: async (agent) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const warnings = await invoke<string[]>('save_agent', { agent, projectPath: scopePath });
+      await get().fetchAgents();
+      return warnings;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  deleteAgent: async (name) => {
+    const scopePath = get().currentProjectPath;
+    set((s) => ({ agents: s.agents.filter((a) => a.name !== name), error: null }));
+    try {
+      await invoke('delete_agent', { name, projectPath: scopePath });
+    } catch (e) {
+      await get().fetchAgents();
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  copyAgent: async (name, fromProject, toProject) => {
+    try {
+      const copied = await invoke<boolean>('copy_agent', { name, fromProject, toProject });
+      if (copied && get().currentProjectPath === toProject) {
+        await get().fetchAgents();
+      }
+      return copied;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchHooks: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const hooks = await invoke<HookConfig>('get_hooks', { projectPath: scopePath });
+      if (get().currentProjectPath === scopePath) set({ hooks });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  saveHooks: async (hooks) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const findings = await invoke<HookFinding[]>('save_hooks', { hooks, projectPath: scopePath });
+      if (get().currentProjectPath === scopePath) set({ hooks, error: null });
+      return findings;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  validateHooks: async (hooks) => {
+    return invoke<HookFinding[]>('validate_hook_config', {
+      hooks, projectPath: get().currentProjectPath,
+    });
+  },
+
+  testHook: async (command, event, payload, timeout) => {
+    return invoke<HookRun>('test_hook', {
+      command, event, payload, timeout, projectPath: get().currentProjectPath,
+    });
+  },
+
+  fetchClaudeSettings: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const claudeSettings = await invoke<LayeredSettings>('get_claude_settings', {
+        projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) set({ claudeSettings });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  saveClaudeSetting: async (layer, key, value) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('save_claude_setting', { layer, key, value, projectPath: scopePath });
+      await get().fetchClaudeSettings();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  gitignoreLocalSettings: async () => {
+    const scopePath = get().currentProjectPath;
+    if (!scopePath) throw new Error('No project selected');
+    try {
+      const added = await invoke<boolean>('gitignore_local_settings', { projectPath: scopePath });
+      await get().fetchClaudeSettings();
+      return added;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchPermissions: async (tool) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const permissions = await invoke<Permissions>('get_permissions', {
+        tool, projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) {
+        set((s) => ({ permissions: { ...s.permissions, [tool]: permissions } }));
+      }
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  savePermissions: async (tool, permissions) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('save_permissions', { tool, permissions, projectPath: scopePath });
+      if (get().currentProjectPath === scopePath) {
+        set((s) => ({ permissions: { ...s.permissions, [tool]: permissions }, error: null }));
+      }
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  comparePermissions: async () => {
+    return invoke<ToolPermissions[]>('compare_permissions', {
+      projectPath: get().currentProjectPath,
+    });
+  },
+
+  copyPermissions: async (from, to) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const result = await invoke<PermissionCopyResult>('copy_permissions', {
+        fromTool: from, toTool: to, projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) {
+        set((s) => ({ permissions: { ...s.permissions, [to]: result.permissions } }));
+      }
+      return result;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchModelSettings: async (tool) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const settings = await invoke<ModelSettings>('get_model_settings', {
+        tool, projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) {
+        set((s) => ({ modelSettings: { ...s.modelSettings, [tool]: settings } }));
+      }
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  saveModelSettings: async (tool, settings) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('save_model_settings', { tool, settings, projectPath: scopePath });
+      if (get().currentProjectPath === scopePath) {
+        await get().fetchModelSettings(tool);
+      }
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  getProjectModelSummary: async (path) => {
+    return invoke<ModelSummary[]>('get_project_model_summary', { path });
+  },
+
+  fetchCodexProfiles: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const codexProfiles = await invoke<CodexProfiles>('get_codex_profiles', {
+        projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) set({ codexProfiles });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  saveCodexProfile: async (profile) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('save_codex_profile', { profile, projectPath: scopePath });
+      await get().fetchCodexProfiles();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  deleteCodexProfile: async (name) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('delete_codex_profile', { name, projectPath: scopePath });
+      await get().fetchCodexProfiles();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  cloneCodexProfile: async (name, newName) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const profile = await invoke<CodexProfile>('clone_codex_profile', {
+        name, newName, projectPath: scopePath,
+      });
+      await get().fetchCodexProfiles();
+      return profile;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  setDefaultCodexProfile: async (name) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('set_default_codex_profile', { name, projectPath: scopePath });
+      await get().fetchCodexProfiles();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  getCodexEffectiveConfig: async (profile) => {
+    return invoke<Record<string, unknown>>('get_codex_effective_config', {
+      profile, projectPath: get().currentProjectPath,
+    });
+  },
+
+  getCodexTrust: async () => {
+    return invoke<CodexTrustEntry[]>('get_codex_trust');
+  },
+
+  setCodexTrust: async (path, trustLevel) => {
+    try {
+      await invoke('set_codex_trust', { path, trustLevel });
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  trustAllProjects: async () => {
+    try {
+      return await invoke<string[]>('trust_all_projects');
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchClaudePlugins: async () => {
+    const scopePath = get().currentProjectPath;
+    try {
+      const claudePlugins = await invoke<ClaudePlugins>('get_claude_plugins', {
+        projectPath: scopePath,
+      });
+      if (get().currentProjectPath === scopePath) set({ claudePlugins });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  setClaudePluginEnabled: async (id, enabled) => {
+    const scopePath = get().currentProjectPath;
+    try {
+      await invoke('set_claude_plugin_enabled', { id, enabled, projectPath: scopePath });
+      await get().fetchClaudePlugins();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  fetchGeminiExtensions: async () => {
+    try {
+      set({ geminiExtensions: await invoke<GeminiExtension[]>('get_gemini_extensions') });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  setGeminiExtensionEnabled: async (name, enabled) => {
+    try {
+      await invoke('set_gemini_extension_enabled', { name, enabled });
+      await get().fetchGeminiExtensions();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  installGeminiExtension: async (source) => {
+    try {
+      const extension = await invoke<GeminiExtension>('install_gemini_extension', { source });
+      await get().fetchGeminiExtensions();
+      return extension;
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
+  uninstallGeminiExtension: async (name) => {
+    try {
+      await invoke('uninstall_gemini_extension', { name });
+      await get().fetchGeminiExtensions();
+    } catch (e) {
+      set({ error: String(e) });
+      throw e;
+    }
+  },
+
   setupEventListeners: async () => {
     eventListenerRefs++;
     if (!eventListenerPromise) {
       eventListenerPromise = listen<{ tool: string; kind: string }>('config-changed', (event) => {
         const { tool, kind } = event.payload;
-        if (!TOOLS.includes(tool as ToolType)) return;
-        const t = tool as ToolType;
-        if (kind === 'mcp') get().fetchMcpServers(t);
-        else if (kind === 'skills') get().fetchSkills(t);
-        else if (kind === 'rules') get().fetchRules(t);
+        if (!get().tools.includes(tool)) return;
+        if (kind === 'mcp') get().fetchMcpServers(tool);
+        else if (kind === 'skills') get().fetchSkills(tool);
+        else if (kind === 'rules') get().fetchRules(tool);
+        else if (kind === 'commands') get().fetchCommands(tool);
       });
     }
     try {